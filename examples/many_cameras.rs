@@ -0,0 +1,49 @@
+//! Headless benchmark of smoothing many rigs at once. Run with `--parallel` to smooth them in parallel and compare the
+//! frame times that get logged.
+
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+};
+use smooth_bevy_cameras::{
+    LookTransform, LookTransformBundle, LookTransformPlugin, SmoothingSettings, Smoother,
+};
+
+const NUM_RIGS: usize = 10_000;
+
+fn main() {
+    let parallel = std::env::args().any(|a| a == "--parallel");
+
+    App::new()
+        .insert_resource(SmoothingSettings {
+            parallel_batch_size: parallel.then_some(256),
+        })
+        .add_plugins(MinimalPlugins)
+        .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_plugin(LogDiagnosticsPlugin::default())
+        .add_plugin(LookTransformPlugin)
+        .add_startup_system(setup)
+        .add_system(move_targets)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    for i in 0..NUM_RIGS {
+        let eye = DVec3::new(i as f64, 5.0, 5.0);
+        commands.spawn((
+            LookTransformBundle {
+                transform: LookTransform::new(eye, DVec3::new(i as f64, 0.0, 0.0)),
+                smoother: Smoother::new(0.9),
+            },
+            Transform::from_translation(eye),
+        ));
+    }
+}
+
+/// Keep every rig moving so none of them settle.
+fn move_targets(time: Res<Time>, mut rigs: Query<&mut LookTransform>) {
+    let offset = time.elapsed_seconds_f64().sin();
+    for mut rig in rigs.iter_mut() {
+        rig.target.y = offset;
+    }
+}
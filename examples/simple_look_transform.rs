@@ -39,10 +39,7 @@ fn setup(
 
     commands
         .spawn(LookTransformBundle {
-            transform: LookTransform {
-                eye: DVec3::new(-2.0, 2.5, 5.0),
-                target: DVec3::new(0.0, 0.5, 0.0),
-            },
+            transform: LookTransform::new(DVec3::new(-2.0, 2.5, 5.0), DVec3::new(0.0, 0.5, 0.0)),
             smoother: Smoother::new(0.9),
         })
         .insert(Camera3dBundle {
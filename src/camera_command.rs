@@ -0,0 +1,237 @@
+use crate::{
+    controllers::{
+        fps::FpsCameraController, map::MapCameraController, orbit::OrbitCameraController,
+        spectator::SpectatorCameraController, unreal::UnrealCameraController, CameraControlSystem,
+    },
+    LookAngles, LookTransform, LookTransformSystem,
+};
+
+use bevy::{app::prelude::*, ecs::prelude::*, math::prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// Applies `CameraCommand` events to the cameras whose controllers are enabled.
+pub struct CameraCommandPlugin;
+
+impl Plugin for CameraCommandPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CameraCommand>().add_system(
+            camera_command_system
+                .after(CameraControlSystem::Control)
+                .before(LookTransformSystem::Locks),
+        );
+    }
+}
+
+/// Drives the cameras with an enabled FPS, map, orbit, spectator or Unreal controller, for an in-game console or a
+/// remote debugger. Changes go through the `LookTransform`, so they are smoothed like any other motion. Angles are in
+/// degrees.
+///
+/// With the `console_commands` feature, commands also parse from strings like `setpos 0 10 -5`, `setangles 90 -15`,
+/// `setspeed 4`, `noclip` or `noclip 0`, and `lookat 0 0 0`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum CameraCommand {
+    /// Move the eye here, keeping the look direction.
+    SetPos(DVec3),
+    /// Look in the direction with this yaw and pitch, like `LookAngles`, keeping the eye in place.
+    SetAngles { yaw: f64, pitch: f64 },
+    /// Look at this point, keeping the eye in place.
+    LookAt(DVec3),
+    /// Move to this transform, e.g. one synced from an external tool.
+    SetTransform(LookTransform),
+    /// Set the translation speed of the FPS, spectator and Unreal controllers.
+    SetSpeed(f64),
+    /// Set whether the spectator controller passes through geometry, or flip it with `None`. See `ghost`.
+    NoClip(Option<bool>),
+}
+
+impl CameraCommand {
+    /// Applies the command to `transform`. Commands that only change controller settings leave it unchanged.
+    pub fn apply(&self, transform: &mut LookTransform) {
+        match *self {
+            CameraCommand::SetPos(position) => {
+                let offset = position - transform.eye;
+                transform.eye += offset;
+                transform.target += offset;
+            }
+            CameraCommand::SetAngles { yaw, pitch } => {
+                let mut look_angles = LookAngles::default();
+                look_angles.set_yaw(yaw.to_radians());
+                look_angles.set_pitch(pitch.to_radians());
+                let radius = transform.radius().max(1.0);
+                transform.target =
+                    transform.eye + radius * look_angles.unit_vector_with_up(transform.up);
+            }
+            CameraCommand::LookAt(target) => {
+                if target != transform.eye {
+                    transform.target = target;
+                }
+            }
+            CameraCommand::SetTransform(new_transform) => {
+                *transform = new_transform;
+            }
+            CameraCommand::SetSpeed(_) | CameraCommand::NoClip(_) => {}
+        }
+    }
+}
+
+#[cfg(feature = "console_commands")]
+pub use parse::*;
+
+#[cfg(feature = "console_commands")]
+mod parse {
+    use super::CameraCommand;
+
+    use bevy::math::DVec3;
+    use std::{fmt, str::FromStr};
+
+    /// Why a string isn't a `CameraCommand`.
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum ParseCameraCommandError {
+        Empty,
+        UnknownCommand(String),
+        /// The command's arguments are missing, extra or not numbers
+        BadArguments(String),
+    }
+
+    impl fmt::Display for ParseCameraCommandError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Self::Empty => write!(f, "empty camera command"),
+                Self::UnknownCommand(name) => write!(f, "unknown camera command `{}`", name),
+                Self::BadArguments(name) => write!(f, "bad arguments to camera command `{}`", name),
+            }
+        }
+    }
+
+    impl std::error::Error for ParseCameraCommandError {}
+
+    impl FromStr for CameraCommand {
+        type Err = ParseCameraCommandError;
+
+        /// Parses a command name, case insensitively, followed by its arguments separated by whitespace.
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut words = s.split_whitespace();
+            let name = words
+                .next()
+                .ok_or(ParseCameraCommandError::Empty)?
+                .to_ascii_lowercase();
+            let bad_arguments = || ParseCameraCommandError::BadArguments(name.clone());
+            let args = words
+                .map(str::parse::<f64>)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| bad_arguments())?;
+
+            match (name.as_str(), args.as_slice()) {
+                ("setpos", &[x, y, z]) => Ok(CameraCommand::SetPos(DVec3::new(x, y, z))),
+                ("setangles", &[yaw, pitch]) => Ok(CameraCommand::SetAngles { yaw, pitch }),
+                ("lookat", &[x, y, z]) => Ok(CameraCommand::LookAt(DVec3::new(x, y, z))),
+                ("setspeed", &[speed]) => Ok(CameraCommand::SetSpeed(speed)),
+                ("noclip", &[]) => Ok(CameraCommand::NoClip(None)),
+                ("noclip", &[on]) => Ok(CameraCommand::NoClip(Some(on != 0.0))),
+                ("setpos" | "setangles" | "lookat" | "setspeed" | "noclip", _) => {
+                    Err(bad_arguments())
+                }
+                _ => Err(ParseCameraCommandError::UnknownCommand(name.clone())),
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn camera_command_system(
+    mut commands: EventReader<CameraCommand>,
+    mut cameras: Query<(
+        &mut LookTransform,
+        Option<&mut FpsCameraController>,
+        Option<&mut MapCameraController>,
+        Option<&mut OrbitCameraController>,
+        Option<&mut SpectatorCameraController>,
+        Option<&mut UnrealCameraController>,
+    )>,
+) {
+    for command in commands.iter() {
+        for (mut transform, mut fps, map, orbit, mut spectator, mut unreal) in cameras.iter_mut() {
+            let enabled = fps.as_ref().map_or(false, |c| c.enabled)
+                || map.map_or(false, |c| c.enabled)
+                || orbit.map_or(false, |c| c.enabled)
+                || spectator.as_ref().map_or(false, |c| c.enabled)
+                || unreal.as_ref().map_or(false, |c| c.enabled);
+            if !enabled {
+                continue;
+            }
+
+            match *command {
+                CameraCommand::SetSpeed(speed) => {
+                    if let Some(fps) = fps.as_mut() {
+                        fps.translate_sensitivity = speed;
+                    }
+                    if let Some(spectator) = spectator.as_mut() {
+                        spectator.translate_sensitivity = speed;
+                    }
+                    if let Some(unreal) = unreal.as_mut() {
+                        unreal.keyboard_mvmt_sensitivity = speed;
+                    }
+                }
+                CameraCommand::NoClip(ghost) => {
+                    if let Some(spectator) = spectator.as_mut() {
+                        spectator.ghost = ghost.unwrap_or(!spectator.ghost);
+                    }
+                }
+                _ => command.apply(&mut transform),
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_apply_camera_command() {
+        let mut transform = LookTransform::new(DVec3::ZERO, DVec3::new(0.0, 0.0, 2.0));
+
+        CameraCommand::SetPos(DVec3::new(1.0, 2.0, 3.0)).apply(&mut transform);
+        assert_eq!(transform.eye, DVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(transform.target, DVec3::new(1.0, 2.0, 5.0));
+
+        CameraCommand::SetAngles {
+            yaw: 90.0,
+            pitch: 0.0,
+        }
+        .apply(&mut transform);
+        assert_relative_eq!(transform.radius(), 2.0);
+        assert!(transform
+            .look_direction()
+            .unwrap()
+            .abs_diff_eq(DVec3::X, 1e-9));
+    }
+
+    #[cfg(feature = "console_commands")]
+    #[test]
+    fn test_parse_camera_command() {
+        assert_eq!(
+            "setpos 1 2 -3".parse(),
+            Ok(CameraCommand::SetPos(DVec3::new(1.0, 2.0, -3.0)))
+        );
+        assert_eq!("NoClip".parse(), Ok(CameraCommand::NoClip(None)));
+        assert_eq!("noclip 0".parse(), Ok(CameraCommand::NoClip(Some(false))));
+        assert_eq!(
+            "setspeed fast".parse::<CameraCommand>(),
+            Err(ParseCameraCommandError::BadArguments("setspeed".into()))
+        );
+        assert_eq!(
+            "fly".parse::<CameraCommand>(),
+            Err(ParseCameraCommandError::UnknownCommand("fly".into()))
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{
+    controllers::orbit::OrbitCameraController, up_axis_rotation, LookAngles, LookTransform,
+};
+
+use bevy::{
+    app::prelude::*,
+    asset::{Assets, Handle},
+    ecs::prelude::*,
+    math::prelude::*,
+    pbr::{NotShadowCaster, PbrBundle, StandardMaterial},
+    render::{color::Color, mesh::Mesh, render_resource::PrimitiveTopology},
+    transform::{components::Transform, TransformSystem},
+    utils::HashMap,
+};
+
+const CIRCLE_SEGMENTS: usize = 48;
+
+/// Draws the rig of every `LookTransform` as lines: the eye and target points, the look ray, the sphere the eye moves on
+/// around the target, and the pitch and yaw limits of orbit cameras. See `CameraDebugSettings`.
+pub struct CameraDebugPlugin;
+
+impl Plugin for CameraDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraDebugSettings>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                camera_debug_system.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// What the `CameraDebugPlugin` draws, and in which colors.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct CameraDebugSettings {
+    pub enabled: bool,
+    /// Half the width of the crosses marking the eye and target
+    pub marker_size: f64,
+    pub eye_color: Color,
+    pub target_color: Color,
+    pub ray_color: Color,
+    /// Color of the circles around the target at the current radius
+    pub radius_color: Color,
+    /// Color of the pitch cones and yaw arc of orbit cameras
+    pub limit_color: Color,
+}
+
+impl Default for CameraDebugSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            marker_size: 0.1,
+            eye_color: Color::YELLOW,
+            target_color: Color::RED,
+            ray_color: Color::WHITE,
+            radius_color: Color::rgba(0.3, 0.6, 1.0, 0.5),
+            limit_color: Color::ORANGE,
+        }
+    }
+}
+
+/// Marks the entity holding the debug lines of `camera`.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct CameraDebugLines {
+    pub camera: Entity,
+}
+
+/// A line segment relative to the target of a camera.
+pub(crate) type DebugLine = ([DVec3; 2], Color);
+
+/// The debug lines of a rig, relative to its target so they stay precise far from the origin.
+pub(crate) fn rig_lines(
+    transform: &LookTransform,
+    orbit: Option<&OrbitCameraController>,
+    settings: &CameraDebugSettings,
+) -> Vec<DebugLine> {
+    let mut lines = Vec::new();
+    let offset = transform.eye - transform.target;
+    let radius = offset.length();
+    let up = transform.up.try_normalize().unwrap_or(DVec3::Y);
+    let frame = up_axis_rotation(up);
+
+    cross(
+        &mut lines,
+        DVec3::ZERO,
+        settings.marker_size,
+        settings.target_color,
+    );
+    cross(&mut lines, offset, settings.marker_size, settings.eye_color);
+    lines.push(([offset, DVec3::ZERO], settings.ray_color));
+
+    if radius > 0.0 {
+        for (a, b) in [
+            (DVec3::X, DVec3::Z),
+            (DVec3::X, DVec3::Y),
+            (DVec3::Y, DVec3::Z),
+        ] {
+            let points = (0..=CIRCLE_SEGMENTS).map(|i| {
+                let angle = std::f64::consts::TAU * i as f64 / CIRCLE_SEGMENTS as f64;
+                frame * (radius * (angle.cos() * a + angle.sin() * b))
+            });
+            polyline(&mut lines, points, settings.radius_color);
+        }
+    }
+
+    if let (Some(orbit), true) = (orbit, radius > 0.0) {
+        let on_sphere = |yaw: f64, pitch: f64| {
+            let mut angles = LookAngles::default();
+            angles.set_yaw(yaw);
+            angles.set_pitch(pitch);
+            radius * angles.unit_vector_with_up(up)
+        };
+        let (min_yaw, max_yaw) = orbit
+            .yaw_range
+            .unwrap_or((-std::f64::consts::PI, std::f64::consts::PI));
+        let yaw_width = (max_yaw - min_yaw).rem_euclid(std::f64::consts::TAU);
+        let yaw_width = if yaw_width == 0.0 {
+            std::f64::consts::TAU
+        } else {
+            yaw_width
+        };
+        let yaw_at = |i: usize| min_yaw + yaw_width * i as f64 / CIRCLE_SEGMENTS as f64;
+
+        for pitch in [orbit.min_pitch, orbit.max_pitch] {
+            // A limit at the pole doesn't restrict anything.
+            if pitch.abs() >= std::f64::consts::FRAC_PI_2 {
+                continue;
+            }
+            let rim = (0..=CIRCLE_SEGMENTS).map(|i| on_sphere(yaw_at(i), pitch));
+            polyline(&mut lines, rim, settings.limit_color);
+            for i in (0..CIRCLE_SEGMENTS).step_by(CIRCLE_SEGMENTS / 4) {
+                lines.push((
+                    [DVec3::ZERO, on_sphere(yaw_at(i), pitch)],
+                    settings.limit_color,
+                ));
+            }
+        }
+        if orbit.yaw_range.is_some() {
+            let arc = (0..=CIRCLE_SEGMENTS).map(|i| on_sphere(yaw_at(i), 0.0));
+            polyline(&mut lines, arc, settings.limit_color);
+            for yaw in [min_yaw, min_yaw + yaw_width] {
+                lines.push(([DVec3::ZERO, on_sphere(yaw, 0.0)], settings.limit_color));
+            }
+        }
+    }
+
+    lines
+}
+
+fn cross(lines: &mut Vec<DebugLine>, center: DVec3, size: f64, color: Color) {
+    for axis in [DVec3::X, DVec3::Y, DVec3::Z] {
+        lines.push(([center - size * axis, center + size * axis], color));
+    }
+}
+
+fn polyline(lines: &mut Vec<DebugLine>, points: impl Iterator<Item = DVec3>, color: Color) {
+    let points: Vec<_> = points.collect();
+    for pair in points.windows(2) {
+        lines.push(([pair[0], pair[1]], color));
+    }
+}
+
+fn line_mesh(lines: &[DebugLine]) -> Mesh {
+    let mut positions = Vec::with_capacity(2 * lines.len());
+    let mut colors = Vec::with_capacity(2 * lines.len());
+    for (points, color) in lines {
+        for point in points {
+            positions.push(point.as_vec3().to_array());
+            colors.push(color.as_linear_rgba_f32());
+        }
+    }
+    let vertex_count = positions.len();
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; vertex_count]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; vertex_count]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+
+    mesh
+}
+
+fn camera_debug_system(
+    mut commands: Commands,
+    settings: Res<CameraDebugSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut debug_entities: Local<HashMap<Entity, Entity>>,
+    mut material: Local<Option<Handle<StandardMaterial>>>,
+    cameras: Query<(Entity, &LookTransform, Option<&OrbitCameraController>)>,
+    mut debug_lines: Query<(&Handle<Mesh>, &mut Transform), With<CameraDebugLines>>,
+) {
+    // Clean up after cameras that were despawned, or everything when disabled.
+    debug_entities.retain(|camera, lines| {
+        let keep = settings.enabled && cameras.contains(*camera);
+        if !keep {
+            commands.entity(*lines).despawn();
+        }
+        keep
+    });
+    if !settings.enabled {
+        return;
+    }
+
+    let material = material
+        .get_or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color: Color::WHITE,
+                unlit: true,
+                ..Default::default()
+            })
+        })
+        .clone();
+
+    for (camera, transform, orbit) in cameras.iter() {
+        let mesh = line_mesh(&rig_lines(transform, orbit, &settings));
+        let existing = debug_entities
+            .get(&camera)
+            .and_then(|lines| debug_lines.get_mut(*lines).ok());
+        if let Some((handle, mut lines_transform)) = existing {
+            lines_transform.translation = transform.target;
+            if let Some(old_mesh) = meshes.get_mut(handle) {
+                *old_mesh = mesh;
+            }
+        } else {
+            let lines = commands
+                .spawn(PbrBundle {
+                    mesh: meshes.add(mesh),
+                    material: material.clone(),
+                    transform: Transform::from_translation(transform.target),
+                    ..Default::default()
+                })
+                .insert((CameraDebugLines { camera }, NotShadowCaster))
+                .id();
+            debug_entities.insert(camera, lines);
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rig_lines_relative_to_target() {
+        let settings = CameraDebugSettings::default();
+        let transform = LookTransform::new(DVec3::new(1e9, 0.0, 2.0), DVec3::new(1e9, 0.0, 0.0));
+
+        let lines = rig_lines(&transform, None, &settings);
+        assert!(lines.contains(&([2.0 * DVec3::Z, DVec3::ZERO], settings.ray_color)));
+        assert!(lines
+            .iter()
+            .filter(|(_, color)| *color == settings.radius_color)
+            .all(|(points, _)| points.iter().all(|p| (p.length() - 2.0).abs() < 1e-9)));
+
+        let orbit = OrbitCameraController {
+            min_pitch: -0.5,
+            yaw_range: Some((-1.0, 1.0)),
+            ..Default::default()
+        };
+        let with_limits = rig_lines(&transform, Some(&orbit), &settings);
+        assert!(with_limits
+            .iter()
+            .any(|(_, color)| *color == settings.limit_color));
+    }
+}
@@ -0,0 +1,35 @@
+use crate::LookTransformSystem;
+
+use bevy::{app::prelude::*, ecs::prelude::*, transform::components::Transform};
+
+/// Moves every `CameraListener` to the smoothed pose of its camera each frame.
+pub struct CameraListenerPlugin;
+
+impl Plugin for CameraListenerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(camera_listener_system.after(LookTransformSystem::Smooth));
+    }
+}
+
+/// Keeps this entity's `Transform` on the smoothed pose of `camera`, so that 3D audio follows what the player sees rather
+/// than the unsmoothed `LookTransform`.
+///
+/// The version of `bevy_audio` this crate targets has no spatial audio, so add this to the listener entity of whichever
+/// audio crate you use. It should not have a parent.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct CameraListener {
+    pub camera: Entity,
+}
+
+fn camera_listener_system(
+    cameras: Query<&Transform, Without<CameraListener>>,
+    mut listeners: Query<(&CameraListener, &mut Transform)>,
+) {
+    for (listener, mut transform) in listeners.iter_mut() {
+        if let Ok(camera_transform) = cameras.get(listener.camera) {
+            if *transform != *camera_transform {
+                *transform = *camera_transform;
+            }
+        }
+    }
+}
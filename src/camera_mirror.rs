@@ -0,0 +1,259 @@
+use crate::{
+    controllers::CameraControlSystem, LookTransform, LookTransformSystem, SmoothedLookTransform,
+    Smoother,
+};
+
+use bevy::{app::prelude::*, ecs::prelude::*};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Publishes the pose of every `MirrorSource` camera and applies published poses to `MirrorTarget` cameras.
+pub struct CameraMirrorPlugin;
+
+impl Plugin for CameraMirrorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<MirroredTransform>()
+            .add_system(mirror_source_system.after(LookTransformSystem::Smooth))
+            .add_system(
+                mirror_target_system
+                    .after(CameraControlSystem::Control)
+                    .before(LookTransformSystem::Smooth),
+            );
+    }
+}
+
+/// The smoothed pose of a `MirrorSource` camera, sent whenever it changes.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MirroredTransform {
+    pub channel: u32,
+    pub transform: LookTransform,
+}
+
+/// Publishes this camera's smoothed `LookTransform` on `channel` each frame it changes, as a `MirroredTransform` event
+/// and to the sink, if any. The smoothed pose comes from the camera's `SmoothedLookTransform`, or else its `Smoother`.
+/// The sink can forward it to other instances of the app, for video walls and co-review sessions, where a
+/// `MirrorTarget` on the same channel applies it.
+#[derive(Clone, Component)]
+pub struct MirrorSource {
+    pub channel: u32,
+    sink: Option<Arc<dyn Fn(&MirroredTransform) + Send + Sync>>,
+    last: Option<LookTransform>,
+}
+
+impl MirrorSource {
+    pub fn new(channel: u32) -> Self {
+        Self {
+            channel,
+            sink: None,
+            last: None,
+        }
+    }
+
+    /// Also pass each mirrored transform to `sink`, e.g. to send it over the network.
+    pub fn with_sink(mut self, sink: impl Fn(&MirroredTransform) + Send + Sync + 'static) -> Self {
+        self.sink = Some(Arc::new(sink));
+        self
+    }
+}
+
+/// Moves this camera's `LookTransform` to each `MirroredTransform` on `channel`, whether it was sent by a
+/// `MirrorSource` in this app or received from another instance and sent as an event.
+///
+/// The mirrored transform is already smoothed, so give the camera no `Smoother`, or one with a lag weight of zero, and
+/// no enabled controller.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct MirrorTarget {
+    pub channel: u32,
+}
+
+fn mirror_source_system(
+    mut events: EventWriter<MirroredTransform>,
+    mut sources: Query<(
+        &mut MirrorSource,
+        &LookTransform,
+        Option<&SmoothedLookTransform>,
+        Option<&Smoother>,
+    )>,
+) {
+    for (mut source, transform, smoothed, smoother) in sources.iter_mut() {
+        let transform = smoothed
+            .map(|s| s.0)
+            .or_else(|| smoother.and_then(|s| s.smoothed_transform()))
+            .unwrap_or(*transform);
+        if source.last == Some(transform) {
+            continue;
+        }
+        source.last = Some(transform);
+
+        let mirrored = MirroredTransform {
+            channel: source.channel,
+            transform,
+        };
+        if let Some(sink) = &source.sink {
+            sink(&mirrored);
+        }
+        events.send(mirrored);
+    }
+}
+
+fn mirror_target_system(
+    mut events: EventReader<MirroredTransform>,
+    mut targets: Query<(&MirrorTarget, &mut LookTransform), Without<MirrorSource>>,
+) {
+    for mirrored in events.iter() {
+        for (target, mut transform) in targets.iter_mut() {
+            if target.channel == mirrored.channel && *transform != mirrored.transform {
+                *transform = mirrored.transform;
+            }
+        }
+    }
+}
+
+/// Sends mirrored transforms to the `RemoteControlPlugin` of another instance.
+#[cfg(feature = "remote_control")]
+mod socket {
+    use super::{MirrorSource, MirroredTransform};
+    use crate::{CameraCommand, LookTransform};
+
+    use std::{
+        io::Write,
+        net::{SocketAddr, TcpStream},
+        sync::{
+            mpsc::{self, Receiver},
+            Mutex,
+        },
+        thread,
+        time::{Duration, Instant},
+    };
+
+    /// How long to wait between attempts to reach an instance that isn't listening.
+    const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+    impl MirrorSource {
+        /// Send each mirrored transform to the `RemoteControlPlugin` listening at `address`, as a
+        /// `CameraCommand::SetTransform`, so it drives the camera with an enabled controller there. Reconnects once a
+        /// second while the other instance isn't reachable.
+        ///
+        /// Connecting and writing happen on a thread of their own, so a slow or unreachable instance never stalls the
+        /// frame. The thread ends when the `MirrorSource` is dropped.
+        pub fn with_remote(self, address: SocketAddr) -> Self {
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || send_to_remote(address, receiver));
+            let sender = Mutex::new(sender);
+            self.with_sink(move |mirrored: &MirroredTransform| {
+                // Only fails once the thread is gone, and then there's nothing left to send to.
+                let _ = sender.lock().unwrap().send(mirrored.transform);
+            })
+        }
+    }
+
+    /// Writes the transforms from `receiver` to `address` until the sending `MirrorSource` is dropped.
+    fn send_to_remote(address: SocketAddr, receiver: Receiver<LookTransform>) {
+        let mut stream = None::<TcpStream>;
+        let mut last_attempt = None::<Instant>;
+        while let Ok(transform) = receiver.recv() {
+            if stream.is_none() {
+                if let Some(last_attempt) = last_attempt {
+                    thread::sleep(RECONNECT_INTERVAL.saturating_sub(last_attempt.elapsed()));
+                }
+                last_attempt = Some(Instant::now());
+                stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).ok();
+            }
+            // Only the latest pose matters, so skip the ones that arrived while connecting or writing.
+            let transform = receiver.try_iter().last().unwrap_or(transform);
+            let line = match ron::to_string(&CameraCommand::SetTransform(transform)) {
+                Ok(line) => line + "\n",
+                Err(_) => continue,
+            };
+            if let Some(s) = &mut stream {
+                if s.write_all(line.as_bytes()).is_err() {
+                    stream = None;
+                }
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::*, LookTransformBundle};
+
+    use bevy::math::DVec3;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_camera_mirror() {
+        let mut app = headless_app();
+        app.add_plugin(CameraMirrorPlugin);
+        let sent = Arc::new(AtomicUsize::new(0));
+        let sink_sent = sent.clone();
+        let transform = LookTransform::new(DVec3::new(1.0, 2.0, 3.0), DVec3::ZERO);
+        let source = app
+            .world
+            .spawn((
+                transform,
+                MirrorSource::new(7).with_sink(move |_| {
+                    sink_sent.fetch_add(1, Ordering::Relaxed);
+                }),
+            ))
+            .id();
+        let target = app
+            .world
+            .spawn((LookTransform::default(), MirrorTarget { channel: 7 }))
+            .id();
+        let other = app
+            .world
+            .spawn((LookTransform::default(), MirrorTarget { channel: 8 }))
+            .id();
+
+        step(&mut app, 3);
+        assert_eq!(look_transform(&app, target), transform);
+        assert_eq!(look_transform(&app, other), LookTransform::default());
+        // Only sent while the source moves.
+        assert_eq!(sent.load(Ordering::Relaxed), 1);
+
+        app.world.get_mut::<LookTransform>(source).unwrap().eye = DVec3::X;
+        step(&mut app, 2);
+        assert_eq!(look_transform(&app, target).eye, DVec3::X);
+        assert_eq!(sent.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_camera_mirror_smoothed() {
+        let mut app = headless_app();
+        app.add_plugin(CameraMirrorPlugin);
+        let source = app
+            .world
+            .spawn((
+                LookTransformBundle {
+                    transform: LookTransform::new(DVec3::Z, DVec3::ZERO),
+                    smoother: Smoother::new(0.9),
+                },
+                MirrorSource::new(7),
+            ))
+            .id();
+        step(&mut app, 1);
+
+        app.world.get_mut::<LookTransform>(source).unwrap().eye = DVec3::X;
+        step(&mut app, 1);
+
+        let smoothed = app
+            .world
+            .get::<Smoother>(source)
+            .and_then(Smoother::smoothed_transform)
+            .unwrap();
+        let events = app.world.resource::<Events<MirroredTransform>>();
+        let mirrored = events.get_reader().iter(events).last().unwrap();
+        assert_eq!(mirrored.transform, smoothed);
+        assert_ne!(mirrored.transform, look_transform(&app, source));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{LookTransform, LookTransformSystem, Smoother};
+
+use bevy::{app::prelude::*, ecs::prelude::*, time::Time};
+use serde::{Deserialize, Serialize};
+
+/// Records the smoothed `LookTransform` of a camera into the `CameraPath` resource, and plays it back onto any entity with a
+/// `CameraPathPlayback`.
+///
+/// `CameraPath` is serializable, so recorded fly-throughs can be saved with any serde format (RON, JSON, ...) and loaded
+/// back by inserting the resource.
+pub struct CameraPathPlugin;
+
+impl Plugin for CameraPathPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraPath>()
+            .init_resource::<CameraPathRecorder>()
+            .add_system(camera_path_record_system.after(LookTransformSystem::Smooth))
+            .add_system(camera_path_playback_system.before(LookTransformSystem::Smooth));
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CameraPathKey {
+    /// Seconds since the start of the path.
+    pub time: f64,
+    pub transform: LookTransform,
+}
+
+/// A timed sequence of `LookTransform`s.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Resource, Serialize)]
+pub struct CameraPath {
+    /// Keys in increasing order of time.
+    pub keys: Vec<CameraPathKey>,
+}
+
+impl CameraPath {
+    pub fn duration(&self) -> f64 {
+        self.keys.last().map_or(0.0, |k| k.time)
+    }
+
+    pub fn push(&mut self, time: f64, transform: LookTransform) {
+        debug_assert!(self.keys.last().map_or(true, |k| k.time <= time));
+        self.keys.push(CameraPathKey { time, transform });
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+
+    /// Linearly interpolates between the keys surrounding `time`, clamping to the ends of the path.
+    pub fn sample(&self, time: f64) -> Option<LookTransform> {
+        let first = self.keys.first()?;
+        let i = self.keys.partition_point(|k| k.time <= time);
+        if i == 0 {
+            return Some(first.transform);
+        }
+        if i == self.keys.len() {
+            return Some(self.keys[i - 1].transform);
+        }
+        let (a, b) = (self.keys[i - 1], self.keys[i]);
+        let t = (time - a.time) / (b.time - a.time);
+
+        Some(LookTransform::new(
+            a.transform.eye.lerp(b.transform.eye, t),
+            a.transform.target.lerp(b.transform.target, t),
+        )
+        .with_up(a.transform.up))
+    }
+}
+
+/// Controls recording into the `CameraPath` resource.
+#[derive(Debug, Default, Resource)]
+pub struct CameraPathRecorder {
+    entity: Option<Entity>,
+    elapsed: f64,
+}
+
+impl CameraPathRecorder {
+    /// Start recording `entity`, clearing any path that was recorded before.
+    pub fn start(&mut self, entity: Entity, path: &mut CameraPath) {
+        self.entity = Some(entity);
+        self.elapsed = 0.0;
+        path.clear();
+    }
+
+    pub fn stop(&mut self) {
+        self.entity = None;
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.entity.is_some()
+    }
+}
+
+/// Plays the `CameraPath` resource back onto this entity's `LookTransform`.
+#[derive(Clone, Component, Copy, Debug, Default)]
+pub struct CameraPathPlayback {
+    pub playing: bool,
+    pub looping: bool,
+    pub elapsed: f64,
+}
+
+fn camera_path_record_system(
+    time: Res<Time>,
+    mut recorder: ResMut<CameraPathRecorder>,
+    mut path: ResMut<CameraPath>,
+    cameras: Query<(&LookTransform, Option<&Smoother>)>,
+) {
+    let entity = if let Some(entity) = recorder.entity {
+        entity
+    } else {
+        return;
+    };
+    let (look_transform, smoother) = if let Ok(camera) = cameras.get(entity) {
+        camera
+    } else {
+        recorder.stop();
+        return;
+    };
+
+    let transform = smoother
+        .and_then(|s| s.smoothed_transform())
+        .unwrap_or(*look_transform);
+    let elapsed = recorder.elapsed;
+    path.push(elapsed, transform);
+    recorder.elapsed += time.delta_seconds_f64();
+}
+
+fn camera_path_playback_system(
+    time: Res<Time>,
+    path: Res<CameraPath>,
+    mut cameras: Query<(
+        &mut CameraPathPlayback,
+        &mut LookTransform,
+        Option<&mut Smoother>,
+    )>,
+) {
+    for (mut playback, mut transform, smoother) in cameras.iter_mut() {
+        if !playback.playing {
+            continue;
+        }
+        if let Some(sample) = path.sample(playback.elapsed) {
+            *transform = sample;
+        }
+        // The path was recorded after smoothing, so don't smooth it twice.
+        if let Some(mut smoother) = smoother {
+            smoother.reset();
+        }
+
+        playback.elapsed += time.delta_seconds_f64();
+        if playback.elapsed > path.duration() {
+            if playback.looping {
+                playback.elapsed = 0.0;
+            } else {
+                playback.playing = false;
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+    use bevy::math::DVec3;
+
+    #[test]
+    fn test_sample_interpolates_and_clamps() {
+        let mut path = CameraPath::default();
+        path.push(0.0, LookTransform::new(DVec3::ZERO, DVec3::Z));
+        path.push(2.0, LookTransform::new(DVec3::new(4.0, 0.0, 0.0), DVec3::Z));
+
+        assert_relative_eq!(path.sample(-1.0).unwrap().eye.x, 0.0);
+        assert_relative_eq!(path.sample(1.0).unwrap().eye.x, 2.0);
+        assert_relative_eq!(path.sample(5.0).unwrap().eye.x, 4.0);
+        assert!(CameraPath::default().sample(0.0).is_none());
+    }
+}
@@ -0,0 +1,325 @@
+use crate::{
+    add_transform_offset_system,
+    controllers::{
+        fixed::FixedCameraController, fps::FpsCameraController, orbit::OrbitCameraController,
+        spectator::SpectatorCameraController, unreal::UnrealCameraController, CameraControlSystem,
+        EnabledController,
+    },
+    CameraPathPlayback, CameraSequence, Easing, LookTransform, LookTransformSystem, Smoother,
+    SmoothingSettings, TransformOffset,
+};
+
+use bevy::{
+    app::prelude::*, ecs::prelude::*, time::Time, transform::components::Transform,
+    utils::HashMap,
+};
+
+/// Switches cameras with a `CameraStateMachine` between behaviors on `ChangeCameraState` events.
+pub struct CameraStateMachinePlugin;
+
+impl Plugin for CameraStateMachinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ChangeCameraState>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                add_transform_offset_system::<CameraStateMachine>,
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                camera_state_system.before(CameraControlSystem::EnabledChanged),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                camera_state_smoother_system.after(CameraControlSystem::EnabledChanged),
+            )
+            .add_system(
+                camera_state_blend_system
+                    .after(LookTransformSystem::Smooth)
+                    .before(LookTransformSystem::Offset),
+            );
+    }
+}
+
+/// What drives a camera while its `CameraStateMachine` is in a state.
+#[derive(Clone, Copy, Debug)]
+pub enum CameraBehavior {
+    /// Enable the `FpsCameraController`.
+    Fps,
+    /// Enable the `OrbitCameraController`.
+    Orbit,
+    /// Enable the `UnrealCameraController`.
+    Unreal,
+    /// Enable the `SpectatorCameraController`.
+    Spectator,
+    /// Enable the `FixedCameraController`.
+    Tracking,
+    /// Play the `CameraPath` resource from the start through the camera's `CameraPathPlayback`.
+    Path { looping: bool },
+    /// Play the camera's `CameraSequence` from the first shot.
+    Sequence,
+    /// Hold still at this transform.
+    Fixed(LookTransform),
+    /// Disable everything above and leave the `LookTransform` to the app.
+    Manual,
+}
+
+/// Allows a change of state, blending from the previous pose over `blend` seconds.
+#[derive(Clone, Debug)]
+pub struct CameraTransition {
+    /// State to leave, or `None` for any state
+    pub from: Option<String>,
+    pub to: String,
+    /// Seconds to blend from the pose at the time of the change to the new behavior
+    pub blend: f64,
+    pub easing: Easing,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct CameraBlend {
+    from: Transform,
+    elapsed: f64,
+    duration: f64,
+    easing: Easing,
+}
+
+/// Declares the states a camera can be in, each bound to a `CameraBehavior`, and the transitions allowed between them.
+/// On entering a state, the controllers and players on the same entity are enabled or disabled to match its
+/// behavior, so they never fight over the `LookTransform`.
+///
+/// Changes are requested with a `ChangeCameraState` event, and ignored unless a `CameraTransition` allows them. A
+/// transition with a blend is applied as a [`TransformOffset`](crate::TransformOffset).
+#[derive(Clone, Component, Debug)]
+pub struct CameraStateMachine {
+    pub states: HashMap<String, CameraBehavior>,
+    pub transitions: Vec<CameraTransition>,
+    current: String,
+    entered: bool,
+    reset_smoother: bool,
+    blend: Option<CameraBlend>,
+}
+
+impl CameraStateMachine {
+    /// Starts in `initial`, which is entered on the first update.
+    pub fn new(initial: impl Into<String>, behavior: CameraBehavior) -> Self {
+        let initial = initial.into();
+        let mut states = HashMap::default();
+        states.insert(initial.clone(), behavior);
+        Self {
+            states,
+            transitions: Vec::new(),
+            current: initial,
+            entered: false,
+            reset_smoother: false,
+            blend: None,
+        }
+    }
+
+    pub fn with_state(mut self, name: impl Into<String>, behavior: CameraBehavior) -> Self {
+        self.states.insert(name.into(), behavior);
+        self
+    }
+
+    /// Allows changing from `from` (or any state, if `None`) to `to`, blending over `blend` seconds.
+    pub fn with_transition(
+        mut self,
+        from: Option<&str>,
+        to: impl Into<String>,
+        blend: f64,
+    ) -> Self {
+        self.transitions.push(CameraTransition {
+            from: from.map(String::from),
+            to: to.into(),
+            blend,
+            easing: Easing::EaseInOut,
+        });
+        self
+    }
+
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    pub fn is_blending(&self) -> bool {
+        self.blend.is_some()
+    }
+
+    /// The transition from the current state to `to`, preferring one from the current state over one from any state.
+    pub fn transition(&self, to: &str) -> Option<&CameraTransition> {
+        let mut matching = self.transitions.iter().filter(|t| t.to == to);
+        let exact = matching
+            .clone()
+            .find(|t| t.from.as_deref() == Some(self.current.as_str()));
+        exact.or_else(|| matching.find(|t| t.from.is_none()))
+    }
+}
+
+/// Request that `camera` changes to `state`.
+pub struct ChangeCameraState {
+    pub camera: Entity,
+    pub state: String,
+}
+
+fn set_enabled<C: EnabledController>(controller: Option<Mut<C>>, enabled: bool) {
+    if let Some(mut controller) = controller {
+        if *controller.enabled_mut() != enabled {
+            *controller.enabled_mut() = enabled;
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn camera_state_system(
+    mut events: EventReader<ChangeCameraState>,
+    mut cameras: Query<(
+        &mut CameraStateMachine,
+        &mut LookTransform,
+        &Transform,
+        Option<&mut FpsCameraController>,
+        Option<&mut OrbitCameraController>,
+        Option<&mut UnrealCameraController>,
+        Option<&mut SpectatorCameraController>,
+        Option<&mut FixedCameraController>,
+        Option<&mut CameraPathPlayback>,
+        Option<&mut CameraSequence>,
+    )>,
+) {
+    for event in events.iter() {
+        if let Ok((mut machine, _, transform, ..)) = cameras.get_mut(event.camera) {
+            if !machine.states.contains_key(&event.state) || machine.current == event.state {
+                continue;
+            }
+            let transition = if let Some(transition) = machine.transition(&event.state) {
+                transition.clone()
+            } else {
+                continue;
+            };
+            machine.current = transition.to;
+            machine.entered = false;
+            machine.blend = (transition.blend > 0.0).then_some(CameraBlend {
+                from: *transform,
+                elapsed: 0.0,
+                duration: transition.blend,
+                easing: transition.easing,
+            });
+        }
+    }
+
+    for (
+        mut machine,
+        mut look_transform,
+        _,
+        fps,
+        orbit,
+        unreal,
+        spectator,
+        fixed,
+        path,
+        sequence,
+    ) in cameras.iter_mut()
+    {
+        if machine.entered {
+            continue;
+        }
+        machine.entered = true;
+        machine.reset_smoother = true;
+        let behavior = machine.states[&machine.current];
+
+        set_enabled(fps, matches!(behavior, CameraBehavior::Fps));
+        set_enabled(orbit, matches!(behavior, CameraBehavior::Orbit));
+        set_enabled(unreal, matches!(behavior, CameraBehavior::Unreal));
+        set_enabled(spectator, matches!(behavior, CameraBehavior::Spectator));
+        set_enabled(fixed, matches!(behavior, CameraBehavior::Tracking));
+        if let Some(mut path) = path {
+            path.playing = false;
+            if let CameraBehavior::Path { looping } = behavior {
+                path.playing = true;
+                path.looping = looping;
+                path.elapsed = 0.0;
+            }
+        }
+        if let Some(mut sequence) = sequence {
+            sequence.playing = matches!(behavior, CameraBehavior::Sequence);
+            if sequence.playing {
+                sequence.rewind();
+            }
+        }
+        if let CameraBehavior::Fixed(fixed) = behavior {
+            *look_transform = fixed;
+        }
+    }
+}
+
+fn camera_state_smoother_system(
+    mut cameras: Query<(&mut CameraStateMachine, &mut Smoother), Changed<CameraStateMachine>>,
+) {
+    // Every behavior is smoothed, whichever controllers were just disabled.
+    for (mut machine, mut smoother) in cameras.iter_mut() {
+        if machine.reset_smoother {
+            machine.reset_smoother = false;
+            smoother.set_enabled(true);
+        }
+    }
+}
+
+fn camera_state_blend_system(
+    time: Res<Time>,
+    settings: Res<SmoothingSettings>,
+    mut cameras: Query<(
+        &mut CameraStateMachine,
+        &LookTransform,
+        Option<&Smoother>,
+        &mut TransformOffset,
+    )>,
+) {
+    for (mut machine, look_transform, smoother, mut offset) in cameras.iter_mut() {
+        let blend = if let Some(blend) = machine.blend.as_mut() {
+            blend
+        } else {
+            continue;
+        };
+        blend.elapsed += time.delta_seconds_f64();
+        let t = blend.easing.apply(blend.elapsed / blend.duration);
+
+        // Offset the new pose back toward the old one, by less and less.
+        let smoothed = smoother
+            .and_then(|s| s.smoothed_transform())
+            .unwrap_or(*look_transform);
+        let base = settings.conversion.transform(smoothed);
+        let translation = blend.from.translation.lerp(base.translation, t);
+        let rotation = blend.from.rotation.slerp(base.rotation, t);
+        let inverse = base.rotation.inverse();
+        offset.translate(inverse * (translation - base.translation));
+        offset.rotate(inverse * rotation);
+
+        if blend.elapsed >= blend.duration {
+            machine.blend = None;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::math::DVec3;
+
+    #[test]
+    fn test_transition_prefers_current_state() {
+        let fixed = CameraBehavior::Fixed(LookTransform::new(DVec3::ZERO, -DVec3::Z));
+        let machine = CameraStateMachine::new("free", CameraBehavior::Fps)
+            .with_state("cutscene", CameraBehavior::Sequence)
+            .with_state("fixed", fixed)
+            .with_transition(None, "cutscene", 1.0)
+            .with_transition(Some("free"), "cutscene", 0.5)
+            .with_transition(Some("cutscene"), "fixed", 0.0);
+
+        assert_eq!(machine.transition("cutscene").unwrap().blend, 0.5);
+        assert!(machine.transition("fixed").is_none());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    controllers::{
+        fixed::FixedCameraController, fps::FpsCameraController, map::MapCameraController,
+        orbit::OrbitCameraController, photo_mode::PhotoModeState,
+        spectator::SpectatorCameraController, unreal::UnrealCameraController,
+    },
+    LookTransform, LookTransformSystem, Smoother,
+};
+
+use bevy::{
+    app::prelude::*,
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics},
+    ecs::prelude::*,
+    math::prelude::*,
+    time::Time,
+};
+
+/// Reports the state of the active camera in the `CameraTelemetry` resource each frame, and its numbers as
+/// `Diagnostics`, so they can be shown with `LogDiagnosticsPlugin` or any diagnostics overlay. Helps find out why a camera
+/// "feels wrong".
+///
+/// The active camera is the first one with an enabled controller, or in photo mode.
+pub struct CameraTelemetryPlugin;
+
+impl CameraTelemetryPlugin {
+    pub const RADIUS: DiagnosticId =
+        DiagnosticId::from_u128(291477165016830520964339307306393154369);
+    pub const EYE_SPEED: DiagnosticId =
+        DiagnosticId::from_u128(125338937316716925476541036779567541627);
+    pub const SMOOTHING_ERROR: DiagnosticId =
+        DiagnosticId::from_u128(53093415287458618227461815813390853271);
+}
+
+impl Plugin for CameraTelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        // `DiagnosticsPlugin` adds this too, but the telemetry works without it.
+        app.init_resource::<Diagnostics>()
+            .init_resource::<CameraTelemetry>()
+            .add_startup_system(setup_telemetry_diagnostics)
+            .add_system(camera_telemetry_system.after(LookTransformSystem::Smooth));
+    }
+}
+
+/// Which controller is driving the active camera.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ActiveController {
+    Fps,
+    Orbit,
+    Unreal,
+    Spectator,
+    Fixed,
+    Map,
+    PhotoMode,
+}
+
+/// The state of the active camera, updated by the `CameraTelemetryPlugin`.
+#[derive(Clone, Copy, Debug, Default, Resource)]
+pub struct CameraTelemetry {
+    /// The active camera, or `None` if no camera has an enabled controller
+    pub camera: Option<Entity>,
+    pub controller: Option<ActiveController>,
+    pub eye: DVec3,
+    pub target: DVec3,
+    pub radius: f64,
+    /// How fast the eye moved over the last frame, in units per second
+    pub eye_speed: f64,
+    /// How far the smoothed eye or target lags behind the `LookTransform`, whichever is farther
+    pub smoothing_error: f64,
+}
+
+fn setup_telemetry_diagnostics(mut diagnostics: ResMut<Diagnostics>) {
+    diagnostics.add(Diagnostic::new(
+        CameraTelemetryPlugin::RADIUS,
+        "camera_radius",
+        20,
+    ));
+    diagnostics.add(Diagnostic::new(
+        CameraTelemetryPlugin::EYE_SPEED,
+        "camera_eye_speed",
+        20,
+    ));
+    diagnostics.add(Diagnostic::new(
+        CameraTelemetryPlugin::SMOOTHING_ERROR,
+        "camera_smoothing_error",
+        20,
+    ));
+}
+
+type TelemetryControllers<'a> = (
+    Option<&'a FpsCameraController>,
+    Option<&'a OrbitCameraController>,
+    Option<&'a UnrealCameraController>,
+    Option<&'a SpectatorCameraController>,
+    Option<&'a FixedCameraController>,
+    Option<&'a MapCameraController>,
+    Option<&'a PhotoModeState>,
+);
+
+fn active_controller(
+    (fps, orbit, unreal, spectator, fixed, map, photo_mode): TelemetryControllers,
+) -> Option<ActiveController> {
+    if photo_mode.is_some() {
+        Some(ActiveController::PhotoMode)
+    } else if fps.map_or(false, |c| c.enabled) {
+        Some(ActiveController::Fps)
+    } else if orbit.map_or(false, |c| c.enabled) {
+        Some(ActiveController::Orbit)
+    } else if unreal.map_or(false, |c| c.enabled) {
+        Some(ActiveController::Unreal)
+    } else if spectator.map_or(false, |c| c.enabled) {
+        Some(ActiveController::Spectator)
+    } else if fixed.map_or(false, |c| c.enabled) {
+        Some(ActiveController::Fixed)
+    } else if map.map_or(false, |c| c.enabled) {
+        Some(ActiveController::Map)
+    } else {
+        None
+    }
+}
+
+fn camera_telemetry_system(
+    time: Res<Time>,
+    mut telemetry: ResMut<CameraTelemetry>,
+    mut diagnostics: ResMut<Diagnostics>,
+    cameras: Query<(
+        Entity,
+        &LookTransform,
+        Option<&Smoother>,
+        TelemetryControllers,
+    )>,
+) {
+    let active = cameras
+        .iter()
+        .find_map(|(entity, transform, smoother, controllers)| {
+            active_controller(controllers)
+                .map(|controller| (entity, transform, smoother, controller))
+        });
+    let (camera, transform, smoother, controller) = if let Some(active) = active {
+        active
+    } else {
+        *telemetry = CameraTelemetry::default();
+        return;
+    };
+
+    // Only measure speed across frames of the same camera, so switching cameras doesn't spike it.
+    let dt = time.delta_seconds_f64();
+    let eye_speed = if telemetry.camera == Some(camera) && dt > 0.0 {
+        transform.eye.distance(telemetry.eye) / dt
+    } else {
+        0.0
+    };
+    let smoothing_error = smoother
+        .and_then(Smoother::smoothed_transform)
+        .map_or(0.0, |smoothed| {
+            smoothed
+                .eye
+                .distance(transform.eye)
+                .max(smoothed.target.distance(transform.target))
+        });
+
+    *telemetry = CameraTelemetry {
+        camera: Some(camera),
+        controller: Some(controller),
+        eye: transform.eye,
+        target: transform.target,
+        radius: transform.radius(),
+        eye_speed,
+        smoothing_error,
+    };
+
+    diagnostics.add_measurement(CameraTelemetryPlugin::RADIUS, || telemetry.radius);
+    diagnostics.add_measurement(CameraTelemetryPlugin::EYE_SPEED, || eye_speed);
+    diagnostics.add_measurement(CameraTelemetryPlugin::SMOOTHING_ERROR, || smoothing_error);
+}
@@ -0,0 +1,223 @@
+use crate::{world_aabb_min_max, Easing, LookTransform, LookTransformSystem};
+
+use bevy::{
+    app::prelude::*,
+    ecs::prelude::*,
+    render::{camera::Projection, primitives::Aabb},
+    time::Time,
+    transform::components::GlobalTransform,
+};
+
+/// Plays the `CameraTour` of every camera that has one.
+pub struct CameraTourPlugin;
+
+impl Plugin for CameraTourPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(camera_tour_system.before(LookTransformSystem::Smooth));
+    }
+}
+
+/// Something a `CameraTour` stops at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TourStop {
+    /// Frame the `Aabb` of this entity, looking from the direction the camera arrived from, or look at its origin from
+    /// the same distance if it has no `Aabb`.
+    Entity(Entity),
+    /// Move to a saved `LookTransform`.
+    Bookmark(LookTransform),
+}
+
+/// Visits each of `stops` in turn: moves there over `transition_seconds`, eased by `easing`, then holds for
+/// `dwell_seconds`. Drives the `LookTransform` of the same entity while playing, for product configurators and kiosks.
+///
+/// Controllers should be disabled on the entity while a tour plays, otherwise both will write the `LookTransform`.
+/// Orthographic cameras keep their scale.
+#[derive(Clone, Component, Debug)]
+pub struct CameraTour {
+    pub stops: Vec<TourStop>,
+    pub dwell_seconds: f64,
+    pub transition_seconds: f64,
+    pub easing: Easing,
+    /// Margin around framed entities, as a fraction of their size
+    pub padding: f64,
+    /// Start over from the first stop after the last one
+    pub looping: bool,
+    pub playing: bool,
+    current_stop: usize,
+    elapsed: f64,
+    /// Where the transition to the current stop started
+    from: Option<LookTransform>,
+}
+
+impl CameraTour {
+    pub fn new(stops: Vec<TourStop>) -> Self {
+        Self {
+            stops,
+            dwell_seconds: 3.0,
+            transition_seconds: 1.5,
+            easing: Easing::EaseInOut,
+            padding: 0.1,
+            looping: false,
+            playing: true,
+            current_stop: 0,
+            elapsed: 0.0,
+            from: None,
+        }
+    }
+
+    pub fn with_dwell(mut self, seconds: f64) -> Self {
+        self.dwell_seconds = seconds;
+        self
+    }
+
+    pub fn with_transition(mut self, seconds: f64, easing: Easing) -> Self {
+        self.transition_seconds = seconds;
+        self.easing = easing;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: f64) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn current_stop(&self) -> Option<&TourStop> {
+        self.stops.get(self.current_stop)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current_stop >= self.stops.len()
+    }
+
+    /// Eased progress of the transition to the current stop, in `[0, 1]`. Stays at 1 while dwelling.
+    pub fn transition_progress(&self) -> f64 {
+        if self.transition_seconds > 0.0 {
+            self.easing.apply(self.elapsed / self.transition_seconds)
+        } else {
+            1.0
+        }
+    }
+
+    /// Restart from the first stop.
+    pub fn rewind(&mut self) {
+        self.current_stop = 0;
+        self.elapsed = 0.0;
+        self.from = None;
+    }
+
+    /// Move on to the next stop, from wherever the camera is now.
+    pub fn skip(&mut self) {
+        self.current_stop += 1;
+        self.elapsed = 0.0;
+        self.from = None;
+        if self.is_finished() && self.looping {
+            self.rewind();
+        }
+    }
+
+    /// Advances the tour by `dt` seconds, moving on to the next stop once the current one has been dwelled on.
+    pub fn advance(&mut self, dt: f64) {
+        self.elapsed += dt;
+        if self.elapsed >= self.transition_seconds + self.dwell_seconds {
+            self.skip();
+        }
+    }
+}
+
+fn camera_tour_system(
+    time: Res<Time>,
+    mut tours: Query<(&mut CameraTour, &mut LookTransform, Option<&Projection>)>,
+    stops: Query<(&GlobalTransform, Option<&Aabb>)>,
+) {
+    for (mut tour, mut transform, projection) in tours.iter_mut() {
+        if !tour.playing {
+            continue;
+        }
+        let stop = if let Some(stop) = tour.current_stop() {
+            *stop
+        } else {
+            tour.playing = false;
+            continue;
+        };
+        let from = *tour.from.get_or_insert(*transform);
+
+        let to = match stop {
+            TourStop::Bookmark(bookmark) => bookmark,
+            TourStop::Entity(entity) => match stops.get(entity) {
+                Ok((global, Some(aabb))) => {
+                    let (min, max) = world_aabb_min_max(aabb, global);
+                    let projection = projection.cloned().unwrap_or_default();
+                    from.fit_bounds(
+                        (min + max) / 2.0,
+                        (max - min) / 2.0,
+                        &projection,
+                        tour.padding,
+                    )
+                    .transform
+                }
+                Ok((global, None)) => {
+                    let offset = global.translation() - from.target;
+                    LookTransform::new(from.eye + offset, from.target + offset).with_up(from.up)
+                }
+                // The entity is gone, so there's nothing to see there.
+                Err(_) => {
+                    tour.skip();
+                    continue;
+                }
+            },
+        };
+
+        let t = tour.transition_progress();
+        *transform = LookTransform::new(from.eye.lerp(to.eye, t), from.target.lerp(to.target, t))
+            .with_up(to.up);
+
+        tour.advance(time.delta_seconds_f64());
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+    use bevy::math::DVec3;
+
+    #[test]
+    fn test_tour_advances_through_stops() {
+        let bookmark =
+            |x| TourStop::Bookmark(LookTransform::new(DVec3::new(x, 0.0, 1.0), DVec3::ZERO));
+        let mut tour = CameraTour::new(vec![bookmark(0.0), bookmark(1.0)])
+            .with_dwell(1.0)
+            .with_transition(2.0, Easing::Linear);
+
+        tour.advance(1.0);
+        assert_relative_eq!(tour.transition_progress(), 0.5);
+        tour.advance(1.5);
+        assert_eq!(tour.current_stop(), Some(&bookmark(0.0)));
+        assert_relative_eq!(tour.transition_progress(), 1.0);
+
+        tour.advance(0.5);
+        assert_eq!(tour.current_stop(), Some(&bookmark(1.0)));
+        assert_relative_eq!(tour.transition_progress(), 0.0);
+        tour.advance(3.0);
+        assert!(tour.is_finished());
+
+        let mut looping = tour.clone().looping(true);
+        looping.rewind();
+        looping.advance(3.0);
+        looping.advance(3.0);
+        assert_eq!(looping.current_stop(), Some(&bookmark(0.0)));
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{
+    controllers::{CameraControlSystem, CameraInputState, ControllerDisableStack},
+    CameraPathPlayback, CameraPathPlugin, CameraSequence, CameraSequencePlugin, LookTransform,
+    LookTransformSystem, Smoother, SmootherSnapshot,
+};
+
+use bevy::{app::prelude::*, ecs::prelude::*};
+#[cfg(feature = "letterbox")]
+use bevy::{render::color::Color, ui::prelude::*};
+
+const CINEMATIC_REASON: &str = "cinematic";
+
+/// Takes a camera away from the player for a cutscene. See `CinematicEvent`.
+pub struct CinematicPlugin;
+
+impl Plugin for CinematicPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<CameraSequencePlugin>() {
+            app.add_plugin(CameraSequencePlugin);
+        }
+        if !app.is_plugin_added::<CameraPathPlugin>() {
+            app.add_plugin(CameraPathPlugin);
+        }
+
+        app.init_resource::<CameraInputState>()
+            .init_resource::<CinematicMode>()
+            .add_event::<CinematicEvent>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                cinematic_event_system.before(CameraControlSystem::EnabledChanged),
+            )
+            .add_system(cinematic_system.before(LookTransformSystem::Smooth));
+    }
+}
+
+/// What drives the camera during a cinematic.
+#[derive(Clone, Debug)]
+pub enum CinematicSource {
+    /// Play this sequence on the camera.
+    Sequence(CameraSequence),
+    /// Play the `CameraPath` resource on the camera.
+    Path { looping: bool },
+}
+
+/// Starts or ends a cinematic on a camera with a `LookTransform`.
+///
+/// On `Start`, input to all controllers is blocked through the `CameraInputState`, the controllers on the camera are
+/// disabled through its `ControllerDisableStack`, and the `source` drives the `LookTransform`. The cinematic ends on
+/// `End`, or once a sequence or non-looping path finishes, which restores the `LookTransform`, the `Smoother` and the
+/// controllers as they were on `Start`.
+#[derive(Clone, Debug)]
+pub enum CinematicEvent {
+    Start {
+        camera: Entity,
+        source: CinematicSource,
+    },
+    End {
+        camera: Entity,
+    },
+}
+
+/// Settings for cinematics, and the camera playing one.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct CinematicMode {
+    /// Fraction of the window height covered by each of the letterbox bars, with the `letterbox` feature. `0.0` hides
+    /// them.
+    pub letterbox: f32,
+    camera: Option<Entity>,
+}
+
+impl Default for CinematicMode {
+    fn default() -> Self {
+        Self {
+            letterbox: 0.12,
+            camera: None,
+        }
+    }
+}
+
+impl CinematicMode {
+    /// The camera playing a cinematic, if any. Only one camera plays a cinematic at a time.
+    pub fn camera(&self) -> Option<Entity> {
+        self.camera
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.camera.is_some()
+    }
+}
+
+/// Present on a camera while it plays a cinematic, with the state to restore when it ends.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct CinematicState {
+    transform: LookTransform,
+    smoother: Option<SmootherSnapshot>,
+}
+
+/// Marks the letterbox bars shown during cinematics.
+#[cfg(feature = "letterbox")]
+#[derive(Clone, Component, Copy, Debug)]
+pub struct LetterboxBar;
+
+#[allow(clippy::too_many_arguments)]
+fn cinematic_event_system(
+    mut commands: Commands,
+    mut events: EventReader<CinematicEvent>,
+    mut mode: ResMut<CinematicMode>,
+    mut input_state: ResMut<CameraInputState>,
+    mut cameras: Query<(
+        &mut LookTransform,
+        Option<&mut Smoother>,
+        Option<&mut ControllerDisableStack>,
+        Option<&CinematicState>,
+    )>,
+    #[cfg(feature = "letterbox")] bars: Query<Entity, With<LetterboxBar>>,
+) {
+    for event in events.iter() {
+        match event {
+            CinematicEvent::Start { camera, source } => {
+                if mode.is_active() {
+                    continue;
+                }
+                let (transform, smoother, stack, _) = if let Ok(c) = cameras.get_mut(*camera) {
+                    c
+                } else {
+                    continue;
+                };
+
+                commands.entity(*camera).insert(CinematicState {
+                    transform: *transform,
+                    smoother: smoother.map(|s| s.snapshot()),
+                });
+                if let Some(mut stack) = stack {
+                    stack.push_disabled(CINEMATIC_REASON);
+                } else {
+                    let mut stack = ControllerDisableStack::default();
+                    stack.push_disabled(CINEMATIC_REASON);
+                    commands.entity(*camera).insert(stack);
+                }
+                input_state.push_blocked(CINEMATIC_REASON);
+
+                match source {
+                    CinematicSource::Sequence(sequence) => {
+                        let mut sequence = sequence.clone();
+                        sequence.rewind();
+                        sequence.playing = true;
+                        commands.entity(*camera).insert(sequence);
+                    }
+                    CinematicSource::Path { looping } => {
+                        commands.entity(*camera).insert(CameraPathPlayback {
+                            playing: true,
+                            looping: *looping,
+                            elapsed: 0.0,
+                        });
+                    }
+                }
+
+                #[cfg(feature = "letterbox")]
+                if mode.letterbox > 0.0 {
+                    spawn_letterbox(&mut commands, mode.letterbox);
+                }
+                mode.camera = Some(*camera);
+            }
+            CinematicEvent::End { camera } => {
+                let (mut transform, smoother, stack, state) = match cameras.get_mut(*camera) {
+                    Ok((transform, smoother, stack, Some(state))) => {
+                        (transform, smoother, stack, *state)
+                    }
+                    _ => continue,
+                };
+
+                *transform = state.transform;
+                if let (Some(mut smoother), Some(snapshot)) = (smoother, state.smoother) {
+                    smoother.restore(&snapshot);
+                }
+                if let Some(mut stack) = stack {
+                    stack.pop_disabled(CINEMATIC_REASON);
+                }
+                input_state.pop_blocked(CINEMATIC_REASON);
+                commands
+                    .entity(*camera)
+                    .remove::<(CinematicState, CameraSequence, CameraPathPlayback)>();
+
+                #[cfg(feature = "letterbox")]
+                for bar in bars.iter() {
+                    commands.entity(bar).despawn();
+                }
+                mode.camera = None;
+            }
+        }
+    }
+}
+
+/// Keeps smoothing the camera while its controllers are disabled, and ends the cinematic once its source finishes.
+fn cinematic_system(
+    #[cfg(feature = "letterbox")] mut commands: Commands,
+    mut mode: ResMut<CinematicMode>,
+    mut events: EventWriter<CinematicEvent>,
+    mut input_state: ResMut<CameraInputState>,
+    mut cameras: Query<
+        (
+            Option<&mut Smoother>,
+            Option<&CameraSequence>,
+            Option<&CameraPathPlayback>,
+        ),
+        With<CinematicState>,
+    >,
+    #[cfg(feature = "letterbox")] bars: Query<Entity, With<LetterboxBar>>,
+) {
+    let camera = if let Some(camera) = mode.camera {
+        camera
+    } else {
+        return;
+    };
+    let (smoother, sequence, playback) = if let Ok(c) = cameras.get_mut(camera) {
+        c
+    } else {
+        // The camera was despawned mid-cinematic, so there's nothing to restore on it, but input and the screen are
+        // given back like on `End`.
+        input_state.pop_blocked(CINEMATIC_REASON);
+        #[cfg(feature = "letterbox")]
+        for bar in bars.iter() {
+            commands.entity(bar).despawn();
+        }
+        mode.camera = None;
+        return;
+    };
+
+    // Disabling the controllers disables the smoother too, which would freeze the camera.
+    if let Some(mut smoother) = smoother.filter(|s| !s.is_enabled()) {
+        smoother.set_enabled(true);
+    }
+
+    let finished = sequence.map_or(false, |s| !s.playing && s.is_finished())
+        || playback.map_or(false, |p| !p.playing);
+    if finished {
+        events.send(CinematicEvent::End { camera });
+    }
+}
+
+#[cfg(feature = "letterbox")]
+fn spawn_letterbox(commands: &mut Commands, height: f32) {
+    for top in [true, false] {
+        let edge = UiRect {
+            left: Val::Px(0.0),
+            top: if top { Val::Px(0.0) } else { Val::Undefined },
+            bottom: if top { Val::Undefined } else { Val::Px(0.0) },
+            ..Default::default()
+        };
+        commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: edge,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0 * height)),
+                    ..Default::default()
+                },
+                background_color: Color::BLACK.into(),
+                ..Default::default()
+            },
+            LetterboxBar,
+        ));
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        controllers::orbit::{OrbitCameraBundle, OrbitCameraController, OrbitCameraPlugin},
+        test_utils::*,
+        Shot, ShotKind,
+    };
+
+    use bevy::math::DVec3;
+
+    #[test]
+    fn test_cinematic_restores_controller() {
+        let mut app = headless_app();
+        app.add_plugin(OrbitCameraPlugin::new(true))
+            .add_plugin(CinematicPlugin);
+        let camera = app
+            .world
+            .spawn(OrbitCameraBundle::new(
+                OrbitCameraController::default(),
+                DVec3::Z,
+                DVec3::ZERO,
+            ))
+            .id();
+        step(&mut app, 2);
+        let before = look_transform(&app, camera);
+
+        let shot = Shot::new(
+            ShotKind::Hold(LookTransform::new(5.0 * DVec3::X, DVec3::ZERO)),
+            0.0,
+        );
+        let source = CinematicSource::Sequence(CameraSequence::new(vec![shot]));
+        send_control_event(&mut app, CinematicEvent::Start { camera, source });
+        step(&mut app, 1);
+        assert!(app.world.get::<CinematicState>(camera).is_some());
+        assert!(app.world.resource::<CameraInputState>().is_blocked());
+
+        // The sequence ends by itself.
+        step(&mut app, 6);
+        assert!(!app.world.resource::<CinematicMode>().is_active());
+        assert!(!app.world.resource::<CameraInputState>().is_blocked());
+        let controller = app.world.get::<OrbitCameraController>(camera).unwrap();
+        assert!(controller.enabled);
+        let after = look_transform(&app, camera);
+        assert!(after.eye.abs_diff_eq(before.eye, 1e-9));
+    }
+
+    #[test]
+    fn test_cinematic_camera_despawned() {
+        let mut app = headless_app();
+        app.add_plugin(OrbitCameraPlugin::new(true))
+            .add_plugin(CinematicPlugin);
+        let camera = app
+            .world
+            .spawn(OrbitCameraBundle::new(
+                OrbitCameraController::default(),
+                DVec3::Z,
+                DVec3::ZERO,
+            ))
+            .id();
+        step(&mut app, 2);
+
+        let shot = Shot::new(
+            ShotKind::Hold(LookTransform::new(5.0 * DVec3::X, DVec3::ZERO)),
+            10.0,
+        );
+        let source = CinematicSource::Sequence(CameraSequence::new(vec![shot]));
+        send_control_event(&mut app, CinematicEvent::Start { camera, source });
+        step(&mut app, 1);
+        assert!(app.world.resource::<CameraInputState>().is_blocked());
+
+        app.world.despawn(camera);
+        step(&mut app, 1);
+        assert!(!app.world.resource::<CinematicMode>().is_active());
+        assert!(!app.world.resource::<CameraInputState>().is_blocked());
+        #[cfg(feature = "letterbox")]
+        assert_eq!(
+            app.world
+                .query_filtered::<(), With<LetterboxBar>>()
+                .iter(&app.world)
+                .count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{
+    controllers::{
+        fps::FpsCameraController, orbit::OrbitCameraController, unreal::UnrealCameraController,
+    },
+    Smoother, SmoothingMode,
+};
+
+use bevy::{
+    app::prelude::*,
+    asset::{AddAsset, AssetEvent, AssetLoader, Assets, Handle, LoadContext, LoadedAsset},
+    ecs::prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::{Deserialize, Serialize};
+
+/// Loads `CameraConfig` assets and applies them to entities with a `CameraConfigHandle`, including on hot reload.
+pub struct CameraConfigPlugin;
+
+impl Plugin for CameraConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<CameraConfig>()
+            .init_asset_loader::<CameraConfigLoader>()
+            .add_system(apply_camera_config_system);
+    }
+}
+
+/// Controller and smoothing settings for a camera. Only the parts that are present are applied, and only to the matching
+/// components that already exist on the entity. The `enabled` flag of each controller is left untouched.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, TypeUuid)]
+#[uuid = "0b1cfa4e-5a5e-4c1b-9d1f-3c6f2f7f0a11"]
+pub struct CameraConfig {
+    #[serde(default)]
+    pub fps: Option<FpsCameraController>,
+    #[serde(default)]
+    pub orbit: Option<OrbitCameraController>,
+    #[serde(default)]
+    pub unreal: Option<UnrealCameraController>,
+    /// Overrides the `Smoother` lag weight, and the `smoothing_weight` of the controllers.
+    #[serde(default)]
+    pub smoothing_weight: Option<f64>,
+}
+
+/// Tags a camera entity with the config asset it should follow.
+#[derive(Clone, Component, Debug, Default)]
+pub struct CameraConfigHandle(pub Handle<CameraConfig>);
+
+#[derive(Default)]
+pub struct CameraConfigLoader;
+
+impl AssetLoader for CameraConfigLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let is_toml = load_context
+                .path()
+                .extension()
+                .map_or(false, |ext| ext == "toml");
+            let config = parse_camera_config(bytes, is_toml)?;
+            load_context.set_default_asset(LoadedAsset::new(config));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["camera.ron", "camera.toml"]
+    }
+}
+
+fn parse_camera_config(bytes: &[u8], is_toml: bool) -> Result<CameraConfig, anyhow::Error> {
+    Ok(if is_toml {
+        toml::from_str(std::str::from_utf8(bytes)?)?
+    } else {
+        ron::de::from_bytes(bytes)?
+    })
+}
+
+fn apply_camera_config_system(
+    mut asset_events: EventReader<AssetEvent<CameraConfig>>,
+    configs: Res<Assets<CameraConfig>>,
+    mut cameras: Query<(
+        &CameraConfigHandle,
+        ChangeTrackers<CameraConfigHandle>,
+        Option<&mut FpsCameraController>,
+        Option<&mut OrbitCameraController>,
+        Option<&mut UnrealCameraController>,
+        Option<&mut Smoother>,
+    )>,
+) {
+    let mut changed_handles = Vec::new();
+    for event in asset_events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                changed_handles.push(handle.clone_weak());
+            }
+            AssetEvent::Removed { .. } => (),
+        }
+    }
+
+    for (config_handle, trackers, fps, orbit, unreal, mut smoother) in cameras.iter_mut() {
+        if !trackers.is_changed() && !changed_handles.contains(&config_handle.0) {
+            continue;
+        }
+        let config = if let Some(config) = configs.get(&config_handle.0) {
+            config
+        } else {
+            continue;
+        };
+
+        if let (Some(mut controller), Some(new)) = (fps, config.fps) {
+            *controller = FpsCameraController {
+                enabled: controller.enabled,
+                smoothing_weight: config.smoothing_weight.unwrap_or(new.smoothing_weight),
+                ..new
+            };
+        }
+        if let (Some(mut controller), Some(new)) = (orbit, config.orbit) {
+            *controller = OrbitCameraController {
+                enabled: controller.enabled,
+                smoothing_weight: config.smoothing_weight.unwrap_or(new.smoothing_weight),
+                ..new
+            };
+            // Fall back to the merged smoothing weight, and drop weights removed from the file.
+            if let Some(smoother) = &mut smoother {
+                let weights = controller.orbit_lag_weights();
+                if weights.is_some() {
+                    smoother.set_mode(SmoothingMode::Orbit);
+                }
+                smoother.set_orbit_lag_weights(weights);
+            }
+        }
+        if let (Some(mut controller), Some(new)) = (unreal, config.unreal) {
+            *controller = UnrealCameraController {
+                enabled: controller.enabled,
+                smoothing_weight: config.smoothing_weight.unwrap_or(new.smoothing_weight),
+                ..new
+            };
+        }
+        if let (Some(mut smoother), Some(weight)) = (smoother, config.smoothing_weight) {
+            smoother.set_lag_weight(weight);
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    use bevy::asset::AssetPlugin;
+
+    #[test]
+    fn test_parse_ron() {
+        let ron = "(fps: Some((translate_sensitivity: 2.0)), smoothing_weight: Some(0.5))";
+        let config = parse_camera_config(ron.as_bytes(), false).unwrap();
+
+        let fps = config.fps.unwrap();
+        assert_eq!(fps.translate_sensitivity, 2.0);
+        // Missing fields keep their defaults.
+        let default = FpsCameraController::default();
+        assert_eq!(fps.acceleration, default.acceleration);
+        assert!(config.orbit.is_none());
+        assert!(config.unreal.is_none());
+        assert_eq!(config.smoothing_weight, Some(0.5));
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let toml = "smoothing_weight = 0.5\n[orbit]\nmouse_wheel_zoom_sensitivity = 0.5\n";
+        let config = parse_camera_config(toml.as_bytes(), true).unwrap();
+
+        let orbit = config.orbit.unwrap();
+        assert_eq!(orbit.mouse_wheel_zoom_sensitivity, 0.5);
+        let default = OrbitCameraController::default().touchpad_zoom_sensitivity;
+        assert_eq!(orbit.touchpad_zoom_sensitivity, default);
+        assert!(config.fps.is_none());
+        assert_eq!(config.smoothing_weight, Some(0.5));
+
+        assert!(parse_camera_config(b"", true).unwrap().fps.is_none());
+    }
+
+    #[test]
+    fn test_config_reload_applies() {
+        let mut app = headless_app();
+        app.add_plugin(AssetPlugin::default())
+            .add_plugin(CameraConfigPlugin);
+        let config = CameraConfig {
+            fps: Some(FpsCameraController {
+                translate_sensitivity: 2.0,
+                ..Default::default()
+            }),
+            smoothing_weight: Some(0.5),
+            ..Default::default()
+        };
+        let handle = app.world.resource_mut::<Assets<CameraConfig>>().add(config);
+        let camera = app
+            .world
+            .spawn((
+                FpsCameraController {
+                    enabled: false,
+                    ..Default::default()
+                },
+                Smoother::new(0.9),
+                CameraConfigHandle(handle.clone()),
+            ))
+            .id();
+        step(&mut app, 1);
+
+        let controller = app.world.get::<FpsCameraController>(camera).unwrap();
+        assert_eq!(controller.translate_sensitivity, 2.0);
+        assert_eq!(controller.smoothing_weight, 0.5);
+        assert!(!controller.enabled);
+        let smoother = app.world.get::<Smoother>(camera).unwrap();
+        assert_eq!(smoother.lag_weight(), 0.5);
+
+        // Editing the asset is how a hot reload shows up.
+        let mut configs = app.world.resource_mut::<Assets<CameraConfig>>();
+        let config = configs.get_mut(&handle).unwrap();
+        config.fps.as_mut().unwrap().translate_sensitivity = 3.0;
+        config.smoothing_weight = Some(0.25);
+        step(&mut app, 2);
+
+        let controller = app.world.get::<FpsCameraController>(camera).unwrap();
+        assert_eq!(controller.translate_sensitivity, 3.0);
+        assert!(!controller.enabled);
+        let smoother = app.world.get::<Smoother>(camera).unwrap();
+        assert_eq!(smoother.lag_weight(), 0.25);
+    }
+}
@@ -3,15 +3,877 @@ mod macros {
     #[macro_export]
     macro_rules! define_on_controller_enabled_changed(($ControllerStruct:ty) => {
         fn on_controller_enabled_changed(
-            mut smoothers: Query<(&mut Smoother, &$ControllerStruct), Changed<$ControllerStruct>>,
+            mut applied_weights: ResMut<$crate::controllers::AppliedWeights<$ControllerStruct, f64>>,
+            mut smoothers: Query<
+                (Entity, &mut Smoother, &$ControllerStruct),
+                Changed<$ControllerStruct>,
+            >,
         ) {
-            for (mut smoother, controller) in smoothers.iter_mut() {
-                smoother.set_enabled(controller.enabled);
+            for (entity, mut smoother, controller) in smoothers.iter_mut() {
+                if smoother.is_enabled() != controller.enabled {
+                    smoother.set_enabled(controller.enabled);
+                }
+                // Picks up a new `smoothing_weight` live, e.g. from a settings slider. Only a weight that changed since
+                // it was last seen is applied, so a custom `Smoother` given to the bundle keeps its own until then.
+                let weight = controller.smoothing_weight;
+                let changed = applied_weights
+                    .weights
+                    .insert(entity, weight)
+                    .map_or(false, |applied| applied != weight);
+                if changed && smoother.lag_weight() != weight {
+                    smoother.set_lag_weight(weight);
+                }
             }
         }
     });
+
+    #[macro_export]
+    macro_rules! define_bundle_builder(($Builder:ident, $Bundle:ident, $Controller:ty) => {
+        #[doc = concat!(
+            "Builds a `", stringify!($Bundle), "` one setting at a time. See `",
+            stringify!($Bundle), "::builder`."
+        )]
+        pub struct $Builder {
+            controller: $Controller,
+            transform: LookTransform,
+            smoother: Option<Smoother>,
+        }
+
+        impl $Bundle {
+            /// Starts from the default controller, at the origin looking down -Z.
+            pub fn builder() -> $Builder {
+                $Builder {
+                    controller: Default::default(),
+                    transform: LookTransform::default(),
+                    smoother: None,
+                }
+            }
+        }
+
+        impl $Builder {
+            pub fn controller(mut self, controller: $Controller) -> Self {
+                self.controller = controller;
+                self
+            }
+
+            pub fn eye(mut self, eye: DVec3) -> Self {
+                self.transform.eye = eye;
+                self
+            }
+
+            pub fn target(mut self, target: DVec3) -> Self {
+                self.transform.target = target;
+                self
+            }
+
+            /// The world's up axis. See `LookTransform::up`.
+            pub fn up(mut self, up: DVec3) -> Self {
+                self.transform.up = up;
+                self
+            }
+
+            pub fn smoothing_weight(mut self, smoothing_weight: f64) -> Self {
+                self.controller.smoothing_weight = smoothing_weight;
+                self
+            }
+
+            /// Use `smoother` instead of one made from the controller's smoothing settings.
+            pub fn smoother(mut self, smoother: Smoother) -> Self {
+                self.smoother = Some(smoother);
+                self
+            }
+
+            pub fn build(self) -> $Bundle {
+                let LookTransform { eye, target, up } = self.transform;
+                let bundle = $Bundle::new(self.controller, eye, target).with_up(up);
+                match self.smoother {
+                    Some(smoother) => bundle.with_smoother(smoother),
+                    None => bundle,
+                }
+            }
+        }
+    });
+}
+
+use crate::{LookAngles, LookTransform, MouseFilter, ResponseCurve, Smoother};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{
+        event::Events,
+        prelude::*,
+        schedule::{ShouldRun, SystemLabel},
+    },
+    input::{
+        keyboard::KeyCode,
+        mouse::{MouseScrollUnit, MouseWheel},
+        Input,
+    },
+    math::prelude::*,
+    reflect::{FromReflect, Reflect},
+    render::camera::Projection,
+    utils::HashMap,
+    window::{CursorGrabMode, WindowFocused, Windows},
+};
+use serde::{Deserialize, Serialize};
+use std::{any::TypeId, marker::PhantomData};
+
+/// Labels shared by the systems of all built-in controller plugins.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, SystemLabel)]
+pub enum CameraControlSystem {
+    /// The default input maps, which turn device input into `ControlEvent`s. Systems that send their own `ControlEvent`s
+    /// should run before `Control`.
+    Input,
+    /// The control systems, which apply `ControlEvent`s to the `LookTransform`.
+    Control,
+    /// The systems in `CoreStage::PreUpdate` that enable or disable a camera's `Smoother` when its controller is toggled.
+    EnabledChanged,
+}
+
+/// Returns `true` if there is a primary window and it doesn't have focus.
+pub(crate) fn is_primary_window_unfocused(windows: Option<&Windows>) -> bool {
+    windows
+        .and_then(|w| w.get_primary())
+        .map_or(false, |w| !w.is_focused())
+}
+
+pub(crate) fn primary_window_unfocused(windows: Option<Res<Windows>>) -> ShouldRun {
+    if is_primary_window_unfocused(windows.as_deref()) {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+/// Drops all pending `ControlEvent`s, so that none of them reach the control system.
+pub(crate) fn discard_control_events<E: Send + Sync + 'static>(mut events: ResMut<Events<E>>) {
+    events.clear();
+}
+
+/// A stack of reasons to suspend camera input, consulted by the input maps of all built-in controllers. Push a reason
+/// when opening a menu, focusing a text field or showing a modal dialog, and pop it when that closes, instead of
+/// toggling each controller's `enabled` field.
+#[derive(Clone, Debug, Default, Resource)]
+pub struct CameraInputState {
+    blockers: Vec<&'static str>,
+}
+
+impl CameraInputState {
+    /// Blocks camera input until `reason` is popped again.
+    pub fn push_blocked(&mut self, reason: &'static str) {
+        self.blockers.push(reason);
+    }
+
+    /// Removes the most recent block pushed with `reason`, returning `false` if there wasn't one.
+    pub fn pop_blocked(&mut self, reason: &'static str) -> bool {
+        if let Some(i) = self.blockers.iter().rposition(|r| *r == reason) {
+            self.blockers.remove(i);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_blocked(&self) -> bool {
+        !self.blockers.is_empty()
+    }
+
+    /// The reasons input is blocked, oldest first.
+    pub fn blockers(&self) -> &[&'static str] {
+        &self.blockers
+    }
+}
+
+/// Suppresses mouse motion in the default input maps for a few frames after the primary window regains focus, the cursor
+/// gets grabbed or camera input is unblocked, since the first motion events after any of them can contain a huge
+/// accumulated delta.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct MouseMotionSuppression {
+    /// How many frames of mouse motion to ignore.
+    pub frames: u32,
+    remaining: u32,
+}
+
+impl Default for MouseMotionSuppression {
+    fn default() -> Self {
+        Self {
+            frames: 2,
+            remaining: 0,
+        }
+    }
+}
+
+impl MouseMotionSuppression {
+    pub fn is_active(&self) -> bool {
+        self.remaining > 0
+    }
+
+    /// Start suppressing motion, as if focus was just regained.
+    pub fn trigger(&mut self) {
+        self.remaining = self.frames;
+    }
+}
+
+/// Adds the `MouseMotionSuppression` resource and its update system, unless another controller plugin already did.
+pub(crate) fn add_mouse_motion_suppression(app: &mut App) {
+    if app.world.contains_resource::<MouseMotionSuppression>() {
+        return;
+    }
+    app.init_resource::<MouseMotionSuppression>()
+        .add_system_to_stage(CoreStage::PreUpdate, update_mouse_motion_suppression);
+}
+
+fn update_mouse_motion_suppression(
+    mut suppression: ResMut<MouseMotionSuppression>,
+    mut focus_events: EventReader<WindowFocused>,
+    windows: Option<Res<Windows>>,
+    input_state: Option<Res<CameraInputState>>,
+    mut last_grab_mode: Local<Option<CursorGrabMode>>,
+    mut was_blocked: Local<bool>,
+) {
+    if suppression.remaining > 0 {
+        suppression.remaining -= 1;
+    }
+
+    let regained_focus = focus_events.iter().any(|e| e.focused);
+
+    let grab_mode = windows
+        .as_deref()
+        .and_then(|w| w.get_primary())
+        .map(|w| w.cursor_grab_mode());
+    let grabbed = last_grab_mode.is_some()
+        && grab_mode != *last_grab_mode
+        && grab_mode != Some(CursorGrabMode::None);
+    *last_grab_mode = grab_mode;
+
+    let is_blocked = input_state.map_or(false, |s| s.is_blocked());
+    let unblocked = *was_blocked && !is_blocked;
+    *was_blocked = is_blocked;
+
+    if regained_focus || grabbed || unblocked {
+        suppression.trigger();
+    }
+}
+
+/// Registers the reflected types shared by the controllers' fields, so controllers can be saved in scenes.
+pub(crate) fn register_controller_types(app: &mut App) {
+    app.register_type::<MouseFilter>()
+        .register_type::<ResponseCurve>()
+        .register_type::<ScrollSensitivity>()
+        .register_type::<WheelAction>()
+        .register_type::<EdgePan>()
+        .register_type::<FocusPolicy>()
+        .register_type::<KeyCode>()
+        .register_type::<Option<KeyCode>>()
+        .register_type::<Option<f64>>();
+}
+
+/// A controller component with an `enabled` field.
+pub(crate) trait EnabledController: Component {
+    fn enabled_mut(&mut self) -> &mut bool;
+}
+
+/// A stack of reasons to disable the controllers of one camera, so that several systems, like a pause menu and a
+/// cutscene, can each disable it without re-enabling it while another still needs it disabled. While any reason is
+/// pushed, every controller on the entity is kept disabled. Popping the last reason restores the `enabled` each
+/// controller had, or the last value anything else wrote to it in the meantime.
+#[derive(Clone, Component, Debug, Default)]
+pub struct ControllerDisableStack {
+    reasons: Vec<&'static str>,
+    wanted: Vec<(TypeId, bool)>,
+}
+
+impl ControllerDisableStack {
+    /// Disables the controllers until `reason` is popped again.
+    pub fn push_disabled(&mut self, reason: &'static str) {
+        self.reasons.push(reason);
+    }
+
+    /// Removes the most recent `reason` pushed, returning `false` if there wasn't one.
+    pub fn pop_disabled(&mut self, reason: &'static str) -> bool {
+        if let Some(i) = self.reasons.iter().rposition(|r| *r == reason) {
+            self.reasons.remove(i);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        !self.reasons.is_empty()
+    }
+
+    /// The reasons the controllers are disabled, oldest first.
+    pub fn reasons(&self) -> &[&'static str] {
+        &self.reasons
+    }
+}
+
+/// Adds a system that applies each camera's `ControllerDisableStack` to its controller of type `C`.
+pub(crate) fn add_disable_stack_system<C: EnabledController>(app: &mut App) {
+    app.add_system_to_stage(
+        CoreStage::PreUpdate,
+        apply_disable_stack::<C>.before(CameraControlSystem::EnabledChanged),
+    );
+}
+
+/// The weights that controllers of type `C` last applied to each camera's `Smoother`, so that only weights changed since
+/// are applied again.
+#[derive(Resource)]
+pub(crate) struct AppliedWeights<C, W> {
+    pub(crate) weights: HashMap<Entity, W>,
+    marker: PhantomData<C>,
+}
+
+impl<C, W> Default for AppliedWeights<C, W> {
+    fn default() -> Self {
+        Self {
+            weights: HashMap::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+pub(crate) fn add_applied_weights<C: Component, W: Send + Sync + 'static>(app: &mut App) {
+    app.init_resource::<AppliedWeights<C, W>>()
+        // Removals are only tracked until the end of the frame, so this runs after all of the other stages.
+        .add_system_to_stage(CoreStage::Last, forget_applied_weights::<C, W>);
+}
+
+/// Forgets the weights of cameras that lost their controller or were despawned.
+fn forget_applied_weights<C: Component, W: Send + Sync + 'static>(
+    mut removed: RemovedComponents<C>,
+    mut applied_weights: ResMut<AppliedWeights<C, W>>,
+) {
+    for entity in removed.iter() {
+        applied_weights.weights.remove(&entity);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn apply_disable_stack<C: EnabledController>(
+    mut cameras: Query<
+        (&mut ControllerDisableStack, &mut C),
+        Or<(Changed<ControllerDisableStack>, Changed<C>)>,
+    >,
+) {
+    let type_id = TypeId::of::<C>();
+    for (mut stack, mut controller) in cameras.iter_mut() {
+        // Read without marking the controller as changed.
+        let enabled = *controller.bypass_change_detection().enabled_mut();
+        let stack = stack.bypass_change_detection();
+        let wanted = stack.wanted.iter().position(|(id, _)| *id == type_id);
+        let new_enabled = match (stack.is_disabled(), wanted) {
+            (true, Some(i)) => {
+                // Anything enabling or disabling the controller meanwhile takes effect once the stack is empty. This
+                // system's own writes don't count as changes the next time it runs.
+                if controller.is_changed() {
+                    stack.wanted[i].1 = enabled;
+                }
+                false
+            }
+            (true, None) => {
+                stack.wanted.push((type_id, enabled));
+                false
+            }
+            (false, Some(i)) => stack.wanted.remove(i).1,
+            (false, None) => continue,
+        };
+        if enabled != new_enabled {
+            *controller.enabled_mut() = new_enabled;
+        }
+    }
 }
 
+struct ToggleKey<C> {
+    key: KeyCode,
+    grab_cursor: bool,
+    marker: PhantomData<C>,
+}
+
+impl<C: Component> Resource for ToggleKey<C> {}
+
+/// Adds a system that flips `enabled` on all controllers of type `C` when `key` is pressed. With `grab_cursor`, the cursor
+/// is also grabbed and hidden while a controller is enabled.
+pub(crate) fn add_toggle_key<C: EnabledController>(app: &mut App, key: KeyCode, grab_cursor: bool) {
+    app.insert_resource(ToggleKey::<C> {
+        key,
+        grab_cursor,
+        marker: PhantomData,
+    })
+    .add_system(toggle_controllers::<C>.before(CameraControlSystem::Input));
+}
+
+fn toggle_controllers<C: EnabledController>(
+    toggle: Res<ToggleKey<C>>,
+    keyboard: Res<Input<KeyCode>>,
+    input_state: Res<CameraInputState>,
+    windows: Option<ResMut<Windows>>,
+    mut controllers: Query<&mut C>,
+) {
+    if input_state.is_blocked() || !keyboard.just_pressed(toggle.key) {
+        return;
+    }
+
+    let mut any_enabled = false;
+    for mut controller in controllers.iter_mut() {
+        let enabled = controller.enabled_mut();
+        *enabled = !*enabled;
+        any_enabled |= *enabled;
+    }
+
+    let mut windows = match windows {
+        Some(windows) if toggle.grab_cursor => windows,
+        _ => return,
+    };
+    if let Some(window) = windows.get_primary_mut() {
+        window.set_cursor_grab_mode(if any_enabled {
+            CursorGrabMode::Locked
+        } else {
+            CursorGrabMode::None
+        });
+        window.set_cursor_visibility(!any_enabled);
+    }
+}
+
+/// Scale for pan and zoom speeds at `radius` when adaptive sensitivity is enabled: `radius` raised to `exponent`, clamped
+/// to `[min_scale, max_scale]`.
+pub fn adaptive_sensitivity_scale(radius: f64, exponent: f64, min_scale: f64, max_scale: f64) -> f64 {
+    radius.powf(exponent).clamp(min_scale, max_scale)
+}
+
+/// Converts `MouseWheel` events to lines of scrolling, with a separate sensitivity for each `MouseScrollUnit`. Mouse
+/// wheels usually report lines and touchpads pixels, so without this a touchpad scrolls far faster than a wheel.
+#[derive(Clone, Copy, Debug, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub struct ScrollSensitivity {
+    /// Lines per line of scrolling
+    pub line: f64,
+    /// Lines per pixel of scrolling
+    pub pixel: f64,
+}
+
+impl Default for ScrollSensitivity {
+    fn default() -> Self {
+        Self {
+            line: 1.0,
+            pixel: 1.0 / 53.0,
+        }
+    }
+}
+
+impl ScrollSensitivity {
+    /// The horizontal and vertical scrolling of `event`, in lines.
+    pub fn lines(&self, event: &MouseWheel) -> DVec2 {
+        let sensitivity = match event.unit {
+            MouseScrollUnit::Line => self.line,
+            MouseScrollUnit::Pixel => self.pixel,
+        };
+        sensitivity * DVec2::new(event.x as f64, event.y as f64)
+    }
+}
+
+/// Pans the camera while the cursor is near an edge of the primary window, as in strategy games. Nothing happens
+/// while the cursor is outside the window, such as on another monitor, or while the window doesn't have focus.
+#[derive(Clone, Copy, Debug, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub struct EdgePan {
+    pub enabled: bool,
+    /// Width in logical pixels of the band along each edge that pans. The speed ramps up toward the edge.
+    pub margin: f64,
+    /// Units per second at the very edge
+    pub speed: f64,
+}
+
+impl Default for EdgePan {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            margin: 10.0,
+            speed: 10.0,
+        }
+    }
+}
+
+impl EdgePan {
+    /// The direction to pan in for a cursor at `cursor` in a window of `window_size`, both in logical pixels from the
+    /// bottom left. +X is right and +Y is up, with each axis between `-1.0` and `1.0`.
+    pub fn direction(&self, cursor: DVec2, window_size: DVec2) -> DVec2 {
+        if !self.enabled || self.margin <= 0.0 {
+            return DVec2::ZERO;
+        }
+        let axis = |position: f64, size: f64| {
+            let low = (1.0 - position / self.margin).clamp(0.0, 1.0);
+            let high = (1.0 - (size - position) / self.margin).clamp(0.0, 1.0);
+            high - low
+        };
+        DVec2::new(axis(cursor.x, window_size.x), axis(cursor.y, window_size.y))
+    }
+
+    /// The pan direction for the cursor in the primary window, if it has focus.
+    pub(crate) fn window_direction(&self, windows: Option<&Windows>) -> DVec2 {
+        let window = match windows.and_then(|w| w.get_primary()) {
+            Some(window) if self.enabled && window.is_focused() => window,
+            _ => return DVec2::ZERO,
+        };
+        window.cursor_position().map_or(DVec2::ZERO, |cursor| {
+            self.direction(
+                cursor.as_dvec2(),
+                DVec2::new(window.width() as f64, window.height() as f64),
+            )
+        })
+    }
+}
+
+/// What scrolling the mouse wheel does for a controller. Each controller defaults to the behavior it has always had.
+#[derive(Clone, Copy, Debug, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub enum WheelAction {
+    /// Zoom toward the target, or move along the view direction for controllers without one
+    Zoom,
+    /// Narrow (scrolling up) or widen the field of view of a perspective `Projection`
+    Fov,
+    /// Change how fast the camera moves
+    Speed,
+    None,
+}
+
+/// How much one line of scrolling scales the field of view or speed for `WheelAction::Fov` and `WheelAction::Speed`.
+pub const WHEEL_STEP_FACTOR: f64 = 1.1;
+
+/// The range of fields of view, in radians, that `WheelAction::Fov` keeps within.
+pub const WHEEL_FOV_RANGE: (f32, f32) = (0.02, 2.5);
+
+/// The factor that `lines` of scrolling scale a field of view or speed by.
+pub(crate) fn wheel_factor(lines: f64) -> f64 {
+    WHEEL_STEP_FACTOR.powf(lines)
+}
+
+/// Divides the field of view of a perspective `projection` by `factor`, within `WHEEL_FOV_RANGE`.
+pub(crate) fn zoom_fov(projection: Option<&mut Projection>, factor: f64) {
+    if let Some(Projection::Perspective(perspective)) = projection {
+        let (min, max) = WHEEL_FOV_RANGE;
+        perspective.fov = (perspective.fov / factor as f32).max(min).min(max);
+    }
+}
+
+/// How the FPS and unreal controllers place the eye and target once a frame of movement and rotation events has been
+/// applied, so the focus distance can't drift depending on how the events interleave.
+#[derive(Clone, Copy, Debug, Default, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub enum FocusPolicy {
+    /// Turn around the eye, keeping the target `focus_distance` in front of it.
+    #[default]
+    PreserveRadius,
+    /// Turn around the target, keeping the eye `focus_distance` behind it. Movement carries the target along.
+    PreserveTarget,
+    /// Turn around the eye, keeping the target as far from the moved eye as the old target was, so moving toward the
+    /// target shortens the focus distance, down to `MIN_FOCUS_DISTANCE`.
+    PreserveEye,
+}
+
+/// The shortest focus distance `FocusPolicy::PreserveEye` keeps, so walking into the target can't collapse it.
+pub const MIN_FOCUS_DISTANCE: f64 = 0.1;
+
+impl FocusPolicy {
+    /// Points `transform` along `direction`, after events moved its eye from where it was in `start`. Without a
+    /// `focus_distance`, the radius of `start` is kept.
+    pub(crate) fn apply(
+        self,
+        transform: &mut LookTransform,
+        start: &LookTransform,
+        direction: DVec3,
+        focus_distance: Option<f64>,
+    ) {
+        let distance = focus_distance.unwrap_or_else(|| start.radius());
+        match self {
+            Self::PreserveRadius => {
+                transform.target = transform.eye + distance * direction;
+            }
+            Self::PreserveTarget => {
+                transform.target = start.target + (transform.eye - start.eye);
+                transform.eye = transform.target - distance * direction;
+            }
+            Self::PreserveEye => {
+                let distance = transform.radius().max(MIN_FOCUS_DISTANCE);
+                transform.target = transform.eye + distance * direction;
+            }
+        }
+    }
+}
+
+/// Reported by the control systems when a camera's `LookTransform` couldn't be used as is and had to be repaired.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraError {
+    /// The eye and target coincided, so there was no direction to look in. The target was moved back in front of the
+    /// eye, along `recovered_direction`.
+    DegenerateLookDirection {
+        camera: Entity,
+        recovered_direction: DVec3,
+    },
+}
+
+/// The direction a camera looks in after its eye and target coincided, when there's no smoothed transform from the
+/// previous frame to recover the direction from. Defaults to -Z.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct FallbackLookDirection(pub DVec3);
+
+impl Default for FallbackLookDirection {
+    fn default() -> Self {
+        Self(-DVec3::Z)
+    }
+}
+
+/// Returns the look direction of `transform`. If there is none, because the eye and target coincide, the target is moved
+/// back in front of the eye first: along the smoothed direction of the previous frame, or else the fallback direction.
+/// Healthy transforms aren't marked as changed.
+pub(crate) fn look_direction_or_recover(
+    camera: Entity,
+    transform: &mut Mut<LookTransform>,
+    smoother: Option<&Smoother>,
+    fallback: Option<&FallbackLookDirection>,
+    errors: &mut EventWriter<CameraError>,
+) -> DVec3 {
+    if let Some(direction) = transform.look_direction() {
+        return direction;
+    }
+
+    let previous = smoother.and_then(Smoother::smoothed_transform);
+    if !transform.eye.is_finite() {
+        transform.eye = previous.map_or(DVec3::ZERO, |p| p.eye);
+    }
+    let direction = previous
+        .and_then(|p| p.look_direction())
+        .or_else(|| fallback.and_then(|f| f.0.try_normalize()))
+        .unwrap_or(-DVec3::Z);
+    let distance = previous
+        .map(|p| p.radius())
+        .filter(|r| r.is_finite() && *r > 0.0)
+        .unwrap_or(1.0);
+    transform.target = transform.eye + distance * direction;
+
+    errors.send(CameraError::DegenerateLookDirection {
+        camera,
+        recovered_direction: direction,
+    });
+
+    direction
+}
+
+/// Quantizes yaw and pitch to multiples of an increment while angle snapping is held. The unsnapped angles are kept
+/// while snapping, so that motions smaller than the increment still add up.
+#[derive(Debug, Default)]
+pub(crate) struct AngleSnap {
+    unsnapped: Option<(f64, f64)>,
+}
+
+impl AngleSnap {
+    /// Rotates `angles` by the deltas, snapping the result to multiples of `increment` radians if it's `Some`.
+    pub(crate) fn rotate(
+        &mut self,
+        angles: &mut LookAngles,
+        yaw_delta: f64,
+        pitch_delta: f64,
+        increment: Option<f64>,
+    ) {
+        let increment = if let Some(increment) = increment.filter(|i| *i > 0.0) {
+            increment
+        } else {
+            self.unsnapped = None;
+            angles.add_yaw(yaw_delta);
+            angles.add_pitch(pitch_delta);
+            return;
+        };
+
+        let (yaw, pitch) = self
+            .unsnapped
+            .get_or_insert((angles.get_yaw(), angles.get_pitch()));
+        *yaw += yaw_delta;
+        *pitch += pitch_delta;
+        angles.set_yaw((*yaw / increment).round() * increment);
+        angles.set_pitch((*pitch / increment).round() * increment);
+    }
+}
+
+/// Longest time between two clicks, in seconds, for them to count as a double click.
+pub const DOUBLE_CLICK_SECONDS: f64 = 0.3;
+
+/// Tracks presses of a button to detect double clicks.
+#[derive(Debug, Default)]
+pub(crate) struct DoubleClickDetector {
+    last_press: Option<f64>,
+}
+
+impl DoubleClickDetector {
+    /// Returns `true` if `just_pressed` completes a double click at time `now`, in seconds.
+    pub(crate) fn update(&mut self, just_pressed: bool, now: f64) -> bool {
+        if !just_pressed {
+            return false;
+        }
+        match self.last_press {
+            Some(last) if now - last <= DOUBLE_CLICK_SECONDS => {
+                self.last_press = None;
+                true
+            }
+            _ => {
+                self.last_press = Some(now);
+                false
+            }
+        }
+    }
+}
+
+pub mod fixed;
 pub mod fps;
+pub mod map;
 pub mod orbit;
+pub mod photo_mode;
+pub mod spectator;
 pub mod unreal;
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        controllers::{
+            fixed::{FixedCameraBundle, FixedCameraController, FixedCameraPlugin},
+            fps::{FpsCameraBundle, FpsCameraController, FpsCameraPlugin},
+        },
+        test_utils::*,
+    };
+
+    #[test]
+    fn test_disable_stack() {
+        let mut app = headless_app();
+        app.add_plugin(FixedCameraPlugin);
+        let controller = FixedCameraController::new(Entity::from_raw(u32::MAX));
+        let camera = app
+            .world
+            .spawn(FixedCameraBundle::new(controller, DVec3::Z, DVec3::ZERO))
+            .id();
+        let enabled = |app: &App| {
+            app.world
+                .get::<FixedCameraController>(camera)
+                .unwrap()
+                .enabled
+        };
+        let mut stack = ControllerDisableStack::default();
+        stack.push_disabled("pause");
+        app.world.entity_mut(camera).insert(stack);
+        step(&mut app, 1);
+        assert!(!enabled(&app));
+
+        // Nested reasons keep it disabled until the last is popped.
+        let mut stack = app.world.get_mut::<ControllerDisableStack>(camera).unwrap();
+        stack.push_disabled("cutscene");
+        assert!(stack.pop_disabled("pause"));
+        step(&mut app, 1);
+        assert!(!enabled(&app));
+        let mut stack = app.world.get_mut::<ControllerDisableStack>(camera).unwrap();
+        assert!(stack.pop_disabled("cutscene"));
+        step(&mut app, 1);
+        assert!(enabled(&app));
+
+        // Enabled and then deliberately disabled while held, so it stays disabled.
+        let mut stack = app.world.get_mut::<ControllerDisableStack>(camera).unwrap();
+        stack.push_disabled("pause");
+        step(&mut app, 1);
+        app.world
+            .get_mut::<FixedCameraController>(camera)
+            .unwrap()
+            .enabled = true;
+        step(&mut app, 1);
+        assert!(!enabled(&app));
+        app.world
+            .get_mut::<FixedCameraController>(camera)
+            .unwrap()
+            .enabled = false;
+        step(&mut app, 1);
+        let mut stack = app.world.get_mut::<ControllerDisableStack>(camera).unwrap();
+        assert!(stack.pop_disabled("pause"));
+        step(&mut app, 1);
+        assert!(!enabled(&app));
+    }
+
+    #[test]
+    fn test_preserve_eye_keeps_min_focus_distance() {
+        // The eye moved onto the target.
+        let start = LookTransform::new(DVec3::ZERO, DVec3::Z);
+        let mut transform = LookTransform::new(DVec3::Z, DVec3::Z);
+        FocusPolicy::PreserveEye.apply(&mut transform, &start, DVec3::Z, None);
+
+        assert!((transform.radius() - MIN_FOCUS_DISTANCE).abs() < 1e-9);
+        assert_eq!(transform.look_direction(), Some(DVec3::Z));
+    }
+
+    #[test]
+    fn test_custom_smoother_survives_spawn() {
+        let mut app = headless_app();
+        app.add_plugin(FpsCameraPlugin::new(true));
+        let camera = app
+            .world
+            .spawn(
+                FpsCameraBundle::new(FpsCameraController::default(), DVec3::ZERO, DVec3::Z)
+                    .with_smoother(Smoother::new(0.3)),
+            )
+            .id();
+        step(&mut app, 2);
+
+        let smoother = app.world.get::<Smoother>(camera).unwrap();
+        assert_eq!(smoother.lag_weight(), 0.3);
+    }
+
+    #[test]
+    fn test_smoothing_weight_applies_live() {
+        let mut app = headless_app();
+        app.add_plugin(FpsCameraPlugin::new(true));
+        let camera = app
+            .world
+            .spawn(FpsCameraBundle::new(
+                FpsCameraController::default(),
+                DVec3::ZERO,
+                DVec3::Z,
+            ))
+            .id();
+        step(&mut app, 1);
+
+        app.world
+            .get_mut::<FpsCameraController>(camera)
+            .unwrap()
+            .smoothing_weight = 0.5;
+        step(&mut app, 1);
+
+        let smoother = app.world.get::<Smoother>(camera).unwrap();
+        assert_eq!(smoother.lag_weight(), 0.5);
+        assert!(smoother.is_enabled());
+    }
+
+    #[test]
+    fn test_applied_weights_forgotten_on_despawn() {
+        let mut app = headless_app();
+        app.add_plugin(FpsCameraPlugin::new(true));
+        let camera = app
+            .world
+            .spawn(FpsCameraBundle::new(
+                FpsCameraController::default(),
+                DVec3::ZERO,
+                DVec3::Z,
+            ))
+            .id();
+        step(&mut app, 1);
+        let applied = |app: &App| {
+            app.world
+                .resource::<AppliedWeights<FpsCameraController, f64>>()
+                .weights
+                .contains_key(&camera)
+        };
+        assert!(applied(&app));
+
+        app.world.despawn(camera);
+        step(&mut app, 1);
+        assert!(!applied(&app));
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{
+    controllers::{
+        add_applied_weights, add_disable_stack_system, CameraControlSystem, EnabledController,
+    },
+    LookTransform, LookTransformBundle, LookTransformSystem, Smoother,
+};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{
+        bundle::Bundle,
+        entity::{EntityMap, MapEntities, MapEntitiesError},
+        prelude::*,
+        reflect::ReflectMapEntities,
+    },
+    math::DVec3,
+    reflect::{FromReflect, Reflect},
+    transform::components::{GlobalTransform, Transform},
+};
+
+/// Turns fixed cameras toward the entities they track. There is no input.
+#[derive(Default)]
+pub struct FixedCameraPlugin;
+
+impl Plugin for FixedCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<FixedCameraController>();
+        add_disable_stack_system::<FixedCameraController>(app);
+        add_applied_weights::<FixedCameraController, f64>(app);
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            on_controller_enabled_changed.label(CameraControlSystem::EnabledChanged),
+        )
+        .add_system(
+            control_system
+                .label(CameraControlSystem::Control)
+                .after(CameraControlSystem::Input)
+                .before(LookTransformSystem::Smooth),
+        );
+    }
+}
+
+#[derive(Bundle)]
+pub struct FixedCameraBundle {
+    controller: FixedCameraController,
+    look_transform: LookTransformBundle,
+    transform: Transform,
+}
+
+impl FixedCameraBundle {
+    /// Place the camera at `eye`, initially looking at `target` until the tracked entity is found.
+    pub fn new(controller: FixedCameraController, eye: DVec3, target: DVec3) -> Self {
+        // Make sure the transform is consistent with the controller to start.
+        let transform = Transform::from_translation(eye).looking_at(target, DVec3::Y);
+
+        Self {
+            controller,
+            look_transform: LookTransformBundle {
+                transform: LookTransform::new(eye, target),
+                smoother: Smoother::new(controller.smoothing_weight),
+            },
+            transform,
+        }
+    }
+
+    /// Use `up` as the world's up axis instead of +Y. See `LookTransform::up`.
+    pub fn with_up(mut self, up: DVec3) -> Self {
+        self.look_transform.transform.up = up;
+        self.transform = self.look_transform.transform.into();
+        self
+    }
+}
+
+/// A stationary camera that turns to follow an entity, like a security camera or the fixed angles of survival horror
+/// games. The eye never moves, so the `Smoother` only smooths the point being looked at.
+#[derive(Clone, Component, Copy, Debug, FromReflect, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct FixedCameraController {
+    pub enabled: bool,
+    /// The entity to look at
+    pub target: Entity,
+    /// Offset from the target's origin to the point looked at, in world space
+    pub target_offset: DVec3,
+    /// The greater, the slower the camera turns to follow the target
+    pub smoothing_weight: f64,
+}
+
+impl FixedCameraController {
+    pub fn new(target: Entity) -> Self {
+        Self {
+            enabled: true,
+            target,
+            target_offset: DVec3::ZERO,
+            smoothing_weight: 0.95,
+        }
+    }
+}
+
+// Only meant for reflection, which needs a value to load a saved controller into.
+impl FromWorld for FixedCameraController {
+    fn from_world(_world: &mut World) -> Self {
+        Self::new(Entity::from_raw(u32::MAX))
+    }
+}
+
+impl MapEntities for FixedCameraController {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        self.target = entity_map.get(self.target)?;
+        Ok(())
+    }
+}
+
+define_on_controller_enabled_changed!(FixedCameraController);
+
+impl EnabledController for FixedCameraController {
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+pub fn control_system(
+    mut cameras: Query<(&FixedCameraController, &mut LookTransform)>,
+    targets: Query<&GlobalTransform>,
+) {
+    for (controller, mut transform) in cameras.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
+        let target = if let Ok(target) = targets.get(controller.target) {
+            target.translation() + controller.target_offset
+        } else {
+            continue;
+        };
+        // Looking at the eye itself has no direction.
+        if target != transform.target && target != transform.eye {
+            transform.target = target;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn test_eye_stays_while_target_follows() {
+        let mut app = headless_app();
+        app.add_plugin(FixedCameraPlugin);
+        let tracked = app
+            .world
+            .spawn(GlobalTransform::from_xyz(10.0, 0.0, 0.0))
+            .id();
+        let controller = FixedCameraController {
+            target_offset: DVec3::Y,
+            ..FixedCameraController::new(tracked)
+        };
+        let eye = DVec3::new(0.0, 5.0, -5.0);
+        let camera = app
+            .world
+            .spawn(FixedCameraBundle::new(controller, eye, DVec3::ZERO))
+            .id();
+        step(&mut app, 1);
+
+        let transform = look_transform(&app, camera);
+        assert_eq!(transform.eye, eye);
+        assert_eq!(transform.target, DVec3::new(10.0, 1.0, 0.0));
+
+        *app.world.get_mut::<GlobalTransform>(tracked).unwrap() =
+            GlobalTransform::from_xyz(-10.0, 0.0, 20.0);
+        step(&mut app, 10);
+
+        let transform = look_transform(&app, camera);
+        assert_eq!(transform.eye, eye);
+        assert_eq!(transform.target, DVec3::new(-10.0, 1.0, 20.0));
+        // Only the point looked at is smoothed, the camera itself never moves.
+        let translation = scene_transform(&app, camera).translation;
+        assert!(translation.abs_diff_eq(eye, 1e-9));
+    }
+}
@@ -1,38 +1,161 @@
-use crate::{LookAngles, LookTransform, LookTransformBundle, Smoother};
+use crate::{
+    add_transform_offset_system, apply_deadzone,
+    controllers::{
+        add_applied_weights, add_disable_stack_system, add_mouse_motion_suppression,
+        add_toggle_key, discard_control_events, look_direction_or_recover,
+        primary_window_unfocused, register_controller_types, wheel_factor, zoom_fov,
+        CameraControlSystem, CameraError, CameraInputState, EnabledController,
+        FallbackLookDirection, FocusPolicy, MouseMotionSuppression, ScrollSensitivity, WheelAction,
+    },
+    up_axis_rotation, KeyRepeat, KeyboardNavigation, LookAngles, LookTransform,
+    LookTransformBundle, LookTransformSystem, MouseFilter, MouseFilterState, ResponseCurve,
+    Smoother, TouchJoystick, TransformOffset, MOUSE_CURVE_REFERENCE,
+};
 
 use bevy::{
     app::prelude::*,
     ecs::{bundle::Bundle, prelude::*},
-    input::{mouse::MouseMotion, prelude::*},
+    input::{
+        mouse::{MouseMotion, MouseWheel},
+        prelude::*,
+    },
     math::prelude::*,
-    transform::components::Transform,
+    reflect::{FromReflect, Reflect},
+    render::camera::Projection,
+    time::{FixedTimestep, FixedTimesteps, Time},
+    transform::components::{GlobalTransform, Transform},
+    utils::{HashMap, HashSet},
+    window::{CursorGrabMode, Windows},
 };
 use bevy::math::{DVec2, DVec3};
 //use bevy::reflect::TypeData;
 use serde::{Deserialize, Serialize};
 
+/// Label of the FPS controller's fixed timestep in `FixedTimesteps`, with `FpsCameraPlugin::fixed_timestep`.
+pub const FIXED_TIMESTEP_LABEL: &str = "fps_camera_fixed_timestep";
+
 #[derive(Default)]
 pub struct FpsCameraPlugin {
     pub override_input_system: bool,
+    /// Run the control system on a fixed timestep of this many seconds instead of every frame, for deterministic motion.
+    /// Input maps and smoothing still run every frame. Steps longer than two frames may drop some input.
+    pub fixed_timestep: Option<f64>,
+    /// Ignore all input while the primary window doesn't have focus.
+    pub disable_when_unfocused: bool,
+    /// Add the keyboard-only input map, configured by the `KeyboardNavigation` resource. This works alongside the default
+    /// input map.
+    pub keyboard_navigation: bool,
+    /// Pressing this key will flip `enabled` on all fps controllers, and grab the cursor while they're enabled, to
+    /// release the mouse for UI.
+    pub toggle_key: Option<KeyCode>,
+    /// Add the on-screen touch input map, configured by the `TouchJoystick` resource. This works alongside the default
+    /// input map.
+    pub touch_joystick: bool,
 }
 
 impl FpsCameraPlugin {
     pub fn new(override_input_system: bool) -> Self {
         Self {
             override_input_system,
+            fixed_timestep: None,
+            disable_when_unfocused: false,
+            keyboard_navigation: false,
+            toggle_key: None,
+            touch_joystick: false,
         }
     }
+
+    pub fn with_fixed_timestep(mut self, step: f64) -> Self {
+        self.fixed_timestep = Some(step);
+        self
+    }
+
+    pub fn with_disable_when_unfocused(mut self, disable_when_unfocused: bool) -> Self {
+        self.disable_when_unfocused = disable_when_unfocused;
+        self
+    }
+
+    pub fn with_keyboard_navigation(mut self, keyboard_navigation: bool) -> Self {
+        self.keyboard_navigation = keyboard_navigation;
+        self
+    }
+
+    pub fn with_toggle_key(mut self, key: KeyCode) -> Self {
+        self.toggle_key = Some(key);
+        self
+    }
+
+    pub fn with_touch_joystick(mut self, touch_joystick: bool) -> Self {
+        self.touch_joystick = touch_joystick;
+        self
+    }
 }
 
 impl Plugin for FpsCameraPlugin {
     fn build(&self, app: &mut App) {
-        let app = app
-            .add_system_to_stage(CoreStage::PreUpdate, on_controller_enabled_changed)
-            .add_system(control_system)
-            .add_event::<ControlEvent>();
+        app.init_resource::<CameraInputState>()
+            .register_type::<FpsCameraController>()
+            .register_type::<MouseButton>();
+        register_controller_types(app);
+        add_disable_stack_system::<FpsCameraController>(app);
+        add_applied_weights::<FpsCameraController, f64>(app);
 
+        let mut systems = SystemSet::new().with_system(
+            control_system
+                .label(CameraControlSystem::Control)
+                .after(CameraControlSystem::Input)
+                .before(LookTransformSystem::Smooth),
+        );
         if !self.override_input_system {
-            app.add_system(default_input_map);
+            app.add_system(default_input_map.label(CameraControlSystem::Input));
+            add_mouse_motion_suppression(app);
+            app.add_system(hold_to_look_cursor_system.before(CameraControlSystem::Input));
+        }
+        if self.keyboard_navigation {
+            app.add_system(keyboard_input_map.label(CameraControlSystem::Input));
+            app.init_resource::<KeyboardNavigation>();
+        }
+        if self.touch_joystick {
+            app.add_system(touch_input_map.label(CameraControlSystem::Input));
+            app.init_resource::<TouchJoystick>();
+        }
+        if let Some(step) = self.fixed_timestep {
+            systems = systems
+                .with_run_criteria(FixedTimestep::step(step).with_label(FIXED_TIMESTEP_LABEL));
+        }
+
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            on_controller_enabled_changed.label(CameraControlSystem::EnabledChanged),
+        )
+        .add_system_to_stage(CoreStage::PreUpdate, add_transform_offset_system::<EyeHeight>)
+        .add_system_set(systems)
+        .add_system(
+            body_attachment_system
+                .after(CameraControlSystem::Control)
+                .before(LookTransformSystem::Smooth),
+        )
+        .add_system(
+            eye_height_system
+                .after(LookTransformSystem::Smooth)
+                .before(LookTransformSystem::Offset),
+        )
+        .add_event::<ControlEvent>()
+        .add_event::<CameraControlEvent>()
+        .add_event::<MovementIntent>()
+        .add_event::<CameraError>();
+
+        if let Some(key) = self.toggle_key {
+            add_toggle_key::<FpsCameraController>(app, key, true);
+        }
+
+        if self.disable_when_unfocused {
+            app.add_system(
+                discard_control_events::<ControlEvent>
+                    .with_run_criteria(primary_window_unfocused)
+                    .after(CameraControlSystem::Input)
+                    .before(CameraControlSystem::Control),
+            );
         }
     }
 }
@@ -63,14 +186,79 @@ impl FpsCameraBundle {
             transform,
         }
     }
+
+    /// Use `up` as the world's up axis instead of +Y. See `LookTransform::up`.
+    pub fn with_up(mut self, up: DVec3) -> Self {
+        self.look_transform.transform.up = up;
+        self.transform = self.look_transform.transform.into();
+        self
+    }
+
+    /// Use `smoother` instead of one made from the controller's smoothing settings.
+    pub fn with_smoother(mut self, smoother: Smoother) -> Self {
+        self.look_transform.smoother = smoother;
+        self
+    }
+}
+
+define_bundle_builder!(FpsCameraBundleBuilder, FpsCameraBundle, FpsCameraController);
+
+impl FpsCameraBundleBuilder {
+    pub fn mouse_rotate_sensitivity(mut self, mouse_rotate_sensitivity: DVec2) -> Self {
+        self.controller.mouse_rotate_sensitivity = mouse_rotate_sensitivity;
+        self
+    }
+
+    pub fn translate_sensitivity(mut self, translate_sensitivity: f64) -> Self {
+        self.controller.translate_sensitivity = translate_sensitivity;
+        self
+    }
 }
 
 /// Your typical first-person camera controller.
-#[derive(Clone, Component, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Component, Copy, Debug, Deserialize, FromReflect, Reflect, Serialize)]
+#[reflect(Component)]
+#[serde(default)]
 pub struct FpsCameraController {
     pub enabled: bool,
     pub mouse_rotate_sensitivity: DVec2,
+    /// Only look around with the mouse while `look_button` is held, and leave the cursor free otherwise, as in level
+    /// editors. Moving with the keyboard and gamepad works either way.
+    pub hold_to_look: bool,
+    pub look_button: MouseButton,
+    /// Filter applied to cursor deltas before they rotate the camera
+    pub mouse_filter: MouseFilter,
+    /// Radians per frame for each rotation axis at full deflection of the right gamepad stick
+    pub gamepad_rotate_sensitivity: DVec2,
+    /// Response curve for mouse and gamepad rotation
+    pub rotate_response_curve: ResponseCurve,
     pub translate_sensitivity: f64,
+    /// Stick deflection ignored on the gamepad's left stick, which moves the camera
+    pub gamepad_stick_deadzone: f64,
+    /// Trigger travel ignored before the triggers dolly the camera forward (right) and back (left)
+    pub gamepad_trigger_deadzone: f64,
+    /// Distance per frame at fully pressed triggers
+    pub gamepad_trigger_dolly_sensitivity: f64,
+    /// Speed multiplier while sprinting, toggled by clicking the left stick and stopped when the stick is released
+    pub gamepad_sprint_multiplier: f64,
+    /// Fraction of the gap to the requested velocity closed every 1/60 s while moving, between `0.0` and `1.0`
+    pub acceleration: f64,
+    /// Fraction of the current velocity removed every 1/60 s while no movement is requested, between `0.0` and `1.0`
+    pub friction: f64,
+    /// Rotate around the camera's own axes with quaternions instead of yaw and pitch, so the camera can look straight up
+    /// or down and loop over. `LookTransform::up` follows the camera, so the horizon can tilt.
+    pub free_look: bool,
+    /// Hold to crouch, moving the eye to the crouching height of the camera's `EyeHeight` component
+    pub crouch_key: KeyCode,
+    /// Normalizes mouse wheel and touchpad scrolling
+    pub scroll_sensitivity: ScrollSensitivity,
+    /// What the mouse wheel does. `WheelAction::Zoom` moves forward and back by `translate_sensitivity` per line, and
+    /// `WheelAction::Speed` scales `translate_sensitivity`.
+    pub wheel_action: WheelAction,
+    /// Which point stays put when the camera turns. Ignored with `free_look`.
+    pub focus_policy: FocusPolicy,
+    /// Distance from the eye to the target to keep. `None` keeps the distance the camera had at the start of each frame.
+    pub focus_distance: Option<f64>,
     pub smoothing_weight: f64,
 }
 
@@ -79,27 +267,212 @@ impl Default for FpsCameraController {
         Self {
             enabled: true,
             mouse_rotate_sensitivity: DVec2::splat(0.002),
+            hold_to_look: false,
+            look_button: MouseButton::Right,
+            mouse_filter: MouseFilter::None,
+            gamepad_rotate_sensitivity: DVec2::splat(0.04),
+            rotate_response_curve: ResponseCurve::Linear,
             translate_sensitivity: 0.5,
+            gamepad_stick_deadzone: 0.1,
+            gamepad_trigger_deadzone: 0.05,
+            gamepad_trigger_dolly_sensitivity: 0.5,
+            gamepad_sprint_multiplier: 2.0,
+            acceleration: 0.25,
+            friction: 0.25,
+            free_look: false,
+            crouch_key: KeyCode::C,
+            scroll_sensitivity: ScrollSensitivity::default(),
+            wheel_action: WheelAction::None,
+            focus_policy: FocusPolicy::PreserveRadius,
+            focus_distance: None,
             smoothing_weight: 0.9,
         }
     }
 }
 
+/// An offset of the eye along `LookTransform::up` for an FPS camera, with crouching, applied as a
+/// [`TransformOffset`](crate::TransformOffset). The eye eases to a new height rather than popping, independently of the
+/// `Smoother`.
+///
+/// When the camera follows a character controller, set the heights from its capsule, and changes in the capsule's
+/// height will ease in the same way.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct EyeHeight {
+    /// Offset while standing
+    pub standing: f64,
+    /// Offset while crouching
+    pub crouching: f64,
+    /// How quickly the eye follows a change of height, per second
+    pub rate: f64,
+    pub crouched: bool,
+    current: Option<f64>,
+}
+
+impl Default for EyeHeight {
+    fn default() -> Self {
+        Self::new(0.0, -0.7)
+    }
+}
+
+impl EyeHeight {
+    pub fn new(standing: f64, crouching: f64) -> Self {
+        Self {
+            standing,
+            crouching,
+            rate: 10.0,
+            crouched: false,
+            current: None,
+        }
+    }
+
+    /// Sets the heights, for example from a character controller whose capsule changed size. The eye eases to them.
+    pub fn set_heights(&mut self, standing: f64, crouching: f64) {
+        self.standing = standing;
+        self.crouching = crouching;
+    }
+
+    /// The height the eye is easing toward.
+    pub fn target(&self) -> f64 {
+        if self.crouched {
+            self.crouching
+        } else {
+            self.standing
+        }
+    }
+
+    /// The current offset of the eye.
+    pub fn current(&self) -> f64 {
+        self.current.unwrap_or_else(|| self.target())
+    }
+
+    /// Moves the eye toward `target` for a frame of `dt` seconds and returns the new offset.
+    pub fn update(&mut self, dt: f64) -> f64 {
+        let target = self.target();
+        let current = self.current();
+        let blend = 1.0 - (-self.rate * dt).exp();
+        let new = current + (target - current) * blend;
+        self.current = Some(new);
+        new
+    }
+}
+
+/// Hands movement of an FPS camera to a character controller. While attached, `control_system` doesn't move the eye,
+/// but sends a `MovementIntent` for the `body` instead, and the eye follows the body's `GlobalTransform` plus
+/// `eye_offset`. The camera keeps its own look direction and smoothing.
+///
+/// The camera shouldn't be a child of the body in the hierarchy, because `LookTransform` is in world space.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct FpsBodyAttachment {
+    pub body: Entity,
+    /// Offset of the eye from the body's origin, in world space
+    pub eye_offset: DVec3,
+}
+
+impl FpsBodyAttachment {
+    pub fn new(body: Entity, eye_offset: DVec3) -> Self {
+        Self { body, eye_offset }
+    }
+}
+
+/// Movement requested for the body of an FPS camera with an `FpsBodyAttachment`, sent each frame the camera is asked to
+/// move.
+#[derive(Clone, Copy, Debug)]
+pub struct MovementIntent {
+    pub camera: Entity,
+    pub body: Entity,
+    /// Unit direction of movement in world space
+    pub direction: DVec3,
+    /// Requested distance per frame, in the same units as `FpsCameraController::translate_sensitivity`
+    pub magnitude: f64,
+}
+
+fn body_attachment_system(
+    mut cameras: Query<(&FpsBodyAttachment, &mut LookTransform)>,
+    bodies: Query<&GlobalTransform>,
+) {
+    for (attachment, mut transform) in cameras.iter_mut() {
+        let body = if let Ok(body) = bodies.get(attachment.body) {
+            body
+        } else {
+            continue;
+        };
+        let eye = body.translation() + attachment.eye_offset;
+        if eye != transform.eye {
+            let delta = eye - transform.eye;
+            transform.eye = eye;
+            transform.target += delta;
+        }
+    }
+}
+
+fn eye_height_system(
+    time: Res<Time>,
+    mut cameras: Query<(
+        &mut EyeHeight,
+        &LookTransform,
+        Option<&Smoother>,
+        &mut TransformOffset,
+    )>,
+) {
+    for (mut eye_height, look_transform, smoother, mut offset) in cameras.iter_mut() {
+        let height = eye_height.update(time.delta_seconds_f64());
+        let smoothed = smoother
+            .and_then(|s| s.smoothed_transform())
+            .unwrap_or(*look_transform);
+        // The offset is local to the camera, so undo its rotation to move along the world's up axis.
+        offset.translate(smoothed.rotation().inverse() * (height * smoothed.up));
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum ControlEvent {
     Rotate(DVec2),
     TranslateEye(DVec3),
+    /// Start or stop crouching. See `EyeHeight`.
+    Crouch(bool),
+    /// Divide the field of view by this factor.
+    ZoomFov(f64),
+}
+
+/// A `ControlEvent` for a specific camera. `ControlEvent`s only control the first enabled camera, but these control any
+/// enabled camera, so several cameras can be driven at once, as in split screen.
+#[derive(Clone, Copy, Debug)]
+pub struct CameraControlEvent {
+    pub camera: Entity,
+    pub event: ControlEvent,
 }
 
 define_on_controller_enabled_changed!(FpsCameraController);
 
+impl EnabledController for FpsCameraController {
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
 pub fn default_input_map(
     mut events: EventWriter<ControlEvent>,
     keyboard: Res<Input<KeyCode>>,
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
     mut mouse_motion_events: EventReader<MouseMotion>,
-    controllers: Query<&FpsCameraController>,
+    motion_suppression: Res<MouseMotionSuppression>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    mut controllers: Query<&mut FpsCameraController>,
+    gamepads: Res<Gamepads>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepad_button_axes: Res<Axis<GamepadButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    time: Res<Time>,
+    input_state: Res<CameraInputState>,
+    mut filter_state: Local<MouseFilterState>,
+    mut sprinting: Local<HashSet<Gamepad>>,
 ) {
+    if input_state.is_blocked() {
+        return;
+    }
+
     // Can only control one camera at a time.
-    let controller = if let Some(controller) = controllers.iter().find(|c| {
+    let mut controller = if let Some(controller) = controllers.iter_mut().find(|c| {
         c.enabled
     }) {
         controller
@@ -109,6 +482,18 @@ pub fn default_input_map(
     let FpsCameraController {
         translate_sensitivity,
         mouse_rotate_sensitivity,
+        hold_to_look,
+        look_button,
+        mouse_filter,
+        gamepad_rotate_sensitivity,
+        rotate_response_curve,
+        gamepad_stick_deadzone,
+        gamepad_trigger_deadzone,
+        gamepad_trigger_dolly_sensitivity,
+        gamepad_sprint_multiplier,
+        crouch_key,
+        scroll_sensitivity,
+        wheel_action,
         ..
     } = *controller;
 
@@ -116,10 +501,72 @@ pub fn default_input_map(
     for event in mouse_motion_events.iter() {
         cursor_delta += DVec2::new(event.delta.x as f64, event.delta.y as f64);
     }
+    if motion_suppression.is_active() || (hold_to_look && !mouse_buttons.pressed(look_button)) {
+        cursor_delta = DVec2::ZERO;
+    }
+
+    let rotate_delta = rotate_response_curve.apply(
+        filter_state.filter(mouse_filter, cursor_delta, time.delta_seconds_f64()),
+        MOUSE_CURVE_REFERENCE,
+    );
+    if rotate_delta != DVec2::ZERO {
+        events.send(ControlEvent::Rotate(
+            mouse_rotate_sensitivity * rotate_delta,
+        ));
+    }
+
+    for gamepad in gamepads.iter() {
+        let axis = |axis_type| {
+            gamepad_axes
+                .get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or(0.0) as f64
+        };
+        // Stick up is positive, but should pitch up like moving the mouse up does.
+        let stick = DVec2::new(
+            axis(GamepadAxisType::RightStickX),
+            -axis(GamepadAxisType::RightStickY),
+        );
+        let stick = rotate_response_curve.apply(stick, 1.0);
+        if stick != DVec2::ZERO {
+            events.send(ControlEvent::Rotate(gamepad_rotate_sensitivity * stick));
+        }
 
-    events.send(ControlEvent::Rotate(
-        mouse_rotate_sensitivity * cursor_delta,
-    ));
+        let button = |button_type| GamepadButton::new(gamepad, button_type);
+        // +X is left and +Z is forward, like the keyboard.
+        let move_stick = DVec2::new(
+            -apply_deadzone(axis(GamepadAxisType::LeftStickX), gamepad_stick_deadzone),
+            apply_deadzone(axis(GamepadAxisType::LeftStickY), gamepad_stick_deadzone),
+        );
+        if gamepad_buttons.just_pressed(button(GamepadButtonType::LeftThumb))
+            && !sprinting.remove(&gamepad)
+        {
+            sprinting.insert(gamepad);
+        }
+        if move_stick == DVec2::ZERO {
+            sprinting.remove(&gamepad);
+        }
+        let speed = if sprinting.contains(&gamepad) {
+            gamepad_sprint_multiplier * translate_sensitivity
+        } else {
+            translate_sensitivity
+        };
+
+        let trigger = |button_type| {
+            let value = gamepad_button_axes.get(button(button_type)).unwrap_or(0.0) as f64;
+            apply_deadzone(value, gamepad_trigger_deadzone)
+        };
+        let dolly = trigger(GamepadButtonType::RightTrigger2)
+            - trigger(GamepadButtonType::LeftTrigger2);
+
+        let translation = DVec3::new(
+            speed * move_stick.x,
+            0.0,
+            speed * move_stick.y + gamepad_trigger_dolly_sensitivity * dolly,
+        );
+        if translation != DVec3::ZERO {
+            events.send(ControlEvent::TranslateEye(translation));
+        }
+    }
 
     for (key, dir) in [
         (KeyCode::W, DVec3::Z),
@@ -136,45 +583,465 @@ pub fn default_input_map(
             events.send(ControlEvent::TranslateEye(translate_sensitivity * dir));
         }
     }
+
+    if keyboard.just_pressed(crouch_key) {
+        events.send(ControlEvent::Crouch(true));
+    }
+    if keyboard.just_released(crouch_key) {
+        events.send(ControlEvent::Crouch(false));
+    }
+
+    let mut lines = 0.0;
+    for event in mouse_wheel_reader.iter() {
+        lines += scroll_sensitivity.lines(event).y;
+    }
+    if lines != 0.0 {
+        match wheel_action {
+            WheelAction::Zoom => {
+                events.send(ControlEvent::TranslateEye(lines * translate_sensitivity * DVec3::Z));
+            }
+            WheelAction::Fov => events.send(ControlEvent::ZoomFov(wheel_factor(lines))),
+            WheelAction::Speed => {
+                // Not a change of camera, so keep the smoother running.
+                controller.bypass_change_detection().translate_sensitivity *= wheel_factor(lines);
+            }
+            WheelAction::None => (),
+        }
+    }
 }
 
+/// Grabs and hides the cursor while the look button of the enabled `hold_to_look` controller is held.
+fn hold_to_look_cursor_system(
+    mouse_buttons: Res<Input<MouseButton>>,
+    input_state: Res<CameraInputState>,
+    windows: Option<ResMut<Windows>>,
+    controllers: Query<&FpsCameraController>,
+) {
+    let controller = controllers.iter().find(|c| c.enabled && c.hold_to_look);
+    let (mut windows, look_button) = match (windows, controller) {
+        (Some(windows), Some(controller)) => (windows, controller.look_button),
+        _ => return,
+    };
+    let grab = if mouse_buttons.just_pressed(look_button) && !input_state.is_blocked() {
+        true
+    } else if mouse_buttons.just_released(look_button) {
+        false
+    } else {
+        return;
+    };
+    if let Some(window) = windows.get_primary_mut() {
+        window.set_cursor_grab_mode(if grab {
+            CursorGrabMode::Locked
+        } else {
+            CursorGrabMode::None
+        });
+        window.set_cursor_visibility(!grab);
+    }
+}
+
+/// Input map for controlling the camera with on-screen touch controls. See `TouchJoystick`.
+pub fn touch_input_map(
+    mut events: EventWriter<ControlEvent>,
+    touches: Res<Touches>,
+    windows: Res<Windows>,
+    settings: Res<TouchJoystick>,
+    controllers: Query<&FpsCameraController>,
+    input_state: Res<CameraInputState>,
+    mut stick_touch: Local<Option<u64>>,
+) {
+    // Forget the stick touch once it's lifted, even while input is blocked.
+    if let Some(id) = *stick_touch {
+        if touches.get_pressed(id).is_none() {
+            *stick_touch = None;
+        }
+    }
+    if stick_touch.is_none() {
+        let window_width = windows.get_primary().map_or(0.0, |window| window.width());
+        *stick_touch = touches
+            .iter_just_pressed()
+            .find(|touch| settings.is_in_stick_zone(touch.start_position(), window_width))
+            .map(|touch| touch.id());
+    }
+
+    if input_state.is_blocked() {
+        return;
+    }
+
+    // Can only control one camera at a time.
+    if !controllers.iter().any(|c| c.enabled) {
+        return;
+    }
+
+    let mut look_delta = DVec2::ZERO;
+    for touch in touches.iter() {
+        if Some(touch.id()) == *stick_touch {
+            let stick = settings.stick(touch.start_position(), touch.position());
+            if stick != DVec2::ZERO {
+                // +X is left and +Z is forward, like the default input map.
+                events.send(ControlEvent::TranslateEye(
+                    settings.translate_sensitivity * DVec3::new(-stick.x, 0.0, stick.y),
+                ));
+            }
+        } else {
+            look_delta += touch.delta().as_dvec2();
+        }
+    }
+    if look_delta != DVec2::ZERO {
+        events.send(ControlEvent::Rotate(settings.rotate_sensitivity * look_delta));
+    }
+}
+
+/// Input map for controlling the camera with only the keyboard. See `KeyboardNavigation`.
+pub fn keyboard_input_map(
+    mut events: EventWriter<ControlEvent>,
+    keyboard: Res<Input<KeyCode>>,
+    settings: Res<KeyboardNavigation>,
+    controllers: Query<&FpsCameraController>,
+    time: Res<Time>,
+    input_state: Res<CameraInputState>,
+    mut repeat: Local<KeyRepeat>,
+) {
+    if input_state.is_blocked() {
+        return;
+    }
+
+    // Can only control one camera at a time.
+    if !controllers.iter().any(|c| c.enabled) {
+        return;
+    }
+
+    let steps = repeat.read(&settings, &keyboard, time.elapsed_seconds_f64());
+    if steps.rotate != DVec2::ZERO {
+        events.send(ControlEvent::Rotate(steps.rotate));
+    }
+    // Zooming moves forward and back.
+    let translation = DVec3::new(
+        -steps.pan.x,
+        steps.pan.y,
+        steps.zoom as f64 * settings.translate_step,
+    );
+    if translation != DVec3::ZERO {
+        events.send(ControlEvent::TranslateEye(translation));
+    }
+}
+
+/// Speed, in distance per frame, below which a braking camera stops, so its `Smoother` can settle.
+const REST_SPEED: f64 = 1e-6;
+
 pub fn control_system(
+    time: Res<Time>,
+    timesteps: Res<FixedTimesteps>,
     mut events: EventReader<ControlEvent>,
-    mut cameras: Query<(&FpsCameraController, &mut LookTransform)>,
+    mut camera_events: EventReader<CameraControlEvent>,
+    mut intents: EventWriter<MovementIntent>,
+    mut errors: EventWriter<CameraError>,
+    fallback: Option<Res<FallbackLookDirection>>,
+    mut cameras: Query<(
+        Entity,
+        &FpsCameraController,
+        &mut LookTransform,
+        Option<&mut EyeHeight>,
+        Option<&FpsBodyAttachment>,
+        Option<&mut Projection>,
+        Option<&Smoother>,
+    )>,
+    mut velocities: Local<HashMap<Entity, DVec3>>,
+    mut shared_events: Local<Vec<ControlEvent>>,
+    mut routed_events: Local<Vec<CameraControlEvent>>,
 ) {
-    // Can only control one camera at a time.
-    let mut transform =
-        if let Some((_, transform)) = cameras.iter_mut().find(|c| {
-            c.0.enabled
-        }) {
-            transform
+    // Each camera walks the events again, so buffer them, reusing last frame's allocations.
+    shared_events.clear();
+    shared_events.extend(events.iter().copied());
+    routed_events.clear();
+    routed_events.extend(camera_events.iter().copied());
+    // On a fixed timestep, this runs once per step rather than once per frame.
+    let dt = timesteps
+        .get(FIXED_TIMESTEP_LABEL)
+        .map_or(time.delta_seconds_f64(), |state| state.step());
+    // Events that don't name a camera can only control one camera at a time.
+    let first_enabled = cameras.iter().find(|c| c.1.enabled).map(|c| c.0);
+
+    for (entity, controller, mut transform, eye_height, attachment, projection, smoother) in
+        cameras.iter_mut()
+    {
+        if !controller.enabled {
+            velocities.remove(&entity);
+            continue;
+        }
+        let shared = if Some(entity) == first_enabled {
+            &shared_events[..]
         } else {
-            return;
+            &[]
         };
+        let mut routed = routed_events
+            .iter()
+            .filter(|e| e.camera == entity)
+            .map(|e| &e.event)
+            .peekable();
+        let velocity = velocities.entry(entity).or_default();
+        if Some(entity) != first_enabled && routed.peek().is_none() && *velocity == DVec3::ZERO {
+            continue;
+        }
+        let look_vector = look_direction_or_recover(
+            entity,
+            &mut transform,
+            smoother,
+            fallback.as_deref(),
+            &mut errors,
+        );
+        control_camera(
+            entity,
+            controller,
+            transform,
+            look_vector,
+            eye_height,
+            attachment,
+            projection,
+            velocity,
+            dt,
+            shared.iter().chain(routed),
+            &mut intents,
+        );
+    }
+}
 
-        let look_vector = transform.look_direction().unwrap();
-        let mut look_angles = LookAngles::from_vector(look_vector);
+#[allow(clippy::too_many_arguments)]
+fn control_camera<'a>(
+    entity: Entity,
+    controller: &FpsCameraController,
+    mut transform: Mut<LookTransform>,
+    look_vector: DVec3,
+    mut eye_height: Option<Mut<EyeHeight>>,
+    attachment: Option<&FpsBodyAttachment>,
+    mut projection: Option<Mut<Projection>>,
+    velocity: &mut DVec3,
+    dt: f64,
+    events: impl Iterator<Item = &'a ControlEvent>,
+    intents: &mut EventWriter<MovementIntent>,
+) {
+    let up = transform.up;
+    let start = *transform;
+    let mut look_angles = LookAngles::from_vector_with_up(look_vector, up);
 
-        let yaw_rot = DQuat::from_axis_angle(DVec3::Y, look_angles.get_yaw());
-        let rot_x = yaw_rot * DVec3::X;
-        let rot_y = yaw_rot * DVec3::Y;
-        let rot_z = yaw_rot * DVec3::Z;
+    let mut rotation = transform.rotation();
 
-        for event in events.iter() {
-            match event {
-                ControlEvent::Rotate(delta) => {
-                    // Rotates with pitch and yaw.
-                    look_angles.add_yaw(-delta.x);
-                    look_angles.add_pitch(-delta.y);
-                }
-                ControlEvent::TranslateEye(delta) => {
-                    // Translates up/down (Y) left/right (X) and forward/back (Z).
-                    transform.eye += delta.x * rot_x + delta.y * rot_y + delta.z * rot_z;
+    let (rot_x, rot_y, rot_z) = if controller.free_look {
+        // The camera looks down -Z, with +X to its right.
+        (rotation * -DVec3::X, rotation * DVec3::Y, rotation * -DVec3::Z)
+    } else {
+        let yaw_rot =
+            up_axis_rotation(up) * DQuat::from_axis_angle(DVec3::Y, look_angles.get_yaw());
+        (yaw_rot * DVec3::X, yaw_rot * DVec3::Y, yaw_rot * DVec3::Z)
+    };
+
+    let mut wish_velocity = DVec3::ZERO;
+    for event in events {
+        match event {
+            ControlEvent::Rotate(delta) if controller.free_look => {
+                // Rotates about the camera's own up and right axes.
+                rotation = rotation
+                    * DQuat::from_rotation_y(-delta.x)
+                    * DQuat::from_rotation_x(-delta.y);
+            }
+            ControlEvent::Rotate(delta) => {
+                // Rotates with pitch and yaw.
+                look_angles.add_yaw(-delta.x);
+                look_angles.add_pitch(-delta.y);
+            }
+            ControlEvent::TranslateEye(delta) => {
+                // Translates up/down (Y) left/right (X) and forward/back (Z).
+                wish_velocity += delta.x * rot_x + delta.y * rot_y + delta.z * rot_z;
+            }
+            ControlEvent::Crouch(crouched) => {
+                if let Some(eye_height) = eye_height.as_mut() {
+                    eye_height.crouched = *crouched;
                 }
             }
+            ControlEvent::ZoomFov(factor) => {
+                zoom_fov(projection.as_deref_mut(), *factor);
+            }
+        }
+    }
+
+    if let Some(attachment) = attachment {
+        // The character controller owns movement, including acceleration.
+        *velocity = DVec3::ZERO;
+        if wish_velocity != DVec3::ZERO {
+            intents.send(MovementIntent {
+                camera: entity,
+                body: attachment.body,
+                direction: wish_velocity.normalize(),
+                magnitude: wish_velocity.length(),
+            });
         }
+    } else {
+        // Ramp toward the requested velocity while moving, and brake toward rest otherwise. This is independent of
+        // the `Smoother`, which only affects the final `Transform`.
+        let rate = if wish_velocity == DVec3::ZERO {
+            controller.friction
+        } else {
+            controller.acceleration
+        };
+        let blend = 1.0 - (1.0 - rate.clamp(0.0, 1.0)).powf(dt * 60.0);
+        *velocity = velocity.lerp(wish_velocity, blend);
+        if wish_velocity == DVec3::ZERO && velocity.length_squared() < REST_SPEED * REST_SPEED {
+            // Braking only closes a fraction of the gap to rest, so stop outright once the camera barely moves.
+            *velocity = DVec3::ZERO;
+        }
+        if *velocity != DVec3::ZERO {
+            transform.eye += *velocity;
+        }
+    }
+
+    if controller.free_look {
+        transform.set_rotation(rotation.normalize());
+        return;
+    }
 
-        look_angles.assert_not_looking_up();
+    look_angles.assert_not_looking_up();
 
-        transform.target = transform.eye + transform.radius() * look_angles.unit_vector();
+    controller.focus_policy.apply(
+        &mut transform,
+        &start,
+        look_angles.unit_vector_with_up(up),
+        controller.focus_distance,
+    );
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    use bevy::input::{keyboard::KeyboardInput, ButtonState, InputPlugin};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn test_degenerate_look_direction_recovers() {
+        let mut app = headless_app();
+        app.add_plugin(FpsCameraPlugin::new(true));
+        let camera = app
+            .world
+            .spawn(FpsCameraBundle::new(
+                FpsCameraController::default(),
+                DVec3::ZERO,
+                DVec3::Z,
+            ))
+            .id();
+        step(&mut app, 2);
+
+        // A bad assignment from gameplay code.
+        app.world.get_mut::<LookTransform>(camera).unwrap().target = DVec3::ZERO;
+        send_control_event(&mut app, ControlEvent::Rotate(DVec2::new(0.1, 0.0)));
+        step(&mut app, 1);
+
+        assert!(look_transform(&app, camera).look_direction().is_some());
+        let errors = app.world.resource::<Events<CameraError>>();
+        let recovered = errors.get_reader().iter(errors).any(|e| {
+            matches!(e, CameraError::DegenerateLookDirection { camera: c, recovered_direction }
+                if *c == camera && recovered_direction.abs_diff_eq(DVec3::Z, 1e-6))
+        });
+        assert!(recovered);
+    }
+
+    #[test]
+    fn test_focus_distance_does_not_drift() {
+        let mut app = headless_app();
+        app.add_plugin(FpsCameraPlugin::new(true));
+        let camera = app
+            .world
+            .spawn(FpsCameraBundle::new(
+                FpsCameraController::default(),
+                DVec3::ZERO,
+                5.0 * DVec3::Z,
+            ))
+            .id();
+
+        for _ in 0..10 {
+            send_control_event(&mut app, ControlEvent::TranslateEye(DVec3::Z));
+            send_control_event(&mut app, ControlEvent::Rotate(DVec2::new(0.05, 0.0)));
+            step(&mut app, 1);
+        }
+
+        assert!((look_transform(&app, camera).radius() - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_camera_comes_to_rest() {
+        let mut app = headless_app();
+        app.add_plugin(FpsCameraPlugin::new(true));
+        let controller = FpsCameraController {
+            acceleration: 1.0,
+            friction: 0.9,
+            ..Default::default()
+        };
+        let camera = app
+            .world
+            .spawn(FpsCameraBundle::new(controller, DVec3::ZERO, DVec3::Z))
+            .id();
+
+        // The first frame has no elapsed time to accelerate over.
+        step(&mut app, 1);
+        thread::sleep(Duration::from_millis(5));
+        send_control_event(&mut app, ControlEvent::TranslateEye(DVec3::Z));
+        step(&mut app, 1);
+        assert!(look_transform(&app, camera).eye.z > 0.0);
+
+        let mut frames = 0;
+        loop {
+            let eye = look_transform(&app, camera).eye;
+            thread::sleep(Duration::from_millis(5));
+            step(&mut app, 1);
+            let transform = look_transform(&app, camera);
+            let smoother = app.world.get::<Smoother>(camera).unwrap();
+            if transform.eye == eye && smoother.is_settled(&transform) {
+                break;
+            }
+            frames += 1;
+            assert!(frames < 1000, "Camera never came to rest");
+        }
+    }
+
+    #[test]
+    fn test_fixed_timestep_keeps_presses() {
+        let mut app = headless_app();
+        app.add_plugin(InputPlugin).add_plugin(
+            FpsCameraPlugin::new(true)
+                .with_keyboard_navigation(true)
+                .with_fixed_timestep(0.1),
+        );
+        let camera = app
+            .world
+            .spawn(FpsCameraBundle::new(
+                FpsCameraController::default(),
+                DVec3::ZERO,
+                DVec3::Z,
+            ))
+            .id();
+        step(&mut app, 1);
+
+        // Pressed in a frame without a step, and released before the next step.
+        let key = |state| KeyboardInput {
+            scan_code: 0,
+            key_code: Some(KeyCode::Right),
+            state,
+        };
+        send_control_event(&mut app, key(ButtonState::Pressed));
+        step(&mut app, 1);
+        let direction = look_transform(&app, camera).look_direction().unwrap();
+        assert_eq!(direction, DVec3::Z);
+        thread::sleep(Duration::from_millis(150));
+        send_control_event(&mut app, key(ButtonState::Released));
+        step(&mut app, 1);
+
+        let direction = look_transform(&app, camera).look_direction().unwrap();
+        assert!(direction.x.abs() > 1e-3);
+    }
 }
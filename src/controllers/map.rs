@@ -0,0 +1,494 @@
+use crate::{
+    controllers::{
+        add_applied_weights, add_disable_stack_system, add_mouse_motion_suppression,
+        add_toggle_key, discard_control_events, look_direction_or_recover,
+        primary_window_unfocused, register_controller_types, CameraControlSystem, CameraError,
+        CameraInputState, EnabledController, FallbackLookDirection, MouseMotionSuppression,
+        ScrollSensitivity,
+    },
+    CameraRay, LookTransform, LookTransformBundle, LookTransformSystem, Smoother,
+};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{bundle::Bundle, prelude::*},
+    input::{
+        mouse::{MouseMotion, MouseWheel},
+        prelude::*,
+    },
+    math::prelude::*,
+    reflect::{FromReflect, Reflect},
+    render::camera::Camera,
+    transform::components::Transform,
+    window::Windows,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default)]
+pub struct MapCameraPlugin {
+    pub override_input_system: bool,
+    /// Ignore all input while the primary window doesn't have focus.
+    pub disable_when_unfocused: bool,
+    /// Pressing this key will flip `enabled` on all map controllers.
+    pub toggle_key: Option<KeyCode>,
+}
+
+impl MapCameraPlugin {
+    pub fn new(override_input_system: bool) -> Self {
+        Self {
+            override_input_system,
+            disable_when_unfocused: false,
+            toggle_key: None,
+        }
+    }
+
+    pub fn with_disable_when_unfocused(mut self, disable_when_unfocused: bool) -> Self {
+        self.disable_when_unfocused = disable_when_unfocused;
+        self
+    }
+
+    pub fn with_toggle_key(mut self, key: KeyCode) -> Self {
+        self.toggle_key = Some(key);
+        self
+    }
+}
+
+impl Plugin for MapCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraInputState>()
+            .register_type::<MapCameraController>();
+        register_controller_types(app);
+        add_disable_stack_system::<MapCameraController>(app);
+        add_applied_weights::<MapCameraController, f64>(app);
+
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            on_controller_enabled_changed.label(CameraControlSystem::EnabledChanged),
+        )
+        .add_system(
+            control_system
+                .label(CameraControlSystem::Control)
+                .after(CameraControlSystem::Input)
+                .before(LookTransformSystem::Smooth),
+        )
+        .add_event::<ControlEvent>()
+        .add_event::<CameraError>();
+
+        if !self.override_input_system {
+            app.add_system(default_input_map.label(CameraControlSystem::Input));
+            add_mouse_motion_suppression(app);
+        }
+
+        if let Some(key) = self.toggle_key {
+            add_toggle_key::<MapCameraController>(app, key, false);
+        }
+
+        if self.disable_when_unfocused {
+            app.add_system(
+                discard_control_events::<ControlEvent>
+                    .with_run_criteria(primary_window_unfocused)
+                    .after(CameraControlSystem::Input)
+                    .before(CameraControlSystem::Control),
+            );
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct MapCameraBundle {
+    controller: MapCameraController,
+    look_transform: LookTransformBundle,
+    transform: Transform,
+}
+
+impl MapCameraBundle {
+    pub fn new(controller: MapCameraController, eye: DVec3, target: DVec3) -> Self {
+        // Make sure the transform is consistent with the controller to start.
+        let transform = Transform::from_translation(eye).looking_at(target, DVec3::Y);
+
+        Self {
+            controller,
+            look_transform: LookTransformBundle {
+                transform: LookTransform::new(eye, target),
+                smoother: Smoother::new(controller.smoothing_weight),
+            },
+            transform,
+        }
+    }
+
+    /// Use `up` as the world's up axis instead of +Y. See `LookTransform::up`. The ground is perpendicular to it.
+    pub fn with_up(mut self, up: DVec3) -> Self {
+        self.look_transform.transform.up = up;
+        self.transform = self.look_transform.transform.into();
+        self
+    }
+}
+
+/// A camera that behaves like the controls of a web map. Dragging grabs the point of the ground under the cursor and
+/// keeps it there, the mouse wheel zooms toward the point under the cursor, and Ctrl+dragging rotates and tilts around
+/// the point where the drag started. Everything is computed in f64, so it stays precise at planet-scale coordinates.
+///
+/// The ground is the plane at `ground_height` along `LookTransform::up`. The default input map assumes the camera
+/// fills the primary window.
+#[derive(Clone, Component, Copy, Debug, Deserialize, FromReflect, Reflect, Serialize)]
+#[reflect(Component)]
+#[serde(default)]
+pub struct MapCameraController {
+    pub enabled: bool,
+    /// Rotation and tilt per pixel of Ctrl+dragging
+    pub mouse_rotate_sensitivity: DVec2,
+    pub mouse_wheel_zoom_sensitivity: f64,
+    /// Normalizes mouse wheel and touchpad scrolling
+    pub scroll_sensitivity: ScrollSensitivity,
+    /// Height of the ground along `LookTransform::up`
+    pub ground_height: f64,
+    /// Lowest height of the eye above the ground that zooming reaches
+    pub min_height: f64,
+    /// Highest height of the eye above the ground that zooming reaches
+    pub max_height: f64,
+    /// Smallest angle in radians between the view direction and straight down. Keep it above zero, since the view
+    /// can't look straight along `LookTransform::up`.
+    pub min_tilt: f64,
+    /// Largest angle in radians between the view direction and straight down
+    pub max_tilt: f64,
+    pub smoothing_weight: f64,
+}
+
+impl Default for MapCameraController {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mouse_rotate_sensitivity: DVec2::splat(0.005),
+            mouse_wheel_zoom_sensitivity: 0.15,
+            scroll_sensitivity: ScrollSensitivity::default(),
+            ground_height: 0.0,
+            min_height: 1.0,
+            max_height: 1e7,
+            min_tilt: 0.01,
+            max_tilt: 1.4,
+            smoothing_weight: 0.5,
+        }
+    }
+}
+
+/// Cursor positions are in logical pixels from the bottom left of the camera's viewport.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum ControlEvent {
+    /// Move the ground point under `from` to be under `to`.
+    Grab { from: DVec2, to: DVec2 },
+    /// Scale the distance to the ground point under `cursor` by this factor.
+    Zoom { cursor: DVec2, scalar: f64 },
+    /// Rotate and tilt around the ground point under this cursor position from now on.
+    StartOrbit(DVec2),
+    /// Rotate around `LookTransform::up` (X) and tilt (Y) around the point from the last `StartOrbit`.
+    Orbit(DVec2),
+}
+
+define_on_controller_enabled_changed!(MapCameraController);
+
+impl EnabledController for MapCameraController {
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn default_input_map(
+    mut events: EventWriter<ControlEvent>,
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    motion_suppression: Res<MouseMotionSuppression>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    keyboard: Res<Input<KeyCode>>,
+    controllers: Query<&MapCameraController>,
+    windows: Option<Res<Windows>>,
+    input_state: Res<CameraInputState>,
+    mut last_cursor: Local<Option<DVec2>>,
+) {
+    // Track the cursor even while input is blocked, so the first drag after it isn't measured from a stale position.
+    let cursor = windows
+        .as_deref()
+        .and_then(Windows::get_primary)
+        .and_then(|window| window.cursor_position())
+        .map(|cursor| cursor.as_dvec2());
+    let previous_cursor = std::mem::replace(&mut *last_cursor, cursor);
+
+    if input_state.is_blocked() {
+        return;
+    }
+
+    // Can only control one camera at a time.
+    let controller = if let Some(controller) = controllers.iter().find(|c| c.enabled) {
+        controller
+    } else {
+        return;
+    };
+    let MapCameraController {
+        mouse_rotate_sensitivity,
+        mouse_wheel_zoom_sensitivity,
+        scroll_sensitivity,
+        ..
+    } = *controller;
+
+    let mut cursor_delta = DVec2::ZERO;
+    for event in mouse_motion_events.iter() {
+        cursor_delta += DVec2::new(event.delta.x as f64, event.delta.y as f64);
+    }
+    if motion_suppression.is_active() {
+        cursor_delta = DVec2::ZERO;
+    }
+    let cursor = if let Some(cursor) = cursor {
+        cursor
+    } else {
+        return;
+    };
+
+    let ctrl = keyboard.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+    let dragging = mouse_buttons.pressed(MouseButton::Left);
+    if ctrl && dragging {
+        let started = mouse_buttons.just_pressed(MouseButton::Left)
+            || keyboard.any_just_pressed([KeyCode::LControl, KeyCode::RControl]);
+        if started {
+            events.send(ControlEvent::StartOrbit(cursor));
+        }
+        if cursor_delta != DVec2::ZERO {
+            events.send(ControlEvent::Orbit(mouse_rotate_sensitivity * cursor_delta));
+        }
+    } else if dragging && !mouse_buttons.just_pressed(MouseButton::Left) {
+        if let Some(from) = previous_cursor.filter(|from| *from != cursor) {
+            events.send(ControlEvent::Grab { from, to: cursor });
+        }
+    }
+
+    let mut lines = 0.0;
+    for event in mouse_wheel_reader.iter() {
+        lines += scroll_sensitivity.lines(event).y;
+    }
+    if lines != 0.0 {
+        events.send(ControlEvent::Zoom {
+            cursor,
+            scalar: 1.0 - lines * mouse_wheel_zoom_sensitivity,
+        });
+    }
+}
+
+/// Where `ray` hits the plane at `height` along the unit vector `up`, if it points toward it.
+pub(crate) fn ground_hit(ray: &CameraRay, up: DVec3, height: f64) -> Option<DVec3> {
+    let approach = ray.direction.dot(up);
+    if approach == 0.0 {
+        return None;
+    }
+    let distance = (height - ray.origin.dot(up)) / approach;
+    (distance > 0.0).then(|| ray.at(distance))
+}
+
+/// Rotates the eye and target of `transform` around `pivot`.
+fn rotate_around(transform: &mut LookTransform, pivot: DVec3, rotation: DQuat) {
+    transform.eye = pivot + rotation * (transform.eye - pivot);
+    transform.target = pivot + rotation * (transform.target - pivot);
+}
+
+pub fn control_system(
+    mut events: EventReader<ControlEvent>,
+    mut errors: EventWriter<CameraError>,
+    fallback: Option<Res<FallbackLookDirection>>,
+    mut cameras: Query<(
+        Entity,
+        &MapCameraController,
+        &mut LookTransform,
+        &Camera,
+        Option<&Smoother>,
+    )>,
+    mut orbit_pivot: Local<Option<DVec3>>,
+) {
+    // Can only control one camera at a time.
+    let (entity, controller, mut transform, camera, smoother) =
+        if let Some(camera) = cameras.iter_mut().find(|c| c.1.enabled) {
+            camera
+        } else {
+            return;
+        };
+
+    let up = transform.up.normalize();
+    look_direction_or_recover(
+        entity,
+        &mut transform,
+        smoother,
+        fallback.as_deref(),
+        &mut errors,
+    );
+    let hit_under = |transform: &LookTransform, cursor: DVec2| {
+        let ray = transform.viewport_to_world_ray(camera, cursor)?;
+        ground_hit(&ray, up, controller.ground_height)
+    };
+
+    for event in events.iter() {
+        apply_control_event(
+            &mut transform,
+            controller,
+            up,
+            &mut orbit_pivot,
+            *event,
+            &hit_under,
+        );
+    }
+    transform.up = up;
+}
+
+/// Applies one `event` to `transform`, finding the ground point under a cursor position with `hit_under`.
+fn apply_control_event(
+    transform: &mut LookTransform,
+    controller: &MapCameraController,
+    up: DVec3,
+    orbit_pivot: &mut Option<DVec3>,
+    event: ControlEvent,
+    hit_under: impl Fn(&LookTransform, DVec2) -> Option<DVec3>,
+) {
+    match event {
+        ControlEvent::Grab { from, to } => {
+            // Rays through the same camera, so the translation moves the first hit onto the second ray.
+            let hits = (hit_under(transform, from), hit_under(transform, to));
+            if let (Some(from), Some(to)) = hits {
+                let delta = from - to;
+                transform.eye += delta;
+                transform.target += delta;
+            }
+        }
+        ControlEvent::Zoom { cursor, scalar } => {
+            let anchor = hit_under(transform, cursor).unwrap_or(transform.target);
+            // Scaling around the anchor keeps it under the cursor.
+            let height = transform.eye.dot(up) - controller.ground_height;
+            let scalar = if height > 0.0 {
+                (scalar * height)
+                    .max(controller.min_height)
+                    .min(controller.max_height)
+                    / height
+            } else {
+                scalar
+            };
+            transform.eye = anchor + scalar * (transform.eye - anchor);
+            transform.target = anchor + scalar * (transform.target - anchor);
+        }
+        ControlEvent::StartOrbit(cursor) => {
+            *orbit_pivot = hit_under(transform, cursor);
+        }
+        ControlEvent::Orbit(delta) => {
+            let pivot = orbit_pivot.unwrap_or(transform.target);
+            rotate_around(transform, pivot, DQuat::from_axis_angle(up, -delta.x));
+
+            let forward = if let Some(forward) = transform.look_direction() {
+                forward
+            } else {
+                return;
+            };
+            let tilt = forward.angle_between(-up);
+            // Not `clamp`, which panics if the limits are the wrong way around.
+            let new_tilt = (tilt - delta.y)
+                .max(controller.min_tilt)
+                .min(controller.max_tilt);
+            // Turning about this axis by a positive angle tilts the view toward the horizon.
+            let right = forward
+                .cross(up)
+                .try_normalize()
+                .unwrap_or_else(|| up.any_orthonormal_vector());
+            rotate_around(
+                transform,
+                pivot,
+                DQuat::from_axis_angle(right, new_tilt - tilt),
+            );
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Maps a cursor position straight onto the ground plane at y = 0, like a camera looking down.
+    fn hit_under(_: &LookTransform, cursor: DVec2) -> Option<DVec3> {
+        Some(DVec3::new(cursor.x, 0.0, -cursor.y))
+    }
+
+    fn apply(transform: &mut LookTransform, controller: &MapCameraController, event: ControlEvent) {
+        apply_control_event(transform, controller, DVec3::Y, &mut None, event, hit_under);
+    }
+
+    #[test]
+    fn test_ground_hit() {
+        let down = CameraRay {
+            origin: 10.0 * DVec3::Y,
+            direction: -DVec3::Y,
+        };
+        assert_eq!(ground_hit(&down, DVec3::Y, 2.0), Some(2.0 * DVec3::Y));
+
+        let up = CameraRay {
+            direction: DVec3::Y,
+            ..down
+        };
+        assert_eq!(ground_hit(&up, DVec3::Y, 2.0), None);
+        let level = CameraRay {
+            direction: DVec3::X,
+            ..down
+        };
+        assert_eq!(ground_hit(&level, DVec3::Y, 2.0), None);
+    }
+
+    #[test]
+    fn test_grab_pans() {
+        let controller = MapCameraController::default();
+        let mut transform = LookTransform::new(DVec3::new(0.0, 10.0, 1.0), DVec3::ZERO);
+        apply(
+            &mut transform,
+            &controller,
+            ControlEvent::Grab {
+                from: DVec2::ZERO,
+                to: DVec2::X,
+            },
+        );
+
+        // The ground point under the cursor follows it.
+        assert_eq!(transform.eye, DVec3::new(-1.0, 10.0, 1.0));
+        assert_eq!(transform.target, -DVec3::X);
+    }
+
+    #[test]
+    fn test_zoom_keeps_height_limits() {
+        let mut controller = MapCameraController::default();
+        let mut transform = LookTransform::new(DVec3::new(0.0, 10.0, 1.0), DVec3::ZERO);
+        let zoom = ControlEvent::Zoom {
+            cursor: DVec2::ZERO,
+            scalar: 0.01,
+        };
+        apply(&mut transform, &controller, zoom);
+        assert!((transform.eye.y - controller.min_height).abs() < 1e-9);
+        assert_eq!(transform.target, DVec3::ZERO);
+
+        // Limits the wrong way around don't panic.
+        controller.min_height = 5.0;
+        controller.max_height = 2.0;
+        apply(&mut transform, &controller, zoom);
+        assert!((transform.eye.y - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tilt_keeps_limits() {
+        let controller = MapCameraController::default();
+        let mut transform = LookTransform::new(DVec3::new(0.0, 10.0, 10.0), DVec3::ZERO);
+        apply(
+            &mut transform,
+            &controller,
+            ControlEvent::Orbit(DVec2::new(0.0, -10.0)),
+        );
+
+        let forward = transform.look_direction().unwrap();
+        assert!((forward.angle_between(-DVec3::Y) - controller.max_tilt).abs() < 1e-9);
+        assert!((transform.radius() - 200f64.sqrt()).abs() < 1e-9);
+    }
+}
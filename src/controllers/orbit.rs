@@ -1,39 +1,136 @@
-use crate::{LookAngles, LookTransform, LookTransformBundle, Smoother};
+use crate::{
+    apply_deadzone,
+    controllers::{
+        adaptive_sensitivity_scale, add_applied_weights, add_disable_stack_system,
+        add_mouse_motion_suppression, add_toggle_key, discard_control_events,
+        look_direction_or_recover, primary_window_unfocused, register_controller_types,
+        wheel_factor, zoom_fov, AngleSnap, AppliedWeights, CameraControlSystem, CameraError,
+        CameraInputState, DoubleClickDetector, EdgePan, EnabledController, FallbackLookDirection,
+        MouseMotionSuppression, ScrollSensitivity, WheelAction,
+    },
+    world_aabb_min_max, CameraRayHits, GroundHeight, KeyRepeat, KeyboardNavigation, LookAngles,
+    LookTransform, LookTransformBundle, LookTransformSystem, MouseFilter, MouseFilterState,
+    OrbitLagWeights, ResponseCurve, Smoother, SmoothingMode, TouchpadGesture,
+    MOUSE_CURVE_REFERENCE,
+};
 
 use bevy::{
     app::prelude::*,
     ecs::{bundle::Bundle, prelude::*},
     input::{
-        mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+        mouse::{MouseMotion, MouseWheel},
         prelude::*,
     },
     math::prelude::*,
-    transform::components::Transform,
+    reflect::{FromReflect, Reflect},
+    render::{camera::Projection, primitives::Aabb},
+    time::{FixedTimestep, Time},
+    transform::components::{GlobalTransform, Transform},
+    window::Windows,
 };
 use serde::{Deserialize, Serialize};
 
 #[derive(Default)]
 pub struct OrbitCameraPlugin {
     pub override_input_system: bool,
+    /// Run the control system on a fixed timestep of this many seconds instead of every frame, for deterministic motion.
+    /// Input maps and smoothing still run every frame. Steps longer than two frames may drop some input.
+    pub fixed_timestep: Option<f64>,
+    /// Ignore all input while the primary window doesn't have focus.
+    pub disable_when_unfocused: bool,
+    /// Add the keyboard-only input map, configured by the `KeyboardNavigation` resource. This works alongside the default
+    /// input map.
+    pub keyboard_navigation: bool,
+    /// Pressing this key will flip `enabled` on all orbit controllers.
+    pub toggle_key: Option<KeyCode>,
 }
 
 impl OrbitCameraPlugin {
     pub fn new(override_input_system: bool) -> Self {
         Self {
             override_input_system,
+            fixed_timestep: None,
+            disable_when_unfocused: false,
+            keyboard_navigation: false,
+            toggle_key: None,
         }
     }
+
+    pub fn with_fixed_timestep(mut self, step: f64) -> Self {
+        self.fixed_timestep = Some(step);
+        self
+    }
+
+    pub fn with_disable_when_unfocused(mut self, disable_when_unfocused: bool) -> Self {
+        self.disable_when_unfocused = disable_when_unfocused;
+        self
+    }
+
+    pub fn with_keyboard_navigation(mut self, keyboard_navigation: bool) -> Self {
+        self.keyboard_navigation = keyboard_navigation;
+        self
+    }
+
+    pub fn with_toggle_key(mut self, key: KeyCode) -> Self {
+        self.toggle_key = Some(key);
+        self
+    }
 }
 
 impl Plugin for OrbitCameraPlugin {
     fn build(&self, app: &mut App) {
-        let app = app
-            .add_system_to_stage(CoreStage::PreUpdate, on_controller_enabled_changed)
-            .add_system(control_system)
-            .add_event::<ControlEvent>();
+        app.init_resource::<CameraInputState>()
+            .register_type::<OrbitCameraController>()
+            .register_type::<OrbitRotationMode>()
+            .register_type::<OrbitPanMode>()
+            .register_type::<Option<(f64, f64)>>();
+        register_controller_types(app);
+        add_disable_stack_system::<OrbitCameraController>(app);
+        add_applied_weights::<OrbitCameraController, f64>(app);
+        add_applied_weights::<OrbitCameraController, Option<OrbitLagWeights>>(app);
 
+        let mut systems = SystemSet::new().with_system(
+            control_system
+                .label(CameraControlSystem::Control)
+                .after(CameraControlSystem::Input)
+                .before(LookTransformSystem::Smooth),
+        );
         if !self.override_input_system {
-            app.add_system(default_input_map);
+            app.add_system(default_input_map.label(CameraControlSystem::Input));
+            add_mouse_motion_suppression(app);
+            app.add_event::<TouchpadGesture>();
+        }
+        if self.keyboard_navigation {
+            app.add_system(keyboard_input_map.label(CameraControlSystem::Input));
+            app.init_resource::<KeyboardNavigation>();
+        }
+        if let Some(step) = self.fixed_timestep {
+            systems = systems.with_run_criteria(FixedTimestep::step(step));
+        }
+
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            on_controller_enabled_changed.label(CameraControlSystem::EnabledChanged),
+        )
+        .add_system_to_stage(
+            CoreStage::PreUpdate,
+            on_orbit_lag_weights_changed.label(CameraControlSystem::EnabledChanged),
+        )
+        .add_system_set(systems)
+        .add_event::<ControlEvent>()
+        .add_event::<CameraError>();
+
+        if let Some(key) = self.toggle_key {
+            add_toggle_key::<OrbitCameraController>(app, key, false);
+        }
+
+        if self.disable_when_unfocused {
+            app.add_system(
+                discard_control_events::<ControlEvent>
+                    .with_run_criteria(primary_window_unfocused)
+                    .after(CameraControlSystem::Input)
+                    .before(CameraControlSystem::Control),
+            );
         }
     }
 }
@@ -47,146 +144,853 @@ pub struct OrbitCameraBundle {
 }
 
 impl OrbitCameraBundle {
-    pub fn new(
-        controller: OrbitCameraController,
-        eye: DVec3,
-        target: DVec3,
-    ) -> Self {
+    pub fn new(controller: OrbitCameraController, eye: DVec3, target: DVec3) -> Self {
         // Make sure the transform is consistent with the controller to start.
         let transform = Transform::from_translation(eye).looking_at(target, DVec3::Y);
 
+        let mut smoother = Smoother::new(controller.smoothing_weight);
+        if let Some(weights) = controller.orbit_lag_weights() {
+            smoother.set_mode(SmoothingMode::Orbit);
+            smoother.set_orbit_lag_weights(Some(weights));
+        }
+
         Self {
             controller,
             look_transform: LookTransformBundle {
                 transform: LookTransform::new(eye, target),
-                smoother: Smoother::new(controller.smoothing_weight),
+                smoother,
             },
             transform,
         }
     }
+
+    /// Use `up` as the world's up axis instead of +Y. See `LookTransform::up`.
+    pub fn with_up(mut self, up: DVec3) -> Self {
+        self.look_transform.transform.up = up;
+        self.transform = self.look_transform.transform.into();
+        self
+    }
+
+    /// Use `smoother` instead of one made from the controller's smoothing settings.
+    pub fn with_smoother(mut self, smoother: Smoother) -> Self {
+        self.look_transform.smoother = smoother;
+        self
+    }
+
+    /// Change how the smoother interpolates. `SmoothingMode::Orbit` keeps the radius constant while rotation catches up.
+    pub fn with_smoothing_mode(mut self, mode: SmoothingMode) -> Self {
+        self.look_transform.smoother.set_mode(mode);
+        self
+    }
+}
+
+define_bundle_builder!(
+    OrbitCameraBundleBuilder,
+    OrbitCameraBundle,
+    OrbitCameraController
+);
+
+impl OrbitCameraBundleBuilder {
+    pub fn mouse_rotate_sensitivity(mut self, mouse_rotate_sensitivity: DVec2) -> Self {
+        self.controller.mouse_rotate_sensitivity = mouse_rotate_sensitivity;
+        self
+    }
+
+    pub fn mouse_translate_sensitivity(mut self, mouse_translate_sensitivity: DVec2) -> Self {
+        self.controller.mouse_translate_sensitivity = mouse_translate_sensitivity;
+        self
+    }
+
+    pub fn mouse_wheel_zoom_sensitivity(mut self, mouse_wheel_zoom_sensitivity: f64) -> Self {
+        self.controller.mouse_wheel_zoom_sensitivity = mouse_wheel_zoom_sensitivity;
+        self
+    }
 }
 
 /// A 3rd person camera that orbits around the target.
-#[derive(Clone, Component, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Component, Copy, Debug, Deserialize, FromReflect, Reflect, Serialize)]
+#[reflect(Component)]
+#[serde(default)]
 pub struct OrbitCameraController {
     pub enabled: bool,
     pub mouse_rotate_sensitivity: DVec2,
+    /// How dragging the mouse rotates the camera around the target
+    pub rotation_mode: OrbitRotationMode,
+    /// Filter applied to cursor deltas before they orbit the camera
+    pub mouse_filter: MouseFilter,
+    /// Response curve for mouse rotation
+    pub rotate_response_curve: ResponseCurve,
     pub mouse_translate_sensitivity: DVec2,
+    /// The plane the target moves in when panning
+    pub pan_mode: OrbitPanMode,
+    /// Pan with the cursor at the edges of the window. With `OrbitPanMode::Ground`, the top edge moves forward.
+    pub edge_pan: EdgePan,
     pub mouse_wheel_zoom_sensitivity: f64,
-    pub pixels_per_line: f32,
+    /// Zoom per unit of `TouchpadGesture::Magnify`
+    pub touchpad_zoom_sensitivity: f64,
+    /// Orbit per radian of `TouchpadGesture::Rotate`
+    pub touchpad_rotate_sensitivity: f64,
+    /// Trigger travel ignored before the gamepad triggers zoom in (right) and out (left)
+    pub gamepad_trigger_deadzone: f64,
+    /// Zoom per frame at fully pressed triggers
+    pub gamepad_trigger_zoom_sensitivity: f64,
+    /// Multiply every sensitivity by this factor, e.g. `0.1`, while `precision_key` is held, for precise framing of
+    /// screenshots
+    pub precision_scale: Option<f64>,
+    pub precision_key: KeyCode,
+    /// When a rotation drag starts, orbit around the geometry under the cursor, taken from the `CameraRayHits` component
+    pub pivot_under_cursor: bool,
+    /// Double click the left mouse button to focus on the geometry under the cursor, taken from the `CameraRayHits`
+    /// component
+    pub double_click_focus: bool,
+    /// Press this key to look straight at the surface under the cursor, along its normal from the `CameraRayHits`
+    /// component, or with Shift to look across it
+    pub align_to_surface_key: Option<KeyCode>,
+    /// Press this key to frame the bounding boxes of the entities marked with `FrameSelection`
+    pub frame_selected_key: Option<KeyCode>,
+    /// Margin around the framed selection, as a fraction of its size
+    pub frame_selected_padding: f64,
+    /// Rotate around this point relative to the target, e.g. a character's head rather than their feet, while still
+    /// looking at the target
+    pub pivot_offset: DVec3,
+    /// Keep `LookTransform::up` pointing from this point to the target, so the camera stays upright anywhere on a
+    /// globe, and keep panning on its surface. See `LatLonAlt`.
+    pub globe_center: Option<DVec3>,
+    /// Normalizes mouse wheel and touchpad scrolling
+    pub scroll_sensitivity: ScrollSensitivity,
+    /// What the mouse wheel does. `WheelAction::Speed` scales `mouse_translate_sensitivity`.
+    pub wheel_action: WheelAction,
+    /// Scale panning speed with the distance to the target. Zoom is always proportional to the distance.
+    pub adaptive_sensitivity: bool,
+    /// Panning speed scales with the distance to the target raised to this power
+    pub adaptive_sensitivity_exponent: f64,
+    /// Lower clamp on the adaptive scale
+    pub adaptive_sensitivity_min_scale: f64,
+    /// Upper clamp on the adaptive scale
+    pub adaptive_sensitivity_max_scale: f64,
+    /// Snap yaw and pitch to multiples of this many degrees while `angle_snap_key` is held, in turntable mode
+    pub angle_snap_degrees: Option<f64>,
+    pub angle_snap_key: KeyCode,
+    /// While panning, move the target up and down with the ground under the eye, taken from the `GroundHeight`
+    /// component, so the eye keeps a constant height above the terrain
+    pub terrain_following: bool,
+    /// Lowest angle in radians of the eye above the target, relative to `LookTransform::up`. `0.0` keeps the eye from
+    /// going below the target.
+    pub min_pitch: f64,
+    /// Highest angle in radians of the eye above the target
+    pub max_pitch: f64,
+    /// Arc of yaw in radians that the eye is kept within, from the first angle increasing to the second. A yaw of zero
+    /// puts the eye on the +Z side of the target, and increasing yaw moves it toward +X.
+    pub yaw_range: Option<(f64, f64)>,
     pub smoothing_weight: f64,
+    /// Smoothing weight for changes in distance to the target. Setting any of the separate weights switches the
+    /// smoother to `SmoothingMode::Orbit`; unset ones fall back to `smoothing_weight`.
+    pub zoom_smoothing_weight: Option<f64>,
+    /// Smoothing weight for rotation around the target
+    pub rotation_smoothing_weight: Option<f64>,
+    /// Smoothing weight for moving the target
+    pub pan_smoothing_weight: Option<f64>,
+}
+
+impl OrbitCameraController {
+    /// The separate smoothing weights for zoom, rotation and pan, if any are set.
+    pub fn orbit_lag_weights(&self) -> Option<OrbitLagWeights> {
+        if self.zoom_smoothing_weight.is_none()
+            && self.rotation_smoothing_weight.is_none()
+            && self.pan_smoothing_weight.is_none()
+        {
+            return None;
+        }
+        Some(OrbitLagWeights {
+            radius: self.zoom_smoothing_weight.unwrap_or(self.smoothing_weight),
+            rotation: self
+                .rotation_smoothing_weight
+                .unwrap_or(self.smoothing_weight),
+            target: self.pan_smoothing_weight.unwrap_or(self.smoothing_weight),
+        })
+    }
 }
 
 impl Default for OrbitCameraController {
     fn default() -> Self {
         Self {
             mouse_rotate_sensitivity: DVec2::splat(0.006),
+            rotation_mode: OrbitRotationMode::Turntable,
+            mouse_filter: MouseFilter::None,
+            rotate_response_curve: ResponseCurve::Linear,
             mouse_translate_sensitivity: DVec2::splat(0.008),
+            pan_mode: OrbitPanMode::View,
+            edge_pan: EdgePan::default(),
             mouse_wheel_zoom_sensitivity: 0.15,
+            touchpad_zoom_sensitivity: 1.0,
+            touchpad_rotate_sensitivity: 1.0,
+            gamepad_trigger_deadzone: 0.05,
+            gamepad_trigger_zoom_sensitivity: 0.03,
+            precision_scale: None,
+            precision_key: KeyCode::LAlt,
+            pivot_under_cursor: false,
+            double_click_focus: false,
+            align_to_surface_key: None,
+            frame_selected_key: None,
+            frame_selected_padding: 0.1,
+            pivot_offset: DVec3::ZERO,
+            globe_center: None,
             smoothing_weight: 0.8,
+            zoom_smoothing_weight: None,
+            rotation_smoothing_weight: None,
+            pan_smoothing_weight: None,
             enabled: true,
-            pixels_per_line: 53.0,
+            scroll_sensitivity: ScrollSensitivity::default(),
+            wheel_action: WheelAction::Zoom,
+            adaptive_sensitivity: false,
+            adaptive_sensitivity_exponent: 1.0,
+            adaptive_sensitivity_min_scale: 0.01,
+            adaptive_sensitivity_max_scale: 1000.0,
+            angle_snap_degrees: None,
+            angle_snap_key: KeyCode::LShift,
+            terrain_following: false,
+            min_pitch: -std::f64::consts::FRAC_PI_2,
+            max_pitch: std::f64::consts::FRAC_PI_2,
+            yaw_range: None,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub enum OrbitPanMode {
+    /// Move in the plane of the screen, following the cursor.
+    #[default]
+    View,
+    /// Move across the ground plane, perpendicular to `LookTransform::up`, keeping the target's height. Dragging up
+    /// moves forward, as in map and terrain applications.
+    Ground,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub enum OrbitRotationMode {
+    /// Yaw around the up axis and pitch toward it, keeping the camera upright.
+    #[default]
+    Turntable,
+    /// Rotate around screen-space axes, like an arcball. The camera can roll and go over the poles, and
+    /// `LookTransform::up` is rotated along with it.
+    Trackball,
+    /// Dragging sideways circles the eye around the up axis at a constant height above the target, and dragging up and
+    /// down changes that height instead of the pitch, like a drone circling a building.
+    ElevationRing,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum ControlEvent {
     Orbit(DVec2),
     TranslateTarget(DVec2),
     Zoom(f64),
+    /// Snap the rotation to multiples of `angle_snap_degrees` this frame. Sent every frame while the snap key is held.
+    SnapRotation,
+    /// Move the target to the cursor hit in the `CameraRayHits` component, if there is one.
+    PivotUnderCursor,
+    /// Move the target to the cursor hit in the `CameraRayHits` component, if there is one, keeping the view direction and
+    /// distance.
+    FocusUnderCursor,
+    /// Orbit around the cursor hit in the `CameraRayHits` component, if there is one, to look along the surface normal
+    /// there, or across the surface if `perpendicular`, keeping the distance.
+    AlignToSurface {
+        perpendicular: bool,
+    },
+    /// Divide the field of view by this factor.
+    ZoomFov(f64),
+    /// Look at this world-space box from the current direction, far enough away to see all of it. See
+    /// `LookTransform::fit_aabb`.
+    FrameBounds {
+        center: DVec3,
+        half_extents: DVec3,
+    },
 }
 
+/// Marks the entities framed by the orbit controller's `frame_selected_key`. They need an `Aabb`.
+#[derive(Clone, Component, Copy, Debug, Default)]
+pub struct FrameSelection;
+
 define_on_controller_enabled_changed!(OrbitCameraController);
 
+fn on_orbit_lag_weights_changed(
+    mut applied_weights: ResMut<AppliedWeights<OrbitCameraController, Option<OrbitLagWeights>>>,
+    mut smoothers: Query<
+        (Entity, &mut Smoother, &OrbitCameraController),
+        Changed<OrbitCameraController>,
+    >,
+) {
+    for (entity, mut smoother, controller) in smoothers.iter_mut() {
+        // Like the `smoothing_weight`, only apply weights that changed since they were last seen, so a custom
+        // `Smoother` keeps its own.
+        let weights = controller.orbit_lag_weights();
+        match applied_weights.weights.insert(entity, weights) {
+            Some(applied) if applied != weights => (),
+            _ => continue,
+        }
+        if weights.is_some() {
+            smoother.set_mode(SmoothingMode::Orbit);
+        }
+        smoother.set_orbit_lag_weights(weights);
+    }
+}
+
+impl EnabledController for OrbitCameraController {
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
 pub fn default_input_map(
     mut events: EventWriter<ControlEvent>,
     mut mouse_wheel_reader: EventReader<MouseWheel>,
     mut mouse_motion_events: EventReader<MouseMotion>,
+    mut gesture_events: EventReader<TouchpadGesture>,
+    motion_suppression: Res<MouseMotionSuppression>,
     mouse_buttons: Res<Input<MouseButton>>,
     keyboard: Res<Input<KeyCode>>,
-    controllers: Query<&OrbitCameraController>,
+    mut controllers: Query<&mut OrbitCameraController>,
+    selection: Query<(&Aabb, &GlobalTransform), With<FrameSelection>>,
+    gamepads: Res<Gamepads>,
+    gamepad_button_axes: Res<Axis<GamepadButton>>,
+    windows: Option<Res<Windows>>,
+    time: Res<Time>,
+    input_state: Res<CameraInputState>,
+    mut filter_state: Local<MouseFilterState>,
+    mut double_click: Local<DoubleClickDetector>,
 ) {
+    if input_state.is_blocked() {
+        return;
+    }
+
     // Can only control one camera at a time.
-    let controller = if let Some(controller) = controllers.iter().find(|c| {
-        c.enabled
-    }) {
+    let mut controller = if let Some(controller) = controllers.iter_mut().find(|c| c.enabled) {
         controller
     } else {
         return;
     };
     let OrbitCameraController {
         mouse_rotate_sensitivity,
+        mouse_filter,
+        rotate_response_curve,
+        pivot_under_cursor,
+        double_click_focus,
+        align_to_surface_key,
+        frame_selected_key,
+        angle_snap_degrees,
+        angle_snap_key,
         mouse_translate_sensitivity,
+        edge_pan,
         mouse_wheel_zoom_sensitivity,
-        pixels_per_line,
+        touchpad_zoom_sensitivity,
+        touchpad_rotate_sensitivity,
+        gamepad_trigger_deadzone,
+        gamepad_trigger_zoom_sensitivity,
+        precision_scale,
+        precision_key,
+        scroll_sensitivity,
+        wheel_action,
         ..
     } = *controller;
 
+    // Applies from the frame the key goes down to the frame it comes up, with no easing in or out.
+    let precision = precision_scale
+        .filter(|_| keyboard.pressed(precision_key))
+        .unwrap_or(1.0);
+    let mouse_rotate_sensitivity = precision * mouse_rotate_sensitivity;
+    let mouse_translate_sensitivity = precision * mouse_translate_sensitivity;
+    let mouse_wheel_zoom_sensitivity = precision * mouse_wheel_zoom_sensitivity;
+    let touchpad_zoom_sensitivity = precision * touchpad_zoom_sensitivity;
+    let touchpad_rotate_sensitivity = precision * touchpad_rotate_sensitivity;
+    let gamepad_trigger_zoom_sensitivity = precision * gamepad_trigger_zoom_sensitivity;
+
     let mut cursor_delta = DVec2::ZERO;
     for event in mouse_motion_events.iter() {
         cursor_delta += DVec2::new(event.delta.x as f64, event.delta.y as f64);
     }
+    if motion_suppression.is_active() {
+        cursor_delta = DVec2::ZERO;
+    }
+
+    let rotate_delta = rotate_response_curve.apply(
+        filter_state.filter(mouse_filter, cursor_delta, time.delta_seconds_f64()),
+        MOUSE_CURVE_REFERENCE,
+    );
+    let double_clicked = double_click.update(
+        mouse_buttons.just_pressed(MouseButton::Left),
+        time.elapsed_seconds_f64(),
+    );
+    if double_click_focus && double_clicked {
+        events.send(ControlEvent::FocusUnderCursor);
+    }
+
+    if angle_snap_degrees.is_some() && keyboard.pressed(angle_snap_key) {
+        events.send(ControlEvent::SnapRotation);
+    }
 
-    if keyboard.pressed(KeyCode::LControl) {
-        events.send(ControlEvent::Orbit(mouse_rotate_sensitivity * cursor_delta));
+    if align_to_surface_key.map_or(false, |key| keyboard.just_pressed(key)) {
+        events.send(ControlEvent::AlignToSurface {
+            perpendicular: keyboard.any_pressed([KeyCode::LShift, KeyCode::RShift]),
+        });
     }
 
-    if mouse_buttons.pressed(MouseButton::Right) {
+    if frame_selected_key.map_or(false, |key| keyboard.just_pressed(key)) {
+        let mut min = DVec3::splat(f64::INFINITY);
+        let mut max = DVec3::splat(f64::NEG_INFINITY);
+        for (aabb, global) in selection.iter() {
+            let (aabb_min, aabb_max) = world_aabb_min_max(aabb, global);
+            min = min.min(aabb_min);
+            max = max.max(aabb_max);
+        }
+        if min.cmple(max).all() {
+            events.send(ControlEvent::FrameBounds {
+                center: (min + max) / 2.0,
+                half_extents: (max - min) / 2.0,
+            });
+        }
+    }
+
+    if pivot_under_cursor && keyboard.just_pressed(KeyCode::LControl) {
+        events.send(ControlEvent::PivotUnderCursor);
+    }
+    if keyboard.pressed(KeyCode::LControl) && rotate_delta != DVec2::ZERO {
+        events.send(ControlEvent::Orbit(mouse_rotate_sensitivity * rotate_delta));
+    }
+
+    if mouse_buttons.pressed(MouseButton::Right) && cursor_delta != DVec2::ZERO {
         events.send(ControlEvent::TranslateTarget(
             mouse_translate_sensitivity * cursor_delta,
         ));
     }
 
+    let edge = edge_pan.window_direction(windows.as_deref());
+    if edge != DVec2::ZERO {
+        // Move the target the way the view should move, which is opposite to dragging it with the mouse.
+        events.send(ControlEvent::TranslateTarget(
+            edge_pan.speed * time.delta_seconds_f64() * DVec2::new(-edge.x, edge.y),
+        ));
+    }
+
     let mut scalar = 1.0;
+    let mut lines = 0.0;
     for event in mouse_wheel_reader.iter() {
-        // scale the event magnitude per pixel or per line
-        let scroll_amount = match event.unit {
-            MouseScrollUnit::Line => event.y as f64,
-            MouseScrollUnit::Pixel => event.y as f64 / pixels_per_line as f64,
+        let event_lines = scroll_sensitivity.lines(event).y;
+        lines += event_lines;
+        if wheel_action == WheelAction::Zoom {
+            scalar *= 1.0 - event_lines * mouse_wheel_zoom_sensitivity;
+        }
+    }
+    if lines != 0.0 {
+        match wheel_action {
+            WheelAction::Fov => events.send(ControlEvent::ZoomFov(wheel_factor(lines))),
+            WheelAction::Speed => {
+                // Not a change of camera, so keep the smoother running.
+                controller
+                    .bypass_change_detection()
+                    .mouse_translate_sensitivity *= wheel_factor(lines);
+            }
+            WheelAction::Zoom | WheelAction::None => (),
+        }
+    }
+    let mut gesture_rotation = 0.0;
+    for event in gesture_events.iter() {
+        match *event {
+            TouchpadGesture::Magnify(magnify) => {
+                scalar *= 1.0 - magnify * touchpad_zoom_sensitivity;
+            }
+            TouchpadGesture::Rotate(angle) => gesture_rotation += angle,
+        }
+    }
+    if gesture_rotation != 0.0 {
+        events.send(ControlEvent::Orbit(DVec2::new(
+            touchpad_rotate_sensitivity * gesture_rotation,
+            0.0,
+        )));
+    }
+    for gamepad in gamepads.iter() {
+        let trigger = |button_type| {
+            let button = GamepadButton::new(gamepad, button_type);
+            let value = gamepad_button_axes.get(button).unwrap_or(0.0) as f64;
+            apply_deadzone(value, gamepad_trigger_deadzone)
         };
-        scalar *= 1.0 - scroll_amount * mouse_wheel_zoom_sensitivity;
+        let zoom =
+            trigger(GamepadButtonType::RightTrigger2) - trigger(GamepadButtonType::LeftTrigger2);
+        scalar *= 1.0 - zoom * gamepad_trigger_zoom_sensitivity;
+    }
+    if scalar != 1.0 {
+        events.send(ControlEvent::Zoom(scalar));
+    }
+}
+
+/// Input map for controlling the camera with only the keyboard. See `KeyboardNavigation`.
+pub fn keyboard_input_map(
+    mut events: EventWriter<ControlEvent>,
+    keyboard: Res<Input<KeyCode>>,
+    settings: Res<KeyboardNavigation>,
+    controllers: Query<&OrbitCameraController>,
+    time: Res<Time>,
+    input_state: Res<CameraInputState>,
+    mut repeat: Local<KeyRepeat>,
+) {
+    if input_state.is_blocked() {
+        return;
+    }
+
+    // Can only control one camera at a time.
+    if !controllers.iter().any(|c| c.enabled) {
+        return;
+    }
+
+    let steps = repeat.read(&settings, &keyboard, time.elapsed_seconds_f64());
+    if steps.rotate != DVec2::ZERO {
+        events.send(ControlEvent::Orbit(steps.rotate));
+    }
+    if steps.zoom != 0 {
+        events.send(ControlEvent::Zoom(settings.zoom_step.powi(-steps.zoom)));
+    }
+    if steps.pan != DVec2::ZERO {
+        // Move the target the way the view should move, which is opposite to dragging it with the mouse.
+        events.send(ControlEvent::TranslateTarget(DVec2::new(
+            -steps.pan.x,
+            steps.pan.y,
+        )));
     }
-    events.send(ControlEvent::Zoom(scalar));
 }
 
 pub fn control_system(
     mut events: EventReader<ControlEvent>,
-    mut cameras: Query<(&OrbitCameraController, &mut LookTransform, &Transform)>,
+    mut errors: EventWriter<CameraError>,
+    fallback: Option<Res<FallbackLookDirection>>,
+    mut cameras: Query<(
+        Entity,
+        &OrbitCameraController,
+        &mut LookTransform,
+        &Transform,
+        Option<&CameraRayHits>,
+        Option<&GroundHeight>,
+        Option<&mut Projection>,
+        Option<&Smoother>,
+    )>,
+    mut angle_snap: Local<AngleSnap>,
 ) {
     // Can only control one camera at a time.
-    let (mut transform, scene_transform) =
-        if let Some((_, transform, scene_transform)) = cameras.iter_mut().find(|c| {
-            c.0.enabled
-        }) {
-            (transform, scene_transform)
-        } else {
-            return;
-        };
+    let (
+        camera,
+        controller,
+        mut transform,
+        scene_transform,
+        ray_hits,
+        ground,
+        mut projection,
+        smoother,
+    ) = if let Some(camera) = cameras.iter_mut().find(|c| c.1.enabled) {
+        camera
+    } else {
+        return;
+    };
 
-        let mut look_angles = LookAngles::from_vector(-transform.look_direction().unwrap());
-        let mut radius_scalar = 1.0;
+    let mut up = controller
+        .globe_center
+        .and_then(|center| (transform.target - center).try_normalize())
+        .unwrap_or(transform.up);
+    let look_vector = look_direction_or_recover(
+        camera,
+        &mut transform,
+        smoother,
+        fallback.as_deref(),
+        &mut errors,
+    );
+    // Orbit around the pivot, while still looking at the target.
+    let pivot_offset = controller.pivot_offset;
+    let pivot_dir = (transform.eye - (transform.target + pivot_offset)).try_normalize();
+    let mut look_angles = match pivot_dir {
+        Some(dir) if pivot_offset != DVec3::ZERO => LookAngles::from_vector_with_up(dir, up),
+        _ => LookAngles::from_vector_with_up(-look_vector, up),
+    };
+    let mut radius_scalar = 1.0;
+    let translate_scale = if controller.adaptive_sensitivity {
+        adaptive_sensitivity_scale(
+            transform.radius(),
+            controller.adaptive_sensitivity_exponent,
+            controller.adaptive_sensitivity_min_scale,
+            controller.adaptive_sensitivity_max_scale,
+        )
+    } else {
+        1.0
+    };
 
-        for event in events.iter() {
-            match event {
-                ControlEvent::Orbit(delta) => {
-                    look_angles.add_yaw(-delta.x);
-                    look_angles.add_pitch(delta.y);
+    let mut pan = DVec3::ZERO;
+    let mut turntable_delta = DVec2::ZERO;
+    let mut ring_delta = DVec2::ZERO;
+    let mut snap = false;
+    for event in events.iter() {
+        match event {
+            ControlEvent::Orbit(delta) => match controller.rotation_mode {
+                OrbitRotationMode::Turntable => {
+                    turntable_delta += *delta;
+                }
+                OrbitRotationMode::ElevationRing => {
+                    ring_delta += *delta;
+                }
+                OrbitRotationMode::Trackball => {
+                    let offset = look_angles.unit_vector_with_up(up);
+                    let camera =
+                        LookTransform::new(transform.target + offset, transform.target).with_up(up);
+                    let camera_rotation = Transform::from(camera).rotation;
+                    let axis = delta.x * (camera_rotation * DVec3::Y)
+                        + delta.y * (camera_rotation * DVec3::X);
+                    // Rotating the up axis along with the offset keeps the pitch, so it never reaches a pole.
+                    let rotation = DQuat::from_scaled_axis(-axis);
+                    up = rotation * up;
+                    look_angles = LookAngles::from_vector_with_up(rotation * offset, up);
+                }
+            },
+            ControlEvent::TranslateTarget(delta) => {
+                let mut right_dir = scene_transform.rotation * -DVec3::X;
+                let mut up_dir = scene_transform.rotation * DVec3::Y;
+                if controller.pan_mode == OrbitPanMode::Ground {
+                    let flatten = |v: DVec3| v - v.dot(up) * up;
+                    right_dir = flatten(right_dir).normalize_or_zero();
+                    // The view direction and the screen's up share a heading on the ground, and at least one
+                    // of them has one, even when looking straight down.
+                    let forward = scene_transform.rotation * -DVec3::Z;
+                    up_dir = flatten(forward + up_dir).normalize_or_zero();
+                }
+                let delta = translate_scale * (delta.x * right_dir + delta.y * up_dir);
+                transform.target += delta;
+                pan += delta;
+            }
+            ControlEvent::Zoom(scalar) => {
+                radius_scalar *= scalar;
+            }
+            ControlEvent::SnapRotation => {
+                snap = true;
+            }
+            ControlEvent::PivotUnderCursor => {
+                if let Some(hit) = ray_hits.and_then(|hits| hits.cursor) {
+                    if let Some(dir) = (transform.eye - hit).try_normalize() {
+                        transform.target = hit;
+                        look_angles = LookAngles::from_vector_with_up(dir, up);
+                    }
                 }
-                ControlEvent::TranslateTarget(delta) => {
-                    let right_dir = scene_transform.rotation * -DVec3::X;
-                    let up_dir = scene_transform.rotation * DVec3::Y;
-                    transform.target += delta.x * right_dir + delta.y * up_dir;
+            }
+            ControlEvent::FocusUnderCursor => {
+                // The eye follows the target below, since the angles and radius are unchanged.
+                if let Some(hit) = ray_hits.and_then(|hits| hits.cursor) {
+                    transform.target = hit;
+                }
+            }
+            ControlEvent::AlignToSurface { perpendicular } => {
+                let hits = ray_hits.and_then(|hits| hits.cursor.zip(hits.cursor_normal));
+                if let Some((hit, normal)) = hits {
+                    let offset = if *perpendicular {
+                        // Keep looking the same way, but flattened onto the surface.
+                        let look = transform.eye - transform.target;
+                        (look - look.dot(normal) * normal)
+                            .try_normalize()
+                            .unwrap_or_else(|| normal.any_orthonormal_vector())
+                    } else {
+                        normal
+                    };
+                    transform.target = hit;
+                    look_angles = LookAngles::from_vector_with_up(offset, up);
                 }
-                ControlEvent::Zoom(scalar) => {
-                    radius_scalar *= scalar;
+            }
+            ControlEvent::ZoomFov(factor) => {
+                zoom_fov(projection.as_deref_mut(), *factor);
+            }
+            ControlEvent::FrameBounds {
+                center,
+                half_extents,
+            } => {
+                let fit = transform.fit_bounds(
+                    *center,
+                    *half_extents,
+                    projection.as_deref().unwrap_or(&Projection::default()),
+                    controller.frame_selected_padding,
+                );
+                // Keeps the view direction, so only the target and radius change.
+                transform.target = fit.transform.target;
+                transform.eye = fit.transform.eye;
+                radius_scalar = 1.0;
+                if let (Some(Projection::Orthographic(orthographic)), Some(scale)) =
+                    (projection.as_deref_mut(), fit.orthographic_scale)
+                {
+                    orthographic.scale = scale;
                 }
             }
         }
+    }
+
+    let snap_increment = controller
+        .angle_snap_degrees
+        .filter(|_| snap && controller.rotation_mode == OrbitRotationMode::Turntable)
+        .map(f64::to_radians);
+    angle_snap.rotate(
+        &mut look_angles,
+        -turntable_delta.x,
+        turntable_delta.y,
+        snap_increment,
+    );
+
+    if ring_delta != DVec2::ZERO {
+        look_angles.add_yaw(-ring_delta.x);
+        // Raise the eye in proportion to its distance, like zooming, rather than pitching it.
+        let radius = transform.eye.distance(transform.target + pivot_offset);
+        let offset = radius * look_angles.unit_vector_with_up(up);
+        let new_offset = offset + ring_delta.y * radius * up;
+        if let Some(dir) = new_offset.try_normalize().filter(|_| radius > 0.0) {
+            look_angles = LookAngles::from_vector_with_up(dir, up);
+            radius_scalar *= new_offset.length() / radius;
+        }
+    }
+
+    if let Some(center) = controller.globe_center.filter(|_| pan != DVec3::ZERO) {
+        // Panning moves along the plane tangent to the globe, so bring the target back down to the surface.
+        let distance = (transform.target - pan - center).length();
+        if let Some(dir) = (transform.target - center).try_normalize() {
+            transform.target = center + distance * dir;
+        }
+    }
+
+    let terrain_ground = ground.filter(|_| controller.terrain_following && pan != DVec3::ZERO);
+    if let Some(ground) = terrain_ground {
+        let heights = (
+            ground.height_below(transform.eye),
+            ground.height_below(transform.eye + pan),
+        );
+        if let (Some(old_height), Some(new_height)) = heights {
+            transform.target += (new_height - old_height) * up;
+        }
+    }
+
+    // Enforced here rather than in the input maps, so the limits hold for any source of events.
+    look_angles.clamp_pitch(controller.min_pitch, controller.max_pitch);
+    if let Some((min_yaw, max_yaw)) = controller.yaw_range {
+        look_angles.clamp_yaw(min_yaw, max_yaw);
+    }
+
+    look_angles.assert_not_looking_up();
 
-        look_angles.assert_not_looking_up();
+    let pivot = transform.target + pivot_offset;
+    let new_radius = (radius_scalar * transform.eye.distance(pivot))
+        .min(1000000.0)
+        .max(0.001);
+    transform.eye = pivot + new_radius * look_angles.unit_vector_with_up(up);
+    transform.up = up;
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    use bevy::{
+        ecs::{entity::EntityMap, reflect::AppTypeRegistry},
+        scene::DynamicScene,
+    };
+
+    #[test]
+    fn test_scene_round_trip() {
+        let mut app = headless_app();
+        app.add_plugin(OrbitCameraPlugin::new(true));
+        let camera = app
+            .world
+            .spawn(OrbitCameraBundle::new(
+                OrbitCameraController::default(),
+                DVec3::Z,
+                DVec3::ZERO,
+            ))
+            .id();
+        // Save while the smoother is still catching up.
+        send_control_event(&mut app, ControlEvent::Orbit(DVec2::new(0.5, 0.0)));
+        step(&mut app, 3);
+
+        let registry = app.world.resource::<AppTypeRegistry>().clone();
+        let scene = DynamicScene::from_world(&app.world, &registry);
+        assert!(scene.serialize_ron(&registry).is_ok());
+
+        let mut loaded = headless_app();
+        loaded.add_plugin(OrbitCameraPlugin::new(true));
+        let mut entity_map = EntityMap::default();
+        scene
+            .write_to_world(&mut loaded.world, &mut entity_map)
+            .unwrap();
+        let loaded_camera = entity_map.get(camera).unwrap();
+        loaded
+            .world
+            .entity_mut(loaded_camera)
+            .insert(Transform::default());
+
+        step(&mut app, 5);
+        step(&mut loaded, 5);
+        let snapshot = |app: &App, entity| app.world.get::<Smoother>(entity).unwrap().snapshot();
+        assert_eq!(snapshot(&app, camera), snapshot(&loaded, loaded_camera));
+        assert_eq!(
+            look_transform(&app, camera),
+            look_transform(&loaded, loaded_camera)
+        );
+    }
 
-        let new_radius = (radius_scalar * transform.radius())
-            .min(1000000.0)
-            .max(0.001);
-        transform.eye = transform.target + new_radius * look_angles.unit_vector();
+    #[test]
+    fn test_orbit_pivot_offset() {
+        let mut app = headless_app();
+        app.add_plugin(OrbitCameraPlugin::new(true));
+        let controller = OrbitCameraController {
+            pivot_offset: DVec3::Y,
+            ..Default::default()
+        };
+        let camera = app
+            .world
+            .spawn(OrbitCameraBundle::new(
+                controller,
+                DVec3::new(0.0, 1.0, 4.0),
+                DVec3::ZERO,
+            ))
+            .id();
+
+        send_control_event(&mut app, ControlEvent::Orbit(DVec2::new(1.0, 0.0)));
+        step(&mut app, 2);
+
+        let transform = look_transform(&app, camera);
+        assert_eq!(transform.target, DVec3::ZERO);
+        assert!((transform.eye.distance(DVec3::Y) - 4.0).abs() < 1e-9);
+        assert!((transform.eye.y - 1.0).abs() < 1e-9);
+        assert!(transform.eye.x.abs() > 1.0);
+    }
+
+    #[test]
+    fn test_orbit_elevation_ring() {
+        let mut app = headless_app();
+        app.add_plugin(OrbitCameraPlugin::new(true));
+        let controller = OrbitCameraController {
+            rotation_mode: OrbitRotationMode::ElevationRing,
+            ..Default::default()
+        };
+        let camera = app
+            .world
+            .spawn(OrbitCameraBundle::new(
+                controller,
+                DVec3::new(0.0, 0.0, 4.0),
+                DVec3::ZERO,
+            ))
+            .id();
+
+        send_control_event(&mut app, ControlEvent::Orbit(DVec2::new(0.0, 0.25)));
+        step(&mut app, 2);
+        let raised = look_transform(&app, camera);
+        assert!(raised.eye.abs_diff_eq(DVec3::new(0.0, 1.0, 4.0), 1e-9));
+
+        send_control_event(&mut app, ControlEvent::Orbit(DVec2::new(1.0, 0.0)));
+        step(&mut app, 2);
+        let circled = look_transform(&app, camera);
+        assert!((circled.eye.y - 1.0).abs() < 1e-9);
+        assert!((circled.eye.x.hypot(circled.eye.z) - 4.0).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,552 @@
+use crate::{
+    controllers::{
+        add_mouse_motion_suppression, look_direction_or_recover, register_controller_types,
+        CameraControlSystem, CameraError, CameraInputState, ControllerDisableStack,
+        FallbackLookDirection, MouseMotionSuppression, ScrollSensitivity,
+    },
+    LookAngles, LookTransform, LookTransformSystem, Smoother,
+};
+
+use bevy::{
+    app::prelude::*,
+    ecs::prelude::*,
+    input::{
+        mouse::{MouseMotion, MouseWheel},
+        prelude::*,
+    },
+    math::prelude::*,
+    reflect::{FromReflect, Reflect},
+    render::camera::Projection,
+    transform::components::Transform,
+};
+use bevy::math::{DVec2, DVec3};
+use serde::{Deserialize, Serialize};
+
+/// A free camera for taking screenshots, which temporarily takes over a gameplay camera.
+///
+/// Send `PhotoModeEvent::Enter` to take over a camera: its current `LookTransform` and field of view are saved, and its
+/// controllers are disabled through its `ControllerDisableStack`. `PhotoModeEvent::Exit` restores all of them. Nothing in photo mode depends on
+/// `Time`, so it keeps working while the game is paused by stopping time.
+#[derive(Default)]
+pub struct PhotoModePlugin {
+    pub override_input_system: bool,
+}
+
+impl PhotoModePlugin {
+    pub fn new(override_input_system: bool) -> Self {
+        Self {
+            override_input_system,
+        }
+    }
+}
+
+impl Plugin for PhotoModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraInputState>()
+            .register_type::<PhotoModeController>();
+        register_controller_types(app);
+
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            photo_mode_event_system.before(CameraControlSystem::EnabledChanged),
+        )
+        .add_system(photo_mode_smoother_system.before(LookTransformSystem::Smooth))
+        .add_system(
+            control_system
+                .label(CameraControlSystem::Control)
+                .after(CameraControlSystem::Input)
+                .before(LookTransformSystem::Smooth),
+        )
+        .add_system(roll_system.after(LookTransformSystem::Smooth))
+        .add_event::<PhotoModeEvent>()
+        .add_event::<ControlEvent>()
+        .add_event::<CameraError>();
+
+        if !self.override_input_system {
+            app.add_system(default_input_map.label(CameraControlSystem::Input));
+            add_mouse_motion_suppression(app);
+        }
+    }
+}
+
+/// Enters or leaves photo mode on a camera with a `LookTransform`.
+#[derive(Clone, Copy, Debug)]
+pub enum PhotoModeEvent {
+    Enter {
+        camera: Entity,
+        /// Keep the camera within `PhotoModeController::max_distance` of this point, usually the player.
+        anchor: Option<DVec3>,
+    },
+    Exit {
+        camera: Entity,
+    },
+}
+
+/// Settings for photo mode. Add this to a camera to configure it before entering photo mode, otherwise the defaults are
+/// used.
+#[derive(Clone, Component, Copy, Debug, Deserialize, FromReflect, Reflect, Serialize)]
+#[reflect(Component)]
+pub struct PhotoModeController {
+    pub mouse_rotate_sensitivity: DVec2,
+    pub translate_sensitivity: f64,
+    /// How much each line of mouse wheel scrolling multiplies the movement speed
+    pub speed_wheel_factor: f64,
+    pub min_speed_scale: f64,
+    pub max_speed_scale: f64,
+    /// Normalizes mouse wheel and touchpad scrolling
+    pub scroll_sensitivity: ScrollSensitivity,
+    /// Radians of roll per frame while a roll key is held
+    pub roll_sensitivity: f64,
+    /// Radians of field of view change per frame while a zoom key is held
+    pub fov_sensitivity: f32,
+    pub min_fov: f32,
+    pub max_fov: f32,
+    /// Farthest the eye can move from the anchor given in `PhotoModeEvent::Enter`
+    pub max_distance: f64,
+}
+
+impl Default for PhotoModeController {
+    fn default() -> Self {
+        Self {
+            mouse_rotate_sensitivity: DVec2::splat(0.002),
+            translate_sensitivity: 0.1,
+            speed_wheel_factor: 1.2,
+            min_speed_scale: 0.01,
+            max_speed_scale: 100.0,
+            scroll_sensitivity: ScrollSensitivity::default(),
+            roll_sensitivity: 0.01,
+            fov_sensitivity: 0.01,
+            min_fov: 0.1,
+            max_fov: 2.0,
+            max_distance: 20.0,
+        }
+    }
+}
+
+/// Present on a camera while it's in photo mode.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct PhotoModeState {
+    snapshot: LookTransform,
+    snapshot_fov: Option<f32>,
+    anchor: Option<DVec3>,
+    roll: f64,
+    speed_scale: f64,
+}
+
+impl PhotoModeState {
+    /// Roll around the view direction, in radians.
+    pub fn roll(&self) -> f64 {
+        self.roll
+    }
+
+    /// Multiplier on the movement speed, adjusted with the mouse wheel.
+    pub fn speed_scale(&self) -> f64 {
+        self.speed_scale
+    }
+
+    /// The `LookTransform` the camera will return to on exit.
+    pub fn snapshot(&self) -> LookTransform {
+        self.snapshot
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum ControlEvent {
+    Rotate(DVec2),
+    TranslateEye(DVec3),
+    Roll(f64),
+    Fov(f32),
+    /// Multiply the movement speed.
+    ScaleSpeed(f64),
+    ResetRoll,
+}
+
+/// The reason pushed onto the `ControllerDisableStack` of a camera in photo mode.
+const PHOTO_MODE_REASON: &str = "photo_mode";
+
+fn photo_mode_event_system(
+    mut commands: Commands,
+    mut events: EventReader<PhotoModeEvent>,
+    mut cameras: Query<(
+        &mut LookTransform,
+        &mut Transform,
+        Option<&mut Projection>,
+        Option<&PhotoModeController>,
+        Option<&PhotoModeState>,
+        Option<&mut ControllerDisableStack>,
+        Option<&mut Smoother>,
+    )>,
+) {
+    for event in events.iter() {
+        match *event {
+            PhotoModeEvent::Enter { camera, anchor } => {
+                let (look, _, projection, controller, state, stack, _) =
+                    if let Ok(c) = cameras.get_mut(camera) {
+                        c
+                    } else {
+                        continue;
+                    };
+                if state.is_some() {
+                    continue;
+                }
+
+                let snapshot_fov = projection.and_then(|p| match p.as_ref() {
+                    Projection::Perspective(perspective) => Some(perspective.fov),
+                    _ => None,
+                });
+                let mut entity_commands = commands.entity(camera);
+                if let Some(mut stack) = stack {
+                    stack.push_disabled(PHOTO_MODE_REASON);
+                } else {
+                    let mut stack = ControllerDisableStack::default();
+                    stack.push_disabled(PHOTO_MODE_REASON);
+                    entity_commands.insert(stack);
+                }
+                entity_commands.insert(PhotoModeState {
+                    snapshot: *look,
+                    snapshot_fov,
+                    anchor,
+                    roll: 0.0,
+                    speed_scale: 1.0,
+                });
+                if controller.is_none() {
+                    entity_commands.insert(PhotoModeController::default());
+                }
+            }
+            PhotoModeEvent::Exit { camera } => {
+                let (mut look, mut transform, projection, _, state, stack, smoother) =
+                    if let Ok(c) = cameras.get_mut(camera) {
+                        c
+                    } else {
+                        continue;
+                    };
+                let state = if let Some(state) = state {
+                    *state
+                } else {
+                    continue;
+                };
+
+                *look = state.snapshot;
+                // Undo the roll right away, since the smoother won't touch the `Transform` again if the camera is
+                // already settled on the snapshot.
+                transform.rotation = Transform::from(*look).rotation;
+                if let (Some(mut projection), Some(fov)) = (projection, state.snapshot_fov) {
+                    if let Projection::Perspective(perspective) = projection.as_mut() {
+                        perspective.fov = fov;
+                    }
+                }
+                if let Some(mut stack) = stack {
+                    stack.pop_disabled(PHOTO_MODE_REASON);
+                }
+                // Jump straight back rather than smoothing across the scene.
+                if let Some(mut smoother) = smoother {
+                    smoother.reset();
+                }
+                commands.entity(camera).remove::<PhotoModeState>();
+            }
+        }
+    }
+}
+
+/// Disabling the gameplay controllers in photo mode also disables the smoother, which would freeze the camera, so keep
+/// turning it back on.
+fn photo_mode_smoother_system(mut smoothers: Query<&mut Smoother, With<PhotoModeState>>) {
+    for mut smoother in smoothers.iter_mut() {
+        if !smoother.is_enabled() {
+            smoother.set_enabled(true);
+        }
+    }
+}
+
+pub fn default_input_map(
+    mut events: EventWriter<ControlEvent>,
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    motion_suppression: Res<MouseMotionSuppression>,
+    keyboard: Res<Input<KeyCode>>,
+    controllers: Query<&PhotoModeController, With<PhotoModeState>>,
+    input_state: Res<CameraInputState>,
+) {
+    if input_state.is_blocked() {
+        return;
+    }
+
+    // Can only control one camera at a time.
+    let controller = if let Some(controller) = controllers.iter().next() {
+        controller
+    } else {
+        return;
+    };
+    let PhotoModeController {
+        mouse_rotate_sensitivity,
+        speed_wheel_factor,
+        scroll_sensitivity,
+        roll_sensitivity,
+        fov_sensitivity,
+        ..
+    } = *controller;
+
+    let mut cursor_delta = DVec2::ZERO;
+    for event in mouse_motion_events.iter() {
+        cursor_delta += DVec2::new(event.delta.x as f64, event.delta.y as f64);
+    }
+    if !motion_suppression.is_active() && cursor_delta != DVec2::ZERO {
+        events.send(ControlEvent::Rotate(mouse_rotate_sensitivity * cursor_delta));
+    }
+
+    for (key, dir) in [
+        (KeyCode::W, DVec3::Z),
+        (KeyCode::A, DVec3::X),
+        (KeyCode::S, -DVec3::Z),
+        (KeyCode::D, -DVec3::X),
+        (KeyCode::LShift, -DVec3::Y),
+        (KeyCode::Space, DVec3::Y),
+    ]
+    .iter()
+    .cloned()
+    {
+        if keyboard.pressed(key) {
+            events.send(ControlEvent::TranslateEye(dir));
+        }
+    }
+
+    if keyboard.pressed(KeyCode::Q) {
+        events.send(ControlEvent::Roll(-roll_sensitivity));
+    }
+    if keyboard.pressed(KeyCode::E) {
+        events.send(ControlEvent::Roll(roll_sensitivity));
+    }
+    if keyboard.just_pressed(KeyCode::R) {
+        events.send(ControlEvent::ResetRoll);
+    }
+
+    if keyboard.pressed(KeyCode::Z) {
+        events.send(ControlEvent::Fov(-fov_sensitivity));
+    }
+    if keyboard.pressed(KeyCode::X) {
+        events.send(ControlEvent::Fov(fov_sensitivity));
+    }
+
+    let mut scroll_lines = 0.0;
+    for event in mouse_wheel_reader.iter() {
+        scroll_lines += scroll_sensitivity.lines(event).y;
+    }
+    if scroll_lines != 0.0 {
+        events.send(ControlEvent::ScaleSpeed(
+            speed_wheel_factor.powf(scroll_lines),
+        ));
+    }
+}
+
+pub fn control_system(
+    mut events: EventReader<ControlEvent>,
+    mut errors: EventWriter<CameraError>,
+    fallback: Option<Res<FallbackLookDirection>>,
+    mut cameras: Query<(
+        Entity,
+        &PhotoModeController,
+        &mut PhotoModeState,
+        &mut LookTransform,
+        Option<&mut Projection>,
+        Option<&Smoother>,
+    )>,
+) {
+    // Can only control one camera at a time.
+    let (camera, controller, mut state, mut transform, mut projection, smoother) =
+        if let Some(camera) = cameras.iter_mut().next() {
+            camera
+        } else {
+            return;
+        };
+
+    let up = transform.up;
+    let look_vector = look_direction_or_recover(
+        camera,
+        &mut transform,
+        smoother,
+        fallback.as_deref(),
+        &mut errors,
+    );
+    let mut look_angles = LookAngles::from_vector_with_up(look_vector, up);
+    let mut translation = DVec3::ZERO;
+    let mut fov_delta = 0.0;
+    for event in events.iter() {
+        match event {
+            ControlEvent::Rotate(delta) => {
+                look_angles.add_yaw(-delta.x);
+                look_angles.add_pitch(-delta.y);
+            }
+            ControlEvent::TranslateEye(delta) => {
+                translation += *delta;
+            }
+            ControlEvent::Roll(delta) => {
+                state.roll += delta;
+            }
+            ControlEvent::ResetRoll => {
+                state.roll = 0.0;
+            }
+            ControlEvent::Fov(delta) => {
+                fov_delta += delta;
+            }
+            ControlEvent::ScaleSpeed(scalar) => {
+                state.speed_scale = (state.speed_scale * scalar)
+                    .clamp(controller.min_speed_scale, controller.max_speed_scale);
+            }
+        }
+    }
+
+    look_angles.assert_not_looking_up();
+
+    // Unlike the fps controller, fly along the view direction, pitch included.
+    let forward = look_angles.unit_vector_with_up(up);
+    let left = up.cross(forward).normalize_or_zero();
+    let speed = controller.translate_sensitivity * state.speed_scale;
+    transform.eye +=
+        speed * (translation.x * left + translation.y * up + translation.z * forward);
+
+    if let Some(anchor) = state.anchor {
+        let offset = transform.eye - anchor;
+        transform.eye = anchor + offset.clamp_length_max(controller.max_distance);
+    }
+
+    transform.target = transform.eye + transform.radius() * forward;
+
+    if fov_delta != 0.0 {
+        if let Some(Projection::Perspective(perspective)) = projection.as_deref_mut() {
+            perspective.fov =
+                (perspective.fov + fov_delta).clamp(controller.min_fov, controller.max_fov);
+        }
+    }
+}
+
+/// `LookTransform` has no roll, so apply it to the smoothed `Transform` after smoothing.
+fn roll_system(
+    mut cameras: Query<(&PhotoModeState, &LookTransform, Option<&Smoother>, &mut Transform)>,
+) {
+    for (state, look_transform, smoother, mut transform) in cameras.iter_mut() {
+        let smoothed = smoother
+            .and_then(|s| s.smoothed_transform())
+            .unwrap_or(*look_transform);
+        let rotation = Transform::from(smoothed).rotation * DQuat::from_rotation_z(state.roll);
+        // Avoid marking a settled camera's `Transform` as changed every frame.
+        if transform.rotation != rotation {
+            transform.rotation = rotation;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::*, LookTransformBundle};
+
+    use bevy::render::camera::PerspectiveProjection;
+
+    const START_FOV: f32 = std::f32::consts::FRAC_PI_4;
+
+    fn photo_mode_app() -> (App, Entity) {
+        let mut app = headless_app();
+        app.add_plugin(PhotoModePlugin::new(true));
+        let camera = app
+            .world
+            .spawn((
+                LookTransformBundle {
+                    transform: LookTransform::new(DVec3::ZERO, DVec3::Z),
+                    smoother: Smoother::new(0.0),
+                },
+                Transform::default(),
+                Projection::Perspective(PerspectiveProjection {
+                    fov: START_FOV,
+                    ..Default::default()
+                }),
+            ))
+            .id();
+        send_control_event(
+            &mut app,
+            PhotoModeEvent::Enter {
+                camera,
+                anchor: Some(DVec3::ZERO),
+            },
+        );
+        step(&mut app, 1);
+
+        (app, camera)
+    }
+
+    fn fov(app: &App, camera: Entity) -> f32 {
+        match app.world.get::<Projection>(camera).unwrap() {
+            Projection::Perspective(perspective) => perspective.fov,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_photo_mode_roll() {
+        let (mut app, camera) = photo_mode_app();
+
+        send_control_event(&mut app, ControlEvent::Roll(0.3));
+        send_control_event(&mut app, ControlEvent::Roll(0.3));
+        step(&mut app, 1);
+        assert!((app.world.get::<PhotoModeState>(camera).unwrap().roll() - 0.6).abs() < 1e-9);
+        let right = scene_transform(&app, camera).rotation * DVec3::X;
+        assert!((right.y - 0.6f64.sin()).abs() < 1e-9);
+
+        send_control_event(&mut app, ControlEvent::ResetRoll);
+        step(&mut app, 1);
+        let right = scene_transform(&app, camera).rotation * DVec3::X;
+        assert!(right.y.abs() < 1e-9);
+
+        send_control_event(&mut app, ControlEvent::Roll(0.3));
+        step(&mut app, 1);
+        send_control_event(&mut app, PhotoModeEvent::Exit { camera });
+        step(&mut app, 1);
+        assert!(app.world.get::<PhotoModeState>(camera).is_none());
+        let right = scene_transform(&app, camera).rotation * DVec3::X;
+        assert!(right.y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_photo_mode_fov_limits() {
+        let (mut app, camera) = photo_mode_app();
+        let controller = PhotoModeController::default();
+
+        send_control_event(&mut app, ControlEvent::Fov(10.0));
+        step(&mut app, 1);
+        assert_eq!(fov(&app, camera), controller.max_fov);
+
+        send_control_event(&mut app, ControlEvent::Fov(-10.0));
+        step(&mut app, 1);
+        assert_eq!(fov(&app, camera), controller.min_fov);
+
+        send_control_event(&mut app, PhotoModeEvent::Exit { camera });
+        step(&mut app, 1);
+        assert_eq!(fov(&app, camera), START_FOV);
+    }
+
+    #[test]
+    fn test_photo_mode_stays_near_anchor() {
+        let (mut app, camera) = photo_mode_app();
+        let max_distance = PhotoModeController::default().max_distance;
+
+        send_control_event(&mut app, ControlEvent::ScaleSpeed(1000.0));
+        for _ in 0..5 {
+            send_control_event(&mut app, ControlEvent::TranslateEye(DVec3::Z));
+            step(&mut app, 1);
+        }
+        let transform = look_transform(&app, camera);
+        assert!((transform.eye.length() - max_distance).abs() < 1e-9);
+        let direction = transform.look_direction().unwrap();
+        assert!(direction.abs_diff_eq(DVec3::Z, 1e-9));
+
+        send_control_event(&mut app, PhotoModeEvent::Exit { camera });
+        step(&mut app, 1);
+        assert_eq!(look_transform(&app, camera).eye, DVec3::ZERO);
+    }
+}
@@ -0,0 +1,510 @@
+use crate::{
+    controllers::{
+        add_applied_weights, add_disable_stack_system, add_mouse_motion_suppression,
+        add_toggle_key, discard_control_events, look_direction_or_recover,
+        primary_window_unfocused, register_controller_types, wheel_factor, zoom_fov,
+        CameraControlSystem, CameraError, CameraInputState, EnabledController,
+        FallbackLookDirection, MouseMotionSuppression, ScrollSensitivity, WheelAction,
+    },
+    CameraObstruction, KeyRepeat, KeyboardNavigation, LookAngles, LookTransform,
+    LookTransformBundle, LookTransformSystem, Smoother,
+};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{bundle::Bundle, prelude::*},
+    input::{
+        mouse::{MouseMotion, MouseWheel},
+        prelude::*,
+    },
+    reflect::{FromReflect, Reflect},
+    render::camera::Projection,
+    time::{FixedTimestep, Time},
+    transform::components::Transform,
+};
+use bevy::math::{DVec2, DVec3};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default)]
+pub struct SpectatorCameraPlugin {
+    pub override_input_system: bool,
+    /// Run the control system on a fixed timestep of this many seconds instead of every frame, for deterministic motion.
+    /// Input maps and smoothing still run every frame. Steps longer than two frames may drop some input.
+    pub fixed_timestep: Option<f64>,
+    /// Ignore all input while the primary window doesn't have focus.
+    pub disable_when_unfocused: bool,
+    /// Add the keyboard-only input map, configured by the `KeyboardNavigation` resource. This works alongside the default
+    /// input map.
+    pub keyboard_navigation: bool,
+    /// Pressing this key will flip `enabled` on all spectator controllers, and grab the cursor while they're enabled.
+    pub toggle_key: Option<KeyCode>,
+}
+
+impl SpectatorCameraPlugin {
+    pub fn new(override_input_system: bool) -> Self {
+        Self {
+            override_input_system,
+            fixed_timestep: None,
+            disable_when_unfocused: false,
+            keyboard_navigation: false,
+            toggle_key: None,
+        }
+    }
+
+    pub fn with_fixed_timestep(mut self, step: f64) -> Self {
+        self.fixed_timestep = Some(step);
+        self
+    }
+
+    pub fn with_disable_when_unfocused(mut self, disable_when_unfocused: bool) -> Self {
+        self.disable_when_unfocused = disable_when_unfocused;
+        self
+    }
+
+    pub fn with_keyboard_navigation(mut self, keyboard_navigation: bool) -> Self {
+        self.keyboard_navigation = keyboard_navigation;
+        self
+    }
+
+    pub fn with_toggle_key(mut self, key: KeyCode) -> Self {
+        self.toggle_key = Some(key);
+        self
+    }
+}
+
+impl Plugin for SpectatorCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraInputState>()
+            .register_type::<SpectatorCameraController>();
+        register_controller_types(app);
+        add_disable_stack_system::<SpectatorCameraController>(app);
+        add_applied_weights::<SpectatorCameraController, f64>(app);
+
+        let mut systems = SystemSet::new().with_system(
+            control_system
+                .label(CameraControlSystem::Control)
+                .after(CameraControlSystem::Input)
+                .before(LookTransformSystem::Smooth),
+        );
+        if !self.override_input_system {
+            app.add_system(default_input_map.label(CameraControlSystem::Input));
+            add_mouse_motion_suppression(app);
+        }
+        if self.keyboard_navigation {
+            app.add_system(keyboard_input_map.label(CameraControlSystem::Input));
+            app.init_resource::<KeyboardNavigation>();
+        }
+        if let Some(step) = self.fixed_timestep {
+            systems = systems.with_run_criteria(FixedTimestep::step(step));
+        }
+
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            on_controller_enabled_changed.label(CameraControlSystem::EnabledChanged),
+        )
+        .add_system_set(systems)
+        .add_system(unsmoothed_rotation_system.after(LookTransformSystem::Smooth))
+        .add_event::<ControlEvent>()
+        .add_event::<CameraError>();
+
+        if let Some(key) = self.toggle_key {
+            add_toggle_key::<SpectatorCameraController>(app, key, true);
+        }
+
+        if self.disable_when_unfocused {
+            app.add_system(
+                discard_control_events::<ControlEvent>
+                    .with_run_criteria(primary_window_unfocused)
+                    .after(CameraControlSystem::Input)
+                    .before(CameraControlSystem::Control),
+            );
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct SpectatorCameraBundle {
+    controller: SpectatorCameraController,
+    look_transform: LookTransformBundle,
+    transform: Transform,
+}
+
+impl SpectatorCameraBundle {
+    pub fn new(controller: SpectatorCameraController, eye: DVec3, target: DVec3) -> Self {
+        // Make sure the transform is consistent with the controller to start.
+        let transform = Transform::from_translation(eye).looking_at(target, DVec3::Y);
+
+        Self {
+            controller,
+            look_transform: LookTransformBundle {
+                transform: LookTransform::new(eye, target),
+                smoother: Smoother::new(controller.smoothing_weight),
+            },
+            transform,
+        }
+    }
+
+    /// Use `up` as the world's up axis instead of +Y. See `LookTransform::up`.
+    pub fn with_up(mut self, up: DVec3) -> Self {
+        self.look_transform.transform.up = up;
+        self.transform = self.look_transform.transform.into();
+        self
+    }
+}
+
+/// A free-flying camera for observing a scene. Unlike the fps controller, it flies along the view direction, steps
+/// through discrete speeds with the mouse wheel, and by default doesn't smooth rotation at all.
+///
+/// Add a `CameraObstruction` to the camera to have it collide with geometry while `ghost` is off.
+#[derive(Clone, Component, Copy, Debug, Deserialize, FromReflect, Reflect, Serialize)]
+#[reflect(Component)]
+#[serde(default)]
+pub struct SpectatorCameraController {
+    pub enabled: bool,
+    pub mouse_rotate_sensitivity: DVec2,
+    /// Distance per frame at a speed multiplier of `1.0`
+    pub translate_sensitivity: f64,
+    /// Each mouse wheel step multiplies or divides the speed by this
+    pub speed_step_factor: f64,
+    /// The slowest speed is `speed_step_factor` to this (negative) power
+    pub min_speed_step: i32,
+    /// The fastest speed is `speed_step_factor` to this power
+    pub max_speed_step: i32,
+    /// Speed multiplier while Shift is held
+    pub boost_multiplier: f64,
+    /// Normalizes mouse wheel and touchpad scrolling
+    pub scroll_sensitivity: ScrollSensitivity,
+    /// What the mouse wheel does. `WheelAction::Speed` steps through speeds, and `WheelAction::Zoom` moves forward and
+    /// back a unit of speed per line.
+    pub wheel_action: WheelAction,
+    /// Pass through geometry instead of colliding with the camera's `CameraObstruction`. Toggled with G.
+    pub ghost: bool,
+    /// Smooth rotation along with translation. Off by default, so the view follows the mouse exactly.
+    pub smooth_rotation: bool,
+    pub smoothing_weight: f64,
+}
+
+impl Default for SpectatorCameraController {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mouse_rotate_sensitivity: DVec2::splat(0.002),
+            translate_sensitivity: 0.25,
+            speed_step_factor: 2.0,
+            min_speed_step: -4,
+            max_speed_step: 4,
+            boost_multiplier: 4.0,
+            scroll_sensitivity: ScrollSensitivity::default(),
+            wheel_action: WheelAction::Speed,
+            ghost: true,
+            smooth_rotation: false,
+            smoothing_weight: 0.9,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum ControlEvent {
+    Rotate(DVec2),
+    TranslateEye(DVec3),
+    /// Step the speed up (positive) or down (negative).
+    StepSpeed(i32),
+    ToggleGhost,
+    /// Divide the field of view by this factor.
+    ZoomFov(f64),
+}
+
+define_on_controller_enabled_changed!(SpectatorCameraController);
+
+impl EnabledController for SpectatorCameraController {
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
+pub fn default_input_map(
+    mut events: EventWriter<ControlEvent>,
+    keyboard: Res<Input<KeyCode>>,
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    motion_suppression: Res<MouseMotionSuppression>,
+    controllers: Query<&SpectatorCameraController>,
+    input_state: Res<CameraInputState>,
+    mut scroll_remainder: Local<f64>,
+) {
+    if input_state.is_blocked() {
+        return;
+    }
+
+    // Can only control one camera at a time.
+    let controller = if let Some(controller) = controllers.iter().find(|c| c.enabled) {
+        controller
+    } else {
+        return;
+    };
+    let SpectatorCameraController {
+        mouse_rotate_sensitivity,
+        boost_multiplier,
+        scroll_sensitivity,
+        wheel_action,
+        ..
+    } = *controller;
+
+    let mut cursor_delta = DVec2::ZERO;
+    for event in mouse_motion_events.iter() {
+        cursor_delta += DVec2::new(event.delta.x as f64, event.delta.y as f64);
+    }
+    if !motion_suppression.is_active() && cursor_delta != DVec2::ZERO {
+        events.send(ControlEvent::Rotate(mouse_rotate_sensitivity * cursor_delta));
+    }
+
+    let boost = if keyboard.pressed(KeyCode::LShift) {
+        boost_multiplier
+    } else {
+        1.0
+    };
+    for (key, dir) in [
+        (KeyCode::W, DVec3::Z),
+        (KeyCode::A, DVec3::X),
+        (KeyCode::S, -DVec3::Z),
+        (KeyCode::D, -DVec3::X),
+        (KeyCode::LControl, -DVec3::Y),
+        (KeyCode::Space, DVec3::Y),
+    ]
+    .iter()
+    .cloned()
+    {
+        if keyboard.pressed(key) {
+            events.send(ControlEvent::TranslateEye(boost * dir));
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::G) {
+        events.send(ControlEvent::ToggleGhost);
+    }
+
+    let mut lines = 0.0;
+    for event in mouse_wheel_reader.iter() {
+        lines += scroll_sensitivity.lines(event).y;
+    }
+    match wheel_action {
+        WheelAction::Speed => {
+            // Accumulate pixel scrolling until it adds up to whole steps.
+            *scroll_remainder += lines;
+            let steps = scroll_remainder.trunc();
+            if steps != 0.0 {
+                *scroll_remainder -= steps;
+                events.send(ControlEvent::StepSpeed(steps as i32));
+            }
+        }
+        WheelAction::Zoom if lines != 0.0 => {
+            events.send(ControlEvent::TranslateEye(lines * DVec3::Z));
+        }
+        WheelAction::Fov if lines != 0.0 => {
+            events.send(ControlEvent::ZoomFov(wheel_factor(lines)));
+        }
+        _ => {}
+    }
+}
+
+/// Input map for controlling the camera with only the keyboard. See `KeyboardNavigation`.
+pub fn keyboard_input_map(
+    mut events: EventWriter<ControlEvent>,
+    keyboard: Res<Input<KeyCode>>,
+    settings: Res<KeyboardNavigation>,
+    controllers: Query<&SpectatorCameraController>,
+    time: Res<Time>,
+    input_state: Res<CameraInputState>,
+    mut repeat: Local<KeyRepeat>,
+) {
+    if input_state.is_blocked() {
+        return;
+    }
+
+    // Can only control one camera at a time.
+    if !controllers.iter().any(|c| c.enabled) {
+        return;
+    }
+
+    let steps = repeat.read(&settings, &keyboard, time.elapsed_seconds_f64());
+    if steps.rotate != DVec2::ZERO {
+        events.send(ControlEvent::Rotate(steps.rotate));
+    }
+    // Zooming moves forward and back. Distances are scaled by the current speed, like keyboard movement.
+    let translation = DVec3::new(
+        -steps.pan.x,
+        steps.pan.y,
+        steps.zoom as f64 * settings.translate_step,
+    );
+    if translation != DVec3::ZERO {
+        events.send(ControlEvent::TranslateEye(translation));
+    }
+}
+
+pub fn control_system(
+    mut events: EventReader<ControlEvent>,
+    mut errors: EventWriter<CameraError>,
+    fallback: Option<Res<FallbackLookDirection>>,
+    mut cameras: Query<(
+        Entity,
+        &mut SpectatorCameraController,
+        &mut LookTransform,
+        Option<&CameraObstruction>,
+        Option<&mut Projection>,
+        Option<&Smoother>,
+    )>,
+    mut speed_step: Local<i32>,
+) {
+    // Can only control one camera at a time.
+    let (camera, mut controller, mut transform, obstruction, mut projection, smoother) =
+        if let Some(camera) = cameras.iter_mut().find(|c| c.1.enabled) {
+            camera
+        } else {
+            return;
+        };
+
+    let up = transform.up;
+    let look_vector = look_direction_or_recover(
+        camera,
+        &mut transform,
+        smoother,
+        fallback.as_deref(),
+        &mut errors,
+    );
+    let mut look_angles = LookAngles::from_vector_with_up(look_vector, up);
+    let mut translation = DVec3::ZERO;
+    for event in events.iter() {
+        match event {
+            ControlEvent::Rotate(delta) => {
+                look_angles.add_yaw(-delta.x);
+                look_angles.add_pitch(-delta.y);
+            }
+            ControlEvent::TranslateEye(delta) => {
+                translation += *delta;
+            }
+            ControlEvent::StepSpeed(steps) => {
+                *speed_step = (*speed_step + steps)
+                    .clamp(controller.min_speed_step, controller.max_speed_step);
+            }
+            ControlEvent::ToggleGhost => {
+                // Don't trigger change detection, which would reset the smoother.
+                let controller = controller.bypass_change_detection();
+                controller.ghost = !controller.ghost;
+            }
+            ControlEvent::ZoomFov(factor) => {
+                zoom_fov(projection.as_deref_mut(), *factor);
+            }
+        }
+    }
+
+    look_angles.assert_not_looking_up();
+
+    let forward = look_angles.unit_vector_with_up(up);
+    let left = up.cross(forward).normalize_or_zero();
+    let speed = controller.translate_sensitivity * controller.speed_step_factor.powi(*speed_step);
+    let wish_eye = transform.eye
+        + speed * (translation.x * left + translation.y * up + translation.z * forward);
+
+    transform.eye = match obstruction {
+        Some(obstruction) if !controller.ghost && wish_eye != transform.eye => {
+            obstruction.resolve(transform.eye, wish_eye)
+        }
+        _ => wish_eye,
+    };
+    transform.target = transform.eye + transform.radius() * forward;
+}
+
+/// Points smoothed cameras straight along their unsmoothed look direction, when rotation smoothing is off.
+fn unsmoothed_rotation_system(
+    mut cameras: Query<(&SpectatorCameraController, &LookTransform, &mut Transform)>,
+) {
+    for (controller, look_transform, mut transform) in cameras.iter_mut() {
+        if !controller.enabled || controller.smooth_rotation {
+            continue;
+        }
+        let rotation = Transform::from(*look_transform).rotation;
+        // Avoid marking a settled camera's `Transform` as changed every frame.
+        if transform.rotation != rotation {
+            transform.rotation = rotation;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn spectator_app() -> (App, Entity) {
+        let mut app = headless_app();
+        app.add_plugin(SpectatorCameraPlugin::new(true));
+        // A wall across the view at z = 1.
+        let wall = |from: DVec3, to: DVec3, _radius: f64| {
+            (from.z < 1.0 && to.z > 1.0).then(|| (1.0 - from.z) / (to.z - from.z))
+        };
+        let camera = app
+            .world
+            .spawn((
+                SpectatorCameraBundle::new(
+                    SpectatorCameraController::default(),
+                    DVec3::ZERO,
+                    DVec3::Z,
+                ),
+                CameraObstruction::new(wall, 0.1),
+            ))
+            .id();
+        step(&mut app, 1);
+
+        (app, camera)
+    }
+
+    #[test]
+    fn test_spectator_speed_steps() {
+        let (mut app, camera) = spectator_app();
+        let controller = SpectatorCameraController::default();
+        let moved = |app: &mut App, event| {
+            let start = look_transform(app, camera).eye;
+            send_control_event(app, event);
+            send_control_event(app, ControlEvent::TranslateEye(-DVec3::Z));
+            step(app, 1);
+            (look_transform(app, camera).eye - start).length()
+        };
+
+        let base = controller.translate_sensitivity;
+        assert!((moved(&mut app, ControlEvent::StepSpeed(1)) - 2.0 * base).abs() < 1e-9);
+        // Steps stop at the fastest and slowest speeds.
+        let fastest = base * controller.speed_step_factor.powi(controller.max_speed_step);
+        assert!((moved(&mut app, ControlEvent::StepSpeed(100)) - fastest).abs() < 1e-9);
+        let slowest = base * controller.speed_step_factor.powi(controller.min_speed_step);
+        assert!((moved(&mut app, ControlEvent::StepSpeed(-100)) - slowest).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_spectator_ghost_toggle() {
+        let (mut app, camera) = spectator_app();
+
+        // Ghosts fly through the wall.
+        send_control_event(&mut app, ControlEvent::StepSpeed(3));
+        send_control_event(&mut app, ControlEvent::TranslateEye(DVec3::Z));
+        step(&mut app, 1);
+        assert!((look_transform(&app, camera).eye.z - 2.0).abs() < 1e-9);
+
+        send_control_event(&mut app, ControlEvent::TranslateEye(-DVec3::Z));
+        step(&mut app, 1);
+        assert!(look_transform(&app, camera).eye.z.abs() < 1e-9);
+
+        send_control_event(&mut app, ControlEvent::ToggleGhost);
+        send_control_event(&mut app, ControlEvent::TranslateEye(DVec3::Z));
+        step(&mut app, 1);
+        let controller = app.world.get::<SpectatorCameraController>(camera).unwrap();
+        assert!(!controller.ghost);
+        assert!((look_transform(&app, camera).eye.z - 1.0).abs() < 1e-9);
+    }
+}
@@ -1,4 +1,16 @@
-use crate::{LookAngles, LookTransform, LookTransformBundle, Smoother};
+use crate::{
+    controllers::{
+        adaptive_sensitivity_scale, add_applied_weights, add_disable_stack_system,
+        add_mouse_motion_suppression, add_toggle_key, discard_control_events,
+        look_direction_or_recover, primary_window_unfocused, register_controller_types,
+        wheel_factor, zoom_fov, AngleSnap, CameraControlSystem, CameraError, CameraInputState,
+        DoubleClickDetector, EdgePan, EnabledController, FallbackLookDirection, FocusPolicy,
+        MouseMotionSuppression, ScrollSensitivity, WheelAction,
+    },
+    up_axis_rotation, CameraRayHits, GroundHeight, KeyRepeat, KeyboardNavigation, LookAngles,
+    LookTransform, LookTransformBundle, LookTransformSystem, MouseFilter, MouseFilterState,
+    ResponseCurve, Smoother, TouchpadGesture, MOUSE_CURVE_REFERENCE,
+};
 
 use bevy::{
     app::prelude::*,
@@ -8,7 +20,12 @@ use bevy::{
         prelude::*,
     },
     math::prelude::*,
+    reflect::{FromReflect, Reflect},
+    render::camera::{OrthographicProjection, PerspectiveProjection, Projection, ScalingMode},
+    time::{FixedTimestep, Time},
     transform::components::Transform,
+    utils::HashMap,
+    window::Windows,
 };
 use bevy::math::DVec2;
 use serde::{Deserialize, Serialize};
@@ -16,24 +33,100 @@ use serde::{Deserialize, Serialize};
 #[derive(Default)]
 pub struct UnrealCameraPlugin {
     pub override_input_system: bool,
+    /// Run the control system on a fixed timestep of this many seconds instead of every frame, for deterministic motion.
+    /// Input maps and smoothing still run every frame. Steps longer than two frames may drop some input.
+    pub fixed_timestep: Option<f64>,
+    /// Ignore all input while the primary window doesn't have focus.
+    pub disable_when_unfocused: bool,
+    /// Add the keyboard-only input map, configured by the `KeyboardNavigation` resource. This works alongside the default
+    /// input map.
+    pub keyboard_navigation: bool,
+    /// Pressing this key will flip `enabled` on all unreal controllers.
+    pub toggle_key: Option<KeyCode>,
 }
 
 impl UnrealCameraPlugin {
     pub fn new(override_input_system: bool) -> Self {
         Self {
             override_input_system,
+            fixed_timestep: None,
+            disable_when_unfocused: false,
+            keyboard_navigation: false,
+            toggle_key: None,
         }
     }
+
+    pub fn with_fixed_timestep(mut self, step: f64) -> Self {
+        self.fixed_timestep = Some(step);
+        self
+    }
+
+    pub fn with_disable_when_unfocused(mut self, disable_when_unfocused: bool) -> Self {
+        self.disable_when_unfocused = disable_when_unfocused;
+        self
+    }
+
+    pub fn with_keyboard_navigation(mut self, keyboard_navigation: bool) -> Self {
+        self.keyboard_navigation = keyboard_navigation;
+        self
+    }
+
+    pub fn with_toggle_key(mut self, key: KeyCode) -> Self {
+        self.toggle_key = Some(key);
+        self
+    }
 }
 
 impl Plugin for UnrealCameraPlugin {
     fn build(&self, app: &mut App) {
-        let app = app
-            .add_system_to_stage(CoreStage::PreUpdate, on_controller_enabled_changed)
-            .add_system(control_system)
-            .add_event::<ControlEvent>();
+        app.init_resource::<CameraInputState>()
+            .register_type::<UnrealCameraController>()
+            .register_type::<UnrealViewport>();
+        register_controller_types(app);
+        add_disable_stack_system::<UnrealCameraController>(app);
+        add_applied_weights::<UnrealCameraController, f64>(app);
+
+        let mut systems = SystemSet::new().with_system(
+            control_system
+                .label(CameraControlSystem::Control)
+                .after(CameraControlSystem::Input)
+                .before(LookTransformSystem::Smooth),
+        );
         if !self.override_input_system {
-            app.add_system(default_input_map);
+            app.add_system(default_input_map.label(CameraControlSystem::Input));
+            add_mouse_motion_suppression(app);
+            app.add_event::<TouchpadGesture>();
+        }
+        if self.keyboard_navigation {
+            app.add_system(keyboard_input_map.label(CameraControlSystem::Input));
+            app.init_resource::<KeyboardNavigation>();
+        }
+        if let Some(step) = self.fixed_timestep {
+            systems = systems.with_run_criteria(FixedTimestep::step(step));
+        }
+
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            on_controller_enabled_changed.label(CameraControlSystem::EnabledChanged),
+        )
+        .add_system_set(systems)
+        .add_system(viewport_system.before(CameraControlSystem::Input))
+        .add_event::<ControlEvent>()
+        .add_event::<SetUnrealViewport>()
+        .add_event::<FlySpeedChanged>()
+        .add_event::<CameraError>();
+
+        if let Some(key) = self.toggle_key {
+            add_toggle_key::<UnrealCameraController>(app, key, false);
+        }
+
+        if self.disable_when_unfocused {
+            app.add_system(
+                discard_control_events::<ControlEvent>
+                    .with_run_criteria(primary_window_unfocused)
+                    .after(CameraControlSystem::Input)
+                    .before(CameraControlSystem::Control),
+            );
         }
     }
 }
@@ -60,10 +153,56 @@ impl UnrealCameraBundle {
             transform,
         }
     }
+
+    /// Use `up` as the world's up axis instead of +Y. See `LookTransform::up`.
+    pub fn with_up(mut self, up: DVec3) -> Self {
+        self.look_transform.transform.up = up;
+        self.transform = self.look_transform.transform.into();
+        self
+    }
+
+    /// Use `smoother` instead of one made from the controller's smoothing settings.
+    pub fn with_smoother(mut self, smoother: Smoother) -> Self {
+        self.look_transform.smoother = smoother;
+        self
+    }
+
+    /// Start in `viewport`, looking at the target from the same distance. Give the camera an `orthographic_projection`
+    /// to go with the orthographic viewports.
+    pub fn with_viewport(mut self, viewport: UnrealViewport) -> Self {
+        let LookTransform { eye, target, up } = self.look_transform.transform;
+        if let Some(view) = viewport.orthographic_view(target, eye.distance(target), up) {
+            self.look_transform.transform = view;
+            self.transform = view.into();
+        }
+        self.controller.viewport = viewport;
+        self
+    }
+}
+
+define_bundle_builder!(UnrealCameraBundleBuilder, UnrealCameraBundle, UnrealCameraController);
+
+impl UnrealCameraBundleBuilder {
+    pub fn rotate_sensitivity(mut self, rotate_sensitivity: DVec2) -> Self {
+        self.controller.rotate_sensitivity = rotate_sensitivity;
+        self
+    }
+
+    pub fn mouse_translate_sensitivity(mut self, mouse_translate_sensitivity: DVec2) -> Self {
+        self.controller.mouse_translate_sensitivity = mouse_translate_sensitivity;
+        self
+    }
+
+    pub fn keyboard_mvmt_sensitivity(mut self, keyboard_mvmt_sensitivity: f64) -> Self {
+        self.controller.keyboard_mvmt_sensitivity = keyboard_mvmt_sensitivity;
+        self
+    }
 }
 
 /// A camera controlled with the mouse in the same way as Unreal Engine's viewport controller.
-#[derive(Clone, Component, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Component, Copy, Debug, Deserialize, FromReflect, Reflect, Serialize)]
+#[reflect(Component)]
+#[serde(default)]
 pub struct UnrealCameraController {
     /// Whether to process input or ignore it
     pub enabled: bool,
@@ -71,12 +210,22 @@ pub struct UnrealCameraController {
     /// How many radians per frame for each rotation axis (yaw, pitch) when rotating with the mouse
     pub rotate_sensitivity: DVec2,
 
+    /// Filter applied to cursor deltas before they rotate the camera
+    pub mouse_filter: MouseFilter,
+
+    /// Response curve for mouse rotation
+    pub rotate_response_curve: ResponseCurve,
+
     /// How many units per frame for each direction when translating using Middle or L+R panning
     pub mouse_translate_sensitivity: DVec2,
 
     /// How many units per frame when translating using scroll wheel
     pub wheel_translate_sensitivity: f64,
 
+    /// Pan with the cursor at the edges of the window. The top and bottom edges move forward and back, except in the
+    /// orthographic viewports.
+    pub edge_pan: EdgePan,
+
     /// How many units per frame when translating using W/S/Q/E
     /// Updated with scroll wheel while dragging with any mouse button
     pub keyboard_mvmt_sensitivity: f64,
@@ -84,8 +233,74 @@ pub struct UnrealCameraController {
     /// Wheel sensitivity for modulating keyboard movement speed
     pub keyboard_mvmt_wheel_sensitivity: f64,
 
+    /// Normalizes mouse wheel and touchpad scrolling
+    pub scroll_sensitivity: ScrollSensitivity,
+
+    /// What the mouse wheel does while no mouse button is held. `WheelAction::Zoom` moves along the view axis, and
+    /// `WheelAction::Speed` changes the fly speed. Scrolling while dragging always changes the fly speed.
+    pub wheel_action: WheelAction,
+
+    /// How many units to move forward per unit of `TouchpadGesture::Magnify`
+    pub touchpad_translate_sensitivity: f64,
+
+    /// How much to turn per radian of `TouchpadGesture::Rotate`
+    pub touchpad_rotate_sensitivity: f64,
+
+    /// Scale translation speeds with the distance to the target
+    pub adaptive_sensitivity: bool,
+
+    /// Translation speeds scale with the distance to the target raised to this power
+    pub adaptive_sensitivity_exponent: f64,
+
+    /// Lower clamp on the adaptive scale
+    pub adaptive_sensitivity_min_scale: f64,
+
+    /// Upper clamp on the adaptive scale
+    pub adaptive_sensitivity_max_scale: f64,
+
+    /// Scale translation speeds with the distance to the geometry in the center of the view, taken from the
+    /// `CameraRayHits` component, instead of the distance to the target. Uses the adaptive exponent and clamps.
+    pub depth_aware_speed: bool,
+
+    /// Double click the left mouse button to focus on the geometry under the cursor, taken from the `CameraRayHits`
+    /// component
+    pub double_click_focus: bool,
+
+    /// Snap yaw and pitch to multiples of this many degrees while `angle_snap_key` is held
+    pub angle_snap_degrees: Option<f64>,
+
+    /// Key to hold for angle snapping
+    pub angle_snap_key: KeyCode,
+
+    /// Walk instead of fly: gravity pulls the eye down to `eye_height` above the ground given by the camera's
+    /// `GroundHeight` component. Looking and moving work the same as flying.
+    pub walk_mode: bool,
+
+    /// Height of the eye above the ground in walk mode
+    pub eye_height: f64,
+
+    /// How many units per frame the falling speed increases by each frame in walk mode
+    pub gravity: f64,
+
+    /// Outside of walk mode, move the eye up and down with the ground under it, taken from the `GroundHeight`
+    /// component, so it keeps a constant height above the terrain while flying over it
+    pub terrain_following: bool,
+
+    /// Which point stays put when the camera turns
+    pub focus_policy: FocusPolicy,
+
+    /// Distance from the eye to the target to keep. `None` keeps the distance the camera had at the start of each frame.
+    pub focus_distance: Option<f64>,
+
     /// The greater, the slower to follow input
     pub smoothing_weight: f64,
+
+    /// The current view. Switch it with the `SetUnrealViewport` event, which also orients the camera and swaps its
+    /// `Projection`.
+    pub viewport: UnrealViewport,
+
+    /// How much each unit of forward locomotion zooms in the orthographic viewports
+    pub orthographic_zoom_sensitivity: f64,
 }
 
 impl Default for UnrealCameraController {
@@ -93,31 +308,146 @@ impl Default for UnrealCameraController {
         Self {
             enabled: true,
             rotate_sensitivity: DVec2::splat(0.002),
+            mouse_filter: MouseFilter::None,
+            rotate_response_curve: ResponseCurve::Linear,
             mouse_translate_sensitivity: DVec2::splat(0.02),
             wheel_translate_sensitivity: 1.0,
+            edge_pan: EdgePan::default(),
             keyboard_mvmt_sensitivity: 0.1,
             keyboard_mvmt_wheel_sensitivity: 0.1,
+            scroll_sensitivity: ScrollSensitivity::default(),
+            wheel_action: WheelAction::Zoom,
+            touchpad_translate_sensitivity: 10.0,
+            touchpad_rotate_sensitivity: 1.0,
+            adaptive_sensitivity: false,
+            adaptive_sensitivity_exponent: 1.0,
+            adaptive_sensitivity_min_scale: 0.01,
+            adaptive_sensitivity_max_scale: 1000.0,
+            depth_aware_speed: false,
+            double_click_focus: false,
+            angle_snap_degrees: None,
+            angle_snap_key: KeyCode::LShift,
+            walk_mode: false,
+            eye_height: 1.7,
+            gravity: 0.01,
+            terrain_following: false,
             smoothing_weight: 0.7,
+            focus_policy: FocusPolicy::PreserveRadius,
+            focus_distance: None,
+            viewport: UnrealViewport::Perspective,
+            orthographic_zoom_sensitivity: 0.1,
         }
     }
 }
 
+impl UnrealCameraController {
+    /// Units per frame when moving with W/S/Q/E/A/D, which scrolling while dragging changes. See `FlySpeedChanged`.
+    pub fn fly_speed(&self) -> f64 {
+        self.keyboard_mvmt_sensitivity
+    }
+}
+
+/// The view of an `UnrealCameraController`, like the viewport types of Unreal Engine's editor. The orthographic views
+/// look along a fixed axis and can only pan and zoom.
+#[derive(Clone, Copy, Debug, Default, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub enum UnrealViewport {
+    #[default]
+    Perspective,
+    /// Orthographic, looking down the up axis, with -Z at the top of the screen
+    Top,
+    /// Orthographic, looking along -Z
+    Front,
+    /// Orthographic, looking along -X
+    Side,
+}
+
+impl UnrealViewport {
+    pub fn is_orthographic(self) -> bool {
+        self != Self::Perspective
+    }
+
+    /// The view looking at `target` from `radius` away, in a world with the given `up` axis. `None` for `Perspective`,
+    /// which has no fixed direction.
+    pub fn orthographic_view(self, target: DVec3, radius: f64, up: DVec3) -> Option<LookTransform> {
+        let world_rotation = up_axis_rotation(up);
+        let (offset, camera_up) = match self {
+            Self::Perspective => return None,
+            Self::Top => (DVec3::Y, -DVec3::Z),
+            Self::Front => (DVec3::Z, DVec3::Y),
+            Self::Side => (DVec3::X, DVec3::Y),
+        };
+        Some(
+            LookTransform::new(target + radius * (world_rotation * offset), target)
+                .with_up(world_rotation * camera_up),
+        )
+    }
+}
+
+/// An orthographic projection showing the same height around the target as a perspective projection with `fov` does
+/// from `radius` away.
+pub fn orthographic_projection(radius: f64, fov: f32) -> OrthographicProjection {
+    OrthographicProjection {
+        scale: (radius * (fov as f64 / 2.0).tan()) as f32,
+        scaling_mode: ScalingMode::FixedVertical(2.0),
+        ..Default::default()
+    }
+}
+
+/// Switches the viewport of an unreal camera. Leaving `UnrealViewport::Perspective` remembers the perspective view and
+/// projection, and switching back restores them.
+#[derive(Clone, Copy, Debug)]
+pub struct SetUnrealViewport {
+    pub camera: Entity,
+    pub viewport: UnrealViewport,
+}
+
+/// Sent when scrolling while dragging changes the fly speed of the enabled unreal camera, with the new
+/// `UnrealCameraController::fly_speed`, for showing a speed indicator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlySpeedChanged(pub f64);
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum ControlEvent {
     Locomotion(DVec2),
     Rotate(DVec2),
     TranslateEye(DVec2),
+    /// Snap the rotation to multiples of `angle_snap_degrees` this frame. Sent every frame while the snap key is held.
+    SnapRotation,
+    /// Move the target to the cursor hit in the `CameraRayHits` component, if there is one, keeping the view direction and
+    /// distance.
+    FocusUnderCursor,
+    /// Divide the field of view by this factor.
+    ZoomFov(f64),
 }
 
 define_on_controller_enabled_changed!(UnrealCameraController);
 
+impl EnabledController for UnrealCameraController {
+    fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+}
+
 pub fn default_input_map(
     mut events: EventWriter<ControlEvent>,
+    mut speed_events: EventWriter<FlySpeedChanged>,
     mut mouse_wheel_reader: EventReader<MouseWheel>,
     mut mouse_motion_events: EventReader<MouseMotion>,
+    mut gesture_events: EventReader<TouchpadGesture>,
+    motion_suppression: Res<MouseMotionSuppression>,
     keyboard: Res<Input<KeyCode>>,
     mouse_buttons: Res<Input<MouseButton>>,
     mut controllers: Query<&mut UnrealCameraController>,
+    windows: Option<Res<Windows>>,
+    time: Res<Time>,
+    input_state: Res<CameraInputState>,
+    mut filter_state: Local<MouseFilterState>,
+    mut double_click: Local<DoubleClickDetector>,
 ) {
+    if input_state.is_blocked() {
+        return;
+    }
+
     // Can only control one camera at a time.
     let mut controller = if let Some(controller) = controllers.iter_mut().find(|c| c.enabled) {
         controller
@@ -126,13 +456,35 @@ pub fn default_input_map(
     };
     let UnrealCameraController {
         rotate_sensitivity: mouse_rotate_sensitivity,
+        mouse_filter,
+        rotate_response_curve,
+        double_click_focus,
+        angle_snap_degrees,
+        angle_snap_key,
         mouse_translate_sensitivity,
         wheel_translate_sensitivity,
-        mut keyboard_mvmt_sensitivity,
+        edge_pan,
+        keyboard_mvmt_sensitivity,
         keyboard_mvmt_wheel_sensitivity,
+        scroll_sensitivity,
+        wheel_action,
+        touchpad_translate_sensitivity,
+        touchpad_rotate_sensitivity,
         ..
     } = *controller;
 
+    let double_clicked = double_click.update(
+        mouse_buttons.just_pressed(MouseButton::Left),
+        time.elapsed_seconds_f64(),
+    );
+    if double_click_focus && double_clicked {
+        events.send(ControlEvent::FocusUnderCursor);
+    }
+
+    if angle_snap_degrees.is_some() && keyboard.pressed(angle_snap_key) {
+        events.send(ControlEvent::SnapRotation);
+    }
+
     let left_pressed = mouse_buttons.pressed(MouseButton::Left);
     let right_pressed = mouse_buttons.pressed(MouseButton::Right);
     let middle_pressed = mouse_buttons.pressed(MouseButton::Middle);
@@ -141,10 +493,19 @@ pub fn default_input_map(
     for event in mouse_motion_events.iter() {
         cursor_delta += DVec2::new(event.delta.x as f64, event.delta.y as f64);
     }
+    if motion_suppression.is_active() {
+        cursor_delta = DVec2::ZERO;
+    }
+
+    let rotate_delta = rotate_response_curve.apply(
+        filter_state.filter(mouse_filter, cursor_delta, time.delta_seconds_f64()),
+        MOUSE_CURVE_REFERENCE,
+    );
 
     let mut wheel_delta = 0.0f64;
     for event in mouse_wheel_reader.iter() {
-        wheel_delta += event.x as f64 + event.y as f64;
+        let lines = scroll_sensitivity.lines(event);
+        wheel_delta += lines.x + lines.y;
     }
 
     let mut panning_dir = DVec2::ZERO;
@@ -183,21 +544,60 @@ pub fn default_input_map(
     let mut panning = DVec2::ZERO;
     let mut locomotion = DVec2::ZERO;
 
+    let dragging = left_pressed || middle_pressed || right_pressed;
     // If any of the mouse button are pressed; read additional signals from the keyboard for panning
     // and locomotion along camera view axis
-    if left_pressed || middle_pressed || right_pressed {
+    if dragging {
         panning += keyboard_mvmt_sensitivity * panning_dir;
 
         if translation_dir.y != 0.0 {
             locomotion.y += keyboard_mvmt_sensitivity * translation_dir.y;
         }
+    }
+
+    if wheel_delta != 0.0 {
+        match wheel_action {
+            _ if dragging => (),
+            // Otherwise, if any scrolling is happening, do locomotion along camera view axis
+            WheelAction::Zoom => locomotion.y += wheel_translate_sensitivity * wheel_delta,
+            WheelAction::Fov => events.send(ControlEvent::ZoomFov(wheel_factor(wheel_delta))),
+            WheelAction::Speed | WheelAction::None => (),
+        }
+        if dragging || wheel_action == WheelAction::Speed {
+            let speed = (keyboard_mvmt_sensitivity + keyboard_mvmt_wheel_sensitivity * wheel_delta)
+                .max(0.01);
+            if speed != keyboard_mvmt_sensitivity {
+                controller.keyboard_mvmt_sensitivity = speed;
+                speed_events.send(FlySpeedChanged(speed));
+            }
+        }
+    }
+
+    let edge = edge_pan.speed
+        * time.delta_seconds_f64()
+        * edge_pan.window_direction(windows.as_deref());
+    panning.x += edge.x;
+    if controller.viewport.is_orthographic() {
+        panning.y += edge.y;
+    } else {
+        locomotion.y += edge.y;
+    }
 
-        keyboard_mvmt_sensitivity += keyboard_mvmt_wheel_sensitivity * wheel_delta;
-        controller.keyboard_mvmt_sensitivity = keyboard_mvmt_sensitivity.max(0.01);
+    // Pinch to move along the view axis, and rotate two fingers to turn
+    let mut gesture_rotation = 0.0;
+    for event in gesture_events.iter() {
+        match *event {
+            TouchpadGesture::Magnify(magnify) => {
+                locomotion.y += touchpad_translate_sensitivity * magnify;
+            }
+            TouchpadGesture::Rotate(angle) => gesture_rotation += angle,
+        }
     }
-    // Otherwise, if any scrolling is happening, do locomotion along camera view axis
-    else if wheel_delta != 0.0 {
-        locomotion.y += wheel_translate_sensitivity * wheel_delta;
+    if gesture_rotation != 0.0 {
+        events.send(ControlEvent::Rotate(DVec2::new(
+            touchpad_rotate_sensitivity * gesture_rotation,
+            0.0,
+        )));
     }
 
     // You can also pan using the mouse only; add those signals to existing panning
@@ -213,7 +613,7 @@ pub fn default_input_map(
 
     if !left_pressed && !middle_pressed && right_pressed {
         events.send(ControlEvent::Rotate(
-            mouse_rotate_sensitivity * cursor_delta,
+            mouse_rotate_sensitivity * rotate_delta,
         ));
     }
 
@@ -226,47 +626,393 @@ pub fn default_input_map(
     }
 }
 
+/// Input map for controlling the camera with only the keyboard. See `KeyboardNavigation`.
+pub fn keyboard_input_map(
+    mut events: EventWriter<ControlEvent>,
+    keyboard: Res<Input<KeyCode>>,
+    settings: Res<KeyboardNavigation>,
+    controllers: Query<&UnrealCameraController>,
+    time: Res<Time>,
+    input_state: Res<CameraInputState>,
+    mut repeat: Local<KeyRepeat>,
+) {
+    if input_state.is_blocked() {
+        return;
+    }
+
+    // Can only control one camera at a time.
+    if !controllers.iter().any(|c| c.enabled) {
+        return;
+    }
+
+    let steps = repeat.read(&settings, &keyboard, time.elapsed_seconds_f64());
+    if steps.rotate != DVec2::ZERO {
+        events.send(ControlEvent::Rotate(steps.rotate));
+    }
+    if steps.pan != DVec2::ZERO {
+        events.send(ControlEvent::TranslateEye(steps.pan));
+    }
+    // Zooming moves forward and back.
+    if steps.zoom != 0 {
+        events.send(ControlEvent::Locomotion(DVec2::new(
+            0.0,
+            steps.zoom as f64 * settings.translate_step,
+        )));
+    }
+}
+
+fn viewport_system(
+    mut events: EventReader<SetUnrealViewport>,
+    mut cameras: Query<(
+        &mut UnrealCameraController,
+        &mut LookTransform,
+        Option<&mut Projection>,
+    )>,
+    mut perspective_views: Local<HashMap<Entity, (LookTransform, Option<Projection>)>>,
+) {
+    for event in events.iter() {
+        let (mut controller, mut transform, mut projection) =
+            if let Ok(camera) = cameras.get_mut(event.camera) {
+                camera
+            } else {
+                continue;
+            };
+        if controller.viewport == event.viewport {
+            continue;
+        }
+        if controller.viewport == UnrealViewport::Perspective {
+            perspective_views.insert(event.camera, (*transform, projection.as_deref().cloned()));
+        }
+        controller.viewport = event.viewport;
+
+        let (perspective_transform, perspective_projection) = perspective_views
+            .get(&event.camera)
+            .cloned()
+            .unwrap_or((*transform, None));
+        let radius = perspective_transform.radius();
+        match event
+            .viewport
+            .orthographic_view(transform.target, radius, perspective_transform.up)
+        {
+            Some(view) => {
+                *transform = view;
+                let fov = match perspective_projection {
+                    Some(Projection::Perspective(perspective)) => perspective.fov,
+                    _ => PerspectiveProjection::default().fov,
+                };
+                if let Some(projection) = projection.as_deref_mut() {
+                    *projection = Projection::Orthographic(orthographic_projection(radius, fov));
+                }
+            }
+            None => {
+                *transform = perspective_transform;
+                if let (Some(projection), Some(perspective_projection)) =
+                    (projection.as_deref_mut(), perspective_projection)
+                {
+                    *projection = perspective_projection;
+                }
+            }
+        }
+    }
+}
+
 pub fn control_system(
     mut events: EventReader<ControlEvent>,
-    mut cameras: Query<(&UnrealCameraController, &mut LookTransform)>,
+    mut errors: EventWriter<CameraError>,
+    fallback: Option<Res<FallbackLookDirection>>,
+    mut cameras: Query<(
+        Entity,
+        &UnrealCameraController,
+        &mut LookTransform,
+        Option<&CameraRayHits>,
+        Option<&GroundHeight>,
+        Option<&mut Projection>,
+        Option<&Smoother>,
+    )>,
+    mut fall_speed: Local<f64>,
+    mut angle_snap: Local<AngleSnap>,
 ) {
     // Can only control one camera at a time.
-    let mut transform = if let Some((_, transform)) = cameras.iter_mut().find(|c| c.0.enabled) {
-        transform
-    } else {
+    let (camera, controller, mut transform, ray_hits, ground, mut projection, smoother) =
+        if let Some(camera) = cameras.iter_mut().find(|c| c.1.enabled) {
+            camera
+        } else {
+            *fall_speed = 0.0;
+            return;
+        };
+
+    if controller.viewport.is_orthographic() {
+        *fall_speed = 0.0;
+        orthographic_control(&mut events, controller, &mut transform, ray_hits, projection);
         return;
-    };
-
-    let look_vector;
-    match transform.look_direction() {
-        Some(safe_look_vector) => look_vector = safe_look_vector,
-        None => return,
     }
-    let mut look_angles = LookAngles::from_vector(look_vector);
 
+    let look_vector = look_direction_or_recover(
+        camera,
+        &mut transform,
+        smoother,
+        fallback.as_deref(),
+        &mut errors,
+    );
+    let up = transform.up;
+    let mut look_angles = LookAngles::from_vector_with_up(look_vector, up);
+    let depth = ray_hits
+        .and_then(|hits| hits.center)
+        .filter(|_| controller.depth_aware_speed)
+        .map(|hit| hit.distance(transform.eye));
+    let scale_distance = depth.or_else(|| {
+        controller
+            .adaptive_sensitivity
+            .then(|| transform.radius())
+    });
+    let translate_scale = if let Some(distance) = scale_distance {
+        adaptive_sensitivity_scale(
+            distance,
+            controller.adaptive_sensitivity_exponent,
+            controller.adaptive_sensitivity_min_scale,
+            controller.adaptive_sensitivity_max_scale,
+        )
+    } else {
+        1.0
+    };
+
+    let start = *transform;
+    let start_eye = transform.eye;
+    let mut rotate_delta = DVec2::ZERO;
+    let mut snap = false;
     for event in events.iter() {
         match event {
             ControlEvent::Locomotion(delta) => {
                 // Translates forward/backward and rotates about the Y axis.
                 look_angles.add_yaw(-delta.x);
-                transform.eye += delta.y * look_vector;
+                transform.eye += translate_scale * delta.y * look_vector;
             }
             ControlEvent::Rotate(delta) => {
                 // Rotates with pitch and yaw.
-                look_angles.add_yaw(-delta.x);
-                look_angles.add_pitch(-delta.y);
+                rotate_delta += *delta;
+            }
+            ControlEvent::SnapRotation => {
+                snap = true;
             }
             ControlEvent::TranslateEye(delta) => {
-                let yaw_rot = DQuat::from_axis_angle(DVec3::Y, look_angles.get_yaw());
+                let yaw_rot =
+                    up_axis_rotation(up) * DQuat::from_axis_angle(DVec3::Y, look_angles.get_yaw());
                 let rot_x = yaw_rot * DVec3::X;
 
                 // Translates up/down (Y) and left/right (X).
-                transform.eye -= delta.x * rot_x - DVec3::new(0.0, delta.y, 0.0);
+                transform.eye -= translate_scale * (delta.x * rot_x - delta.y * up);
+            }
+            ControlEvent::FocusUnderCursor => {
+                // The target is placed back in front of the eye below, so move the eye to keep the view direction.
+                if let Some(hit) = ray_hits.and_then(|hits| hits.cursor) {
+                    transform.eye = hit - transform.radius() * look_angles.unit_vector_with_up(up);
+                }
+            }
+            ControlEvent::ZoomFov(factor) => {
+                zoom_fov(projection.as_deref_mut(), *factor);
             }
         }
     }
 
+    let snap_increment = controller
+        .angle_snap_degrees
+        .filter(|_| snap)
+        .map(f64::to_radians);
+    angle_snap.rotate(
+        &mut look_angles,
+        -rotate_delta.x,
+        -rotate_delta.y,
+        snap_increment,
+    );
+
     look_angles.assert_not_looking_up();
 
-    transform.target = transform.eye + transform.radius() * look_angles.unit_vector();
+    controller.focus_policy.apply(
+        &mut transform,
+        &start,
+        look_angles.unit_vector_with_up(up),
+        controller.focus_distance,
+    );
+
+    // The height adjustments below move the target with the eye, so the focus policy can't undo them.
+    let ground_height = ground
+        .filter(|_| controller.walk_mode)
+        .and_then(|ground| ground.height_below(transform.eye));
+    if let Some(ground_height) = ground_height {
+        // Fall toward the eye height, but step up onto higher ground immediately.
+        let eye_height = transform.eye.dot(up);
+        let walk_height = ground_height + controller.eye_height;
+        let new_height = if eye_height > walk_height {
+            *fall_speed += controller.gravity;
+            (eye_height - *fall_speed).max(walk_height)
+        } else {
+            walk_height
+        };
+        let lift = (new_height - eye_height) * up;
+        transform.eye += lift;
+        transform.target += lift;
+        if new_height == walk_height {
+            *fall_speed = 0.0;
+        }
+    } else {
+        *fall_speed = 0.0;
+    }
+
+    let terrain_ground = ground
+        .filter(|_| controller.terrain_following && !controller.walk_mode)
+        .filter(|_| transform.eye != start_eye);
+    if let Some(ground) = terrain_ground {
+        let heights = (
+            ground.height_below(start_eye),
+            ground.height_below(transform.eye),
+        );
+        if let (Some(old_height), Some(new_height)) = heights {
+            let lift = (new_height - old_height) * up;
+            transform.eye += lift;
+            transform.target += lift;
+        }
+    }
+}
+
+/// Pans and zooms in the orthographic viewports. Rotation is ignored.
+fn orthographic_control(
+    events: &mut EventReader<ControlEvent>,
+    controller: &UnrealCameraController,
+    transform: &mut LookTransform,
+    ray_hits: Option<&CameraRayHits>,
+    mut projection: Option<Mut<Projection>>,
+) {
+    let mut orthographic = match projection.as_deref_mut() {
+        Some(Projection::Orthographic(orthographic)) => Some(orthographic),
+        _ => None,
+    };
+    // Pan the same distance on screen at any zoom.
+    let translate_scale = orthographic.as_ref().map_or(1.0, |o| o.scale as f64);
+    let rotation = transform.rotation();
+    let right = rotation * DVec3::X;
+    let screen_up = rotation * DVec3::Y;
+    let look_vector = rotation * -DVec3::Z;
+
+    let mut offset = DVec3::ZERO;
+    for event in events.iter() {
+        match event {
+            ControlEvent::Locomotion(delta) => {
+                if let Some(orthographic) = orthographic.as_mut() {
+                    orthographic.scale *=
+                        (-controller.orthographic_zoom_sensitivity * delta.y).exp() as f32;
+                }
+            }
+            ControlEvent::TranslateEye(delta) => {
+                offset -= translate_scale * (delta.x * right - delta.y * screen_up);
+            }
+            ControlEvent::FocusUnderCursor => {
+                if let Some(hit) = ray_hits.and_then(|hits| hits.cursor) {
+                    let to_hit = hit - transform.target;
+                    offset += to_hit - to_hit.dot(look_vector) * look_vector;
+                }
+            }
+            ControlEvent::Rotate(_) | ControlEvent::SnapRotation | ControlEvent::ZoomFov(_) => (),
+        }
+    }
+    transform.eye += offset;
+    transform.target += offset;
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    use bevy::{
+        ecs::event::Events,
+        input::{mouse::MouseScrollUnit, InputPlugin},
+    };
+
+    #[test]
+    fn test_orthographic_viewport_switch() {
+        let mut app = headless_app();
+        app.add_plugin(UnrealCameraPlugin::new(true));
+        let camera = app
+            .world
+            .spawn((
+                UnrealCameraBundle::new(
+                    UnrealCameraController::default(),
+                    -10.0 * DVec3::Z,
+                    DVec3::ZERO,
+                ),
+                Projection::Perspective(Default::default()),
+            ))
+            .id();
+        step(&mut app, 1);
+        let perspective_view = look_transform(&app, camera);
+
+        let viewport = UnrealViewport::Top;
+        send_control_event(&mut app, SetUnrealViewport { camera, viewport });
+        step(&mut app, 1);
+
+        let transform = look_transform(&app, camera);
+        assert!(transform.eye.abs_diff_eq(10.0 * DVec3::Y, 1e-9));
+        assert_eq!(transform.target, DVec3::ZERO);
+        assert_eq!(transform.up, -DVec3::Z);
+        let controller = app.world.get::<UnrealCameraController>(camera).unwrap();
+        assert_eq!(controller.viewport, UnrealViewport::Top);
+        let projection = app.world.get::<Projection>(camera).unwrap();
+        assert!(matches!(projection, Projection::Orthographic(_)));
+
+        // Switching back restores the perspective view and projection.
+        let viewport = UnrealViewport::Perspective;
+        send_control_event(&mut app, SetUnrealViewport { camera, viewport });
+        step(&mut app, 1);
+
+        assert_eq!(look_transform(&app, camera), perspective_view);
+        let projection = app.world.get::<Projection>(camera).unwrap();
+        assert!(matches!(projection, Projection::Perspective(_)));
+    }
+
+    #[test]
+    fn test_fly_speed_changed() {
+        let mut app = headless_app();
+        app.add_plugin(InputPlugin)
+            .add_plugin(UnrealCameraPlugin::new(false));
+        let controller = UnrealCameraController {
+            wheel_action: WheelAction::Speed,
+            ..Default::default()
+        };
+        let camera = app
+            .world
+            .spawn(UnrealCameraBundle::new(
+                controller,
+                -10.0 * DVec3::Z,
+                DVec3::ZERO,
+            ))
+            .id();
+        step(&mut app, 1);
+
+        send_control_event(
+            &mut app,
+            MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: 1.0,
+            },
+        );
+        step(&mut app, 1);
+
+        let speed =
+            controller.keyboard_mvmt_sensitivity + controller.keyboard_mvmt_wheel_sensitivity;
+        let controller = app.world.get::<UnrealCameraController>(camera).unwrap();
+        assert_eq!(controller.fly_speed(), speed);
+        let events = app.world.resource::<Events<FlySpeedChanged>>();
+        let changes: Vec<_> = events.get_reader().iter(events).copied().collect();
+        assert_eq!(changes, [FlySpeedChanged(speed)]);
+        // The speed wheel action doesn't move the camera.
+        assert_eq!(look_transform(&app, camera).eye, -10.0 * DVec3::Z);
+    }
 }
@@ -0,0 +1,103 @@
+use crate::{LookTransform, LookTransformSystem, SmoothedLookTransform};
+
+use bevy::{app::prelude::*, ecs::prelude::*, transform::components::GlobalTransform};
+
+/// Keeps the `FocalDistance` of every camera with a `DepthOfFieldFocus` on its target, or on a tracked entity.
+pub struct DepthOfFieldFocusPlugin;
+
+impl Plugin for DepthOfFieldFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(depth_of_field_focus_system.after(LookTransformSystem::Smooth));
+    }
+}
+
+/// Focuses depth of field on the target of this camera's rig, at `LookTransform::radius`, or on `entity`.
+///
+/// The version of Bevy this crate targets has no depth of field pass, so this only writes the `FocalDistance`
+/// component, for a post-process pass of your own to read.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct DepthOfFieldFocus {
+    /// Focus on this entity instead of the target
+    pub entity: Option<Entity>,
+    /// How much of the previous focal distance is kept each frame, like `Smoother`'s lag weight. `0.0` follows the
+    /// focus immediately.
+    pub lag_weight: f64,
+}
+
+impl Default for DepthOfFieldFocus {
+    fn default() -> Self {
+        Self {
+            entity: None,
+            lag_weight: 0.9,
+        }
+    }
+}
+
+/// The smoothed distance from the eye to the focus of a camera with a `DepthOfFieldFocus`.
+///
+/// Nothing in Bevy reads this. It's a hook for your own depth of field pass, e.g. copied into its uniforms each frame.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq)]
+pub struct FocalDistance(pub f64);
+
+/// Moves `previous` toward `target`, keeping `lag_weight` of the way.
+pub(crate) fn smooth_focal_distance(previous: f64, target: f64, lag_weight: f64) -> f64 {
+    let lag_weight = lag_weight.clamp(0.0, 1.0);
+    previous * lag_weight + target * (1.0 - lag_weight)
+}
+
+fn depth_of_field_focus_system(
+    mut commands: Commands,
+    mut cameras: Query<(
+        Entity,
+        &DepthOfFieldFocus,
+        &LookTransform,
+        Option<&SmoothedLookTransform>,
+        Option<&mut FocalDistance>,
+    )>,
+    focus_entities: Query<&GlobalTransform>,
+) {
+    for (camera, focus, transform, smoothed, focal_distance) in cameras.iter_mut() {
+        // Focus on what's on screen, rather than where the camera is heading.
+        let transform = smoothed.map_or(transform, |s| &s.0);
+        let target = match focus.entity.map(|e| focus_entities.get(e)) {
+            Some(Ok(global)) => transform.eye.distance(global.translation()),
+            // The entity is gone, so fall back to the target.
+            Some(Err(_)) | None => transform.radius(),
+        };
+
+        if let Some(mut focal_distance) = focal_distance {
+            let new_distance = smooth_focal_distance(focal_distance.0, target, focus.lag_weight);
+            if focal_distance.0 != new_distance {
+                focal_distance.0 = new_distance;
+            }
+        } else {
+            commands.entity(camera).insert(FocalDistance(target));
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_smooth_focal_distance() {
+        assert_relative_eq!(smooth_focal_distance(10.0, 20.0, 0.0), 20.0);
+        assert_relative_eq!(smooth_focal_distance(10.0, 20.0, 0.75), 12.5);
+
+        let mut distance = 10.0;
+        for _ in 0..200 {
+            distance = smooth_focal_distance(distance, 2.0, 0.9);
+        }
+        assert_relative_eq!(distance, 2.0, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{LookTransform, LookTransformSystem, Smoother};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{prelude::*, system::EntityCommands},
+    render::camera::Projection,
+    time::Time,
+};
+
+pub struct DollyZoomPlugin;
+
+impl Plugin for DollyZoomPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(dolly_zoom_system.before(LookTransformSystem::Smooth));
+    }
+}
+
+/// An in-progress dolly zoom (the "vertigo" effect). The field of view is eased toward `target_fov` over `duration` seconds
+/// while the eye moves along the view axis, so anything at the `LookTransform::target` distance keeps its size on screen.
+///
+/// The component removes itself once finished. Don't combine it with a `FovZoom` on the same camera, since both write the
+/// field of view.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct DollyZoom {
+    pub target_fov: f32,
+    pub duration: f64,
+    elapsed: f64,
+    /// The field of view and radius when the zoom started.
+    start: Option<(f32, f64)>,
+}
+
+impl DollyZoom {
+    pub fn new(target_fov: f32, duration: f64) -> Self {
+        Self {
+            target_fov,
+            duration,
+            elapsed: 0.0,
+            start: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// Starts dolly zooms from `Commands`.
+pub trait DollyZoomCommands {
+    /// Dolly zoom the camera to `fov` over `duration` seconds.
+    fn dolly_zoom_to(&mut self, fov: f32, duration: f64) -> &mut Self;
+}
+
+impl<'w, 's, 'a> DollyZoomCommands for EntityCommands<'w, 's, 'a> {
+    fn dolly_zoom_to(&mut self, fov: f32, duration: f64) -> &mut Self {
+        self.insert(DollyZoom::new(fov, duration))
+    }
+}
+
+/// Radius that keeps the frustum width at `start_radius` unchanged when the field of view goes from `start_fov` to `fov`.
+pub fn dolly_zoom_radius(start_fov: f32, start_radius: f64, fov: f32) -> f64 {
+    start_radius * (start_fov as f64 / 2.0).tan() / (fov as f64 / 2.0).tan()
+}
+
+fn dolly_zoom_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut cameras: Query<(
+        Entity,
+        &mut DollyZoom,
+        &mut LookTransform,
+        &mut Projection,
+        Option<&mut Smoother>,
+    )>,
+) {
+    for (entity, mut zoom, mut transform, mut projection, smoother) in cameras.iter_mut() {
+        let perspective = if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective
+        } else {
+            commands.entity(entity).remove::<DollyZoom>();
+            continue;
+        };
+        let look_direction = if let Some(dir) = transform.look_direction() {
+            dir
+        } else {
+            commands.entity(entity).remove::<DollyZoom>();
+            continue;
+        };
+
+        let (start_fov, start_radius) =
+            *zoom.start.get_or_insert((perspective.fov, transform.radius()));
+        zoom.elapsed += time.delta_seconds_f64();
+
+        let t = if zoom.duration > 0.0 {
+            (zoom.elapsed / zoom.duration).min(1.0)
+        } else {
+            1.0
+        };
+        // Smoothstep easing.
+        let s = (t * t * (3.0 - 2.0 * t)) as f32;
+        let fov = start_fov + (zoom.target_fov - start_fov) * s;
+
+        perspective.fov = fov;
+        let radius = dolly_zoom_radius(start_fov, start_radius, fov);
+        transform.eye = transform.target - radius * look_direction;
+
+        // The eye and field of view have to move in lockstep, so smoothing the eye would break the effect.
+        if let Some(mut smoother) = smoother {
+            smoother.reset();
+        }
+
+        if zoom.is_finished() {
+            commands.entity(entity).remove::<DollyZoom>();
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_dolly_zoom_radius_keeps_frustum_width() {
+        let start_fov = std::f32::consts::PI / 4.0;
+        let fov = std::f32::consts::PI / 8.0;
+        let radius = dolly_zoom_radius(start_fov, 10.0, fov);
+
+        let width = |fov: f32, radius: f64| 2.0 * radius * (fov as f64 / 2.0).tan();
+        assert_relative_eq!(width(start_fov, 10.0), width(fov, radius), epsilon = 1e-6);
+        assert!(radius > 10.0);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    GroundClamp, LookTransform, LookTransformLocks, LookTransformSystem, QuadViewportRig, Smoother,
+    SmoothingSettings, TransformConversion, TransformHistory,
+};
+
+use bevy::{
+    app::prelude::*, ecs::prelude::*, hierarchy::Parent, math::prelude::*,
+    transform::components::Transform,
+};
+
+/// Keeps the `FloatingOriginCamera` near the origin of the scene, so that rendering, which happens in `f32`, stays
+/// precise in large worlds. Whenever the camera's smoothed eye gets farther than `FloatingOrigin::threshold` from the
+/// origin, the whole scene is shifted back in one step, after smoothing, so there is no visible hitch.
+pub struct FloatingOriginPlugin;
+
+impl Plugin for FloatingOriginPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FloatingOrigin>()
+            .add_event::<OriginShifted>()
+            .add_system(floating_origin_system.after(LookTransformSystem::Smooth));
+    }
+}
+
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct FloatingOrigin {
+    /// Distance from the origin at which the scene is shifted
+    pub threshold: f64,
+    /// Where the origin of the scene is in the unshifted world. Add this to a scene position to get its world position.
+    pub origin: DVec3,
+}
+
+impl Default for FloatingOrigin {
+    fn default() -> Self {
+        Self {
+            threshold: 10_000.0,
+            origin: DVec3::ZERO,
+        }
+    }
+}
+
+/// The camera that the `FloatingOriginPlugin` keeps near the origin.
+#[derive(Clone, Component, Copy, Debug, Default)]
+pub struct FloatingOriginCamera;
+
+/// Marks an entity to be moved when the origin shifts. Only entities without a parent are moved, since children move
+/// with their parents.
+///
+/// Root entities with a `LookTransform` are moved too, along with their `Smoother` state, `LookTransformLocks` and
+/// `GroundClamp`, and so is the focus of every `QuadViewportRig`. When `SmoothingSettings::conversion` is a
+/// `TransformConversion::OriginOffset`, `LookTransform`s are in world coordinates, so they stay put and the offset
+/// follows the origin instead. Every `TransformHistory` is moved either way.
+#[derive(Clone, Component, Copy, Debug, Default)]
+pub struct FloatingOriginShift;
+
+/// Sent when the scene is shifted, for moving anything else that stores positions, like a physics world.
+#[derive(Clone, Copy, Debug)]
+pub struct OriginShifted {
+    /// What was added to every shifted position
+    pub offset: DVec3,
+}
+
+#[allow(clippy::type_complexity)]
+fn floating_origin_system(
+    mut origin: ResMut<FloatingOrigin>,
+    mut events: EventWriter<OriginShifted>,
+    settings: Option<ResMut<SmoothingSettings>>,
+    mut shifted: Query<
+        (
+            &mut Transform,
+            Option<&mut LookTransform>,
+            Option<&mut Smoother>,
+            Option<&mut LookTransformLocks>,
+            Option<&mut GroundClamp>,
+            Option<&FloatingOriginCamera>,
+        ),
+        (
+            Or<(
+                With<FloatingOriginShift>,
+                With<LookTransform>,
+                With<FloatingOriginCamera>,
+            )>,
+            Without<Parent>,
+        ),
+    >,
+    mut rigs: Query<&mut QuadViewportRig>,
+    mut histories: Query<&mut TransformHistory>,
+) {
+    let eye = if let Some((transform, ..)) = shifted.iter().find(|(.., camera)| camera.is_some()) {
+        transform.translation
+    } else {
+        return;
+    };
+    if eye.length() < origin.threshold {
+        return;
+    }
+
+    // Shift everything at once, so the scene is consistent by the time it's rendered.
+    let offset = -eye;
+    origin.origin -= offset;
+
+    // World-space `LookTransform`s only need their offset moved along with the origin.
+    let mut shift_look_transforms = true;
+    if let Some(mut settings) = settings {
+        if let TransformConversion::OriginOffset(render_origin) = settings.conversion {
+            settings.conversion = TransformConversion::OriginOffset(render_origin - offset);
+            shift_look_transforms = false;
+        }
+    }
+
+    for (mut transform, look_transform, smoother, locks, clamp, _) in shifted.iter_mut() {
+        transform.translation += offset;
+        if !shift_look_transforms {
+            continue;
+        }
+        if let Some(mut look_transform) = look_transform {
+            look_transform.eye += offset;
+            look_transform.target += offset;
+        }
+        if let Some(mut smoother) = smoother {
+            smoother.translate(offset);
+        }
+        if let Some(mut locks) = locks {
+            locks.translate(offset);
+        }
+        if let Some(mut clamp) = clamp {
+            clamp.ground_point += offset;
+        }
+    }
+    if shift_look_transforms {
+        for mut rig in rigs.iter_mut() {
+            rig.focus += offset;
+        }
+    }
+    // Histories are recorded from `GlobalTransform`s, which shift whatever the conversion.
+    for mut history in histories.iter_mut() {
+        history.translate(offset);
+    }
+    events.send(OriginShifted { offset });
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::*, LookTransformBundle};
+
+    use bevy::{ecs::event::Events, hierarchy::BuildWorldChildren};
+
+    #[test]
+    fn test_origin_shift() {
+        let mut app = headless_app();
+        app.add_plugin(FloatingOriginPlugin)
+            .insert_resource(FloatingOrigin {
+                threshold: 100.0,
+                ..Default::default()
+            });
+        let eye = DVec3::new(150.0, 0.0, 0.0);
+        let camera = app
+            .world
+            .spawn((
+                LookTransformBundle {
+                    transform: LookTransform::new(eye, eye + DVec3::Z),
+                    smoother: Smoother::new(0.0),
+                },
+                Transform::default(),
+                GroundClamp {
+                    ground_point: eye - DVec3::Y,
+                    ..Default::default()
+                },
+                FloatingOriginCamera,
+            ))
+            .id();
+        let prop = app
+            .world
+            .spawn((Transform::from_xyz(160.0, 0.0, 0.0), FloatingOriginShift))
+            .id();
+        let child = app
+            .world
+            .spawn((Transform::from_xyz(1.0, 0.0, 0.0), FloatingOriginShift))
+            .id();
+        app.world.entity_mut(prop).push_children(&[child]);
+
+        step(&mut app, 1);
+
+        let offset = -eye;
+        let transform = look_transform(&app, camera);
+        assert_eq!(transform.eye, DVec3::ZERO);
+        assert_eq!(transform.target, DVec3::Z);
+        assert_eq!(scene_transform(&app, camera).translation, DVec3::ZERO);
+        let smoother = app.world.get::<Smoother>(camera).unwrap();
+        assert!(smoother.is_settled(&transform));
+        let clamp = app.world.get::<GroundClamp>(camera).unwrap();
+        assert_eq!(clamp.ground_point, -DVec3::Y);
+        assert_eq!(scene_transform(&app, prop).translation, 10.0 * DVec3::X);
+        // Children move with their parent.
+        assert_eq!(scene_transform(&app, child).translation, DVec3::X);
+        assert_eq!(app.world.resource::<FloatingOrigin>().origin, eye);
+        let events = app.world.resource::<Events<OriginShifted>>();
+        let shifts: Vec<_> = events.get_reader().iter(events).map(|e| e.offset).collect();
+        assert_eq!(shifts, [offset]);
+
+        // Back near the origin, nothing shifts again.
+        step(&mut app, 1);
+        assert_eq!(look_transform(&app, camera).eye, DVec3::ZERO);
+        assert_eq!(app.world.resource::<FloatingOrigin>().origin, eye);
+    }
+}
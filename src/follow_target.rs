@@ -0,0 +1,255 @@
+use crate::{controllers::CameraControlSystem, Easing, LookTransform, LookTransformSystem};
+
+use bevy::{
+    app::prelude::*, ecs::prelude::*, math::prelude::*, time::Time,
+    transform::components::GlobalTransform,
+};
+
+/// Moves every camera with a `FollowTarget` along with the entity it follows.
+pub struct FollowTargetPlugin;
+
+impl Plugin for FollowTargetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            follow_target_system
+                .after(CameraControlSystem::Control)
+                .before(LookTransformSystem::Locks),
+        );
+    }
+}
+
+/// Keeps the target of this camera's `LookTransform` on `entity`, moving the eye along with it, so any controller can
+/// still orbit or look around the followed entity.
+///
+/// Switching to another entity with `switch_to` hands the camera off over `transition_seconds`, eased by `easing`,
+/// rather than cutting, e.g. for a spectator mode cycling between players with the number keys. With an `arc_height`,
+/// the eye rises over the path between the entities instead of cutting straight through the geometry in between.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct FollowTarget {
+    pub entity: Entity,
+    /// Offset from the entity's origin to the point looked at, in world space
+    pub offset: DVec3,
+    pub transition_seconds: f64,
+    pub easing: Easing,
+    /// Highest rise of the eye over a hand-off, along `LookTransform::up`, as a fraction of the distance between the
+    /// entities. `0.0` moves in a straight line.
+    pub arc_height: f64,
+    hand_off: Option<HandOff>,
+    /// The point followed last frame
+    last_point: Option<DVec3>,
+    /// How far the eye is raised by the current hand-off
+    lift: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct HandOff {
+    from: DVec3,
+    /// The lift of the hand-off this one interrupted, eased out over this one
+    from_lift: f64,
+    elapsed: f64,
+}
+
+impl FollowTarget {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            offset: DVec3::ZERO,
+            transition_seconds: 1.0,
+            easing: Easing::EaseInOut,
+            arc_height: 0.0,
+            hand_off: None,
+            last_point: None,
+            lift: 0.0,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: DVec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_transition(mut self, seconds: f64, easing: Easing) -> Self {
+        self.transition_seconds = seconds;
+        self.easing = easing;
+        self
+    }
+
+    pub fn with_arc_height(mut self, arc_height: f64) -> Self {
+        self.arc_height = arc_height;
+        self
+    }
+
+    /// Hand the camera off to `entity`, starting from where it looks now, even in the middle of another hand-off.
+    pub fn switch_to(&mut self, entity: Entity) {
+        if entity == self.entity {
+            return;
+        }
+        self.entity = entity;
+        if let Some(from) = self.last_point {
+            self.hand_off = Some(HandOff {
+                from,
+                from_lift: self.lift,
+                elapsed: 0.0,
+            });
+        }
+    }
+
+    pub fn is_handing_off(&self) -> bool {
+        self.hand_off.is_some()
+    }
+
+    /// Advances by `dt` seconds toward `point`, where the followed entity is now. Returns the point to look at, and how
+    /// far to raise the eye above it.
+    pub fn advance(&mut self, point: DVec3, dt: f64) -> (DVec3, f64) {
+        let (look_at, lift) = match self.hand_off.as_mut() {
+            Some(hand_off) => {
+                hand_off.elapsed += dt;
+                let t = if self.transition_seconds > 0.0 {
+                    hand_off.elapsed / self.transition_seconds
+                } else {
+                    1.0
+                };
+                if t >= 1.0 {
+                    self.hand_off = None;
+                    (point, 0.0)
+                } else {
+                    let eased = self.easing.apply(t);
+                    // A parabola peaking halfway through.
+                    let arc = 4.0 * eased * (1.0 - eased);
+                    let lift = self.arc_height * hand_off.from.distance(point) * arc
+                        + hand_off.from_lift * (1.0 - eased);
+                    (hand_off.from.lerp(point, eased), lift)
+                }
+            }
+            None => (point, 0.0),
+        };
+        self.last_point = Some(look_at);
+        self.lift = lift;
+        (look_at, lift)
+    }
+}
+
+fn follow_target_system(
+    time: Res<Time>,
+    mut cameras: Query<(&mut FollowTarget, &mut LookTransform)>,
+    entities: Query<&GlobalTransform>,
+) {
+    let dt = time.delta_seconds_f64();
+    for (mut follow, mut transform) in cameras.iter_mut() {
+        let up = transform.up.normalize();
+        let point = if let Ok(global) = entities.get(follow.entity) {
+            global.translation() + follow.offset
+        } else {
+            // The entity was despawned, so stop where the camera is, without leaving the eye raised.
+            if follow.lift != 0.0 {
+                transform.eye -= follow.lift * up;
+                follow.lift = 0.0;
+            }
+            follow.hand_off = None;
+            continue;
+        };
+        let previous_lift = follow.lift;
+        let (look_at, lift) = follow.advance(point, dt);
+
+        let delta = look_at - transform.target;
+        let eye_delta = delta + (lift - previous_lift) * up;
+        if delta != DVec3::ZERO || eye_delta != DVec3::ZERO {
+            transform.target = look_at;
+            transform.eye += eye_delta;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_follow_target_hand_off() {
+        let mut follow = FollowTarget::new(Entity::from_raw(0))
+            .with_transition(2.0, Easing::Linear)
+            .with_arc_height(0.5);
+        assert_eq!(follow.advance(DVec3::ZERO, 0.1), (DVec3::ZERO, 0.0));
+
+        follow.switch_to(Entity::from_raw(1));
+        let target = DVec3::new(10.0, 0.0, 0.0);
+        let (look_at, lift) = follow.advance(target, 1.0);
+        assert!(look_at.abs_diff_eq(DVec3::new(5.0, 0.0, 0.0), 1e-9));
+        assert_relative_eq!(lift, 5.0);
+        assert!(follow.is_handing_off());
+
+        let (look_at, lift) = follow.advance(target, 1.0);
+        assert_eq!((look_at, lift), (target, 0.0));
+        assert!(!follow.is_handing_off());
+    }
+
+    #[test]
+    fn test_follow_target_interrupted_hand_off() {
+        let mut follow = FollowTarget::new(Entity::from_raw(0))
+            .with_transition(2.0, Easing::Linear)
+            .with_arc_height(0.5);
+        follow.advance(DVec3::ZERO, 0.1);
+        follow.switch_to(Entity::from_raw(1));
+        let (look_at, lift) = follow.advance(DVec3::new(10.0, 0.0, 0.0), 1.0);
+        assert_relative_eq!(lift, 5.0);
+
+        // Switching back at the top of the arc eases the lift out instead of dropping it.
+        follow.switch_to(Entity::from_raw(0));
+        let (_, lift) = follow.advance(look_at, 0.0);
+        assert_relative_eq!(lift, 5.0);
+        let (_, lift) = follow.advance(look_at, 1.0);
+        assert_relative_eq!(lift, 2.5);
+        let (_, lift) = follow.advance(look_at, 1.0);
+        assert_eq!(lift, 0.0);
+    }
+
+    #[test]
+    fn test_follow_target_despawned_mid_hand_off() {
+        let mut app = headless_app();
+        app.add_plugin(FollowTargetPlugin);
+        let first = app.world.spawn(GlobalTransform::IDENTITY).id();
+        let second = app
+            .world
+            .spawn(GlobalTransform::from_xyz(10.0, 0.0, 0.0))
+            .id();
+        let follow = FollowTarget::new(first)
+            .with_transition(1000.0, Easing::Linear)
+            .with_arc_height(0.5);
+        let camera = app
+            .world
+            .spawn((
+                LookTransform::new(DVec3::new(0.0, 2.0, 5.0), DVec3::ZERO),
+                follow,
+            ))
+            .id();
+        step(&mut app, 1);
+
+        app.world
+            .get_mut::<FollowTarget>(camera)
+            .unwrap()
+            .switch_to(second);
+        step(&mut app, 2);
+        assert!(app
+            .world
+            .get::<FollowTarget>(camera)
+            .unwrap()
+            .is_handing_off());
+
+        app.world.despawn(second);
+        step(&mut app, 1);
+        let follow = app.world.get::<FollowTarget>(camera).unwrap();
+        assert!(!follow.is_handing_off());
+        let transform = look_transform(&app, camera);
+        assert_relative_eq!(transform.eye.y - transform.target.y, 2.0, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::controllers::CameraInputState;
+
+use bevy::{
+    app::prelude::*,
+    ecs::prelude::*,
+    input::prelude::*,
+    render::camera::Projection,
+};
+
+pub struct FovZoomPlugin;
+
+impl Plugin for FovZoomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraInputState>()
+            .add_system(fov_zoom_input_system)
+            .add_system(fov_zoom_system.after(fov_zoom_input_system));
+    }
+}
+
+/// Eases the field of view of a perspective camera toward `zoomed_fov` while `active`, and back to `default_fov` otherwise.
+/// This works alongside any controller, since it only touches the `Projection`.
+///
+/// If `key` or `mouse_button` is set, `active` is driven by holding that input. Otherwise `active` can be set directly.
+/// Neither is set by default, since the right mouse button already looks around with most controllers.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct FovZoom {
+    pub enabled: bool,
+    pub active: bool,
+    /// Vertical field of view in radians when not zoomed
+    pub default_fov: f32,
+    /// Vertical field of view in radians when zoomed
+    pub zoomed_fov: f32,
+    pub key: Option<KeyCode>,
+    pub mouse_button: Option<MouseButton>,
+    /// The greater, the slower to reach the target field of view
+    pub smoothing_weight: f32,
+}
+
+impl Default for FovZoom {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            active: false,
+            default_fov: std::f32::consts::PI / 4.0,
+            zoomed_fov: std::f32::consts::PI / 12.0,
+            key: None,
+            mouse_button: None,
+            smoothing_weight: 0.8,
+        }
+    }
+}
+
+impl FovZoom {
+    pub fn target_fov(&self) -> f32 {
+        if self.active {
+            self.zoomed_fov
+        } else {
+            self.default_fov
+        }
+    }
+}
+
+fn fov_zoom_input_system(
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    mut zooms: Query<&mut FovZoom>,
+    input_state: Res<CameraInputState>,
+) {
+    if input_state.is_blocked() {
+        return;
+    }
+
+    for mut zoom in zooms.iter_mut() {
+        if zoom.key.is_none() && zoom.mouse_button.is_none() {
+            continue;
+        }
+        let active = zoom.enabled
+            && (zoom.key.map_or(false, |k| keyboard.pressed(k))
+                || zoom.mouse_button.map_or(false, |b| mouse_buttons.pressed(b)));
+        if zoom.active != active {
+            zoom.active = active;
+        }
+    }
+}
+
+fn fov_zoom_system(mut cameras: Query<(&FovZoom, &mut Projection)>) {
+    for (zoom, mut projection) in cameras.iter_mut() {
+        let target_fov = zoom.target_fov();
+        if let Projection::Perspective(perspective) = projection.as_ref() {
+            if (perspective.fov - target_fov).abs() <= f32::EPSILON {
+                continue;
+            }
+        }
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            let lag_weight = zoom.smoothing_weight.clamp(0.0, 1.0);
+            perspective.fov = perspective.fov * lag_weight + target_fov * (1.0 - lag_weight);
+            if (perspective.fov - target_fov).abs() < 1e-4 {
+                perspective.fov = target_fov;
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    use bevy::{input::InputPlugin, render::camera::PerspectiveProjection};
+
+    fn fov(app: &App, camera: Entity) -> f32 {
+        match app.world.get::<Projection>(camera).unwrap() {
+            Projection::Perspective(perspective) => perspective.fov,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_fov_zoom_eases_in_and_out() {
+        let mut app = headless_app();
+        app.add_plugin(InputPlugin).add_plugin(FovZoomPlugin);
+        let zoom = FovZoom::default();
+        let camera = app
+            .world
+            .spawn((
+                zoom,
+                Projection::Perspective(PerspectiveProjection {
+                    fov: zoom.default_fov,
+                    ..Default::default()
+                }),
+            ))
+            .id();
+
+        app.world.get_mut::<FovZoom>(camera).unwrap().active = true;
+        step(&mut app, 1);
+        let eased = fov(&app, camera);
+        assert!(eased < zoom.default_fov && eased > zoom.zoomed_fov);
+        step(&mut app, 100);
+        assert_eq!(fov(&app, camera), zoom.zoomed_fov);
+
+        app.world.get_mut::<FovZoom>(camera).unwrap().active = false;
+        step(&mut app, 1);
+        let eased = fov(&app, camera);
+        assert!(eased < zoom.default_fov && eased > zoom.zoomed_fov);
+        step(&mut app, 100);
+        assert_eq!(fov(&app, camera), zoom.default_fov);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::LookTransform;
+
+use bevy::math::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A position in geodetic terms, on a sphere centered at the origin with +Y toward the north pole. A longitude of zero
+/// faces +Z, and increasing longitude moves toward +X. Angles are in degrees, and `altitude` is above the surface.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LatLonAlt {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: f64,
+}
+
+impl LatLonAlt {
+    pub fn new(latitude: f64, longitude: f64, altitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            altitude,
+        }
+    }
+
+    /// The position on a globe of `globe_radius`, in world units.
+    pub fn to_position(&self, globe_radius: f64) -> DVec3 {
+        (globe_radius + self.altitude) * self.up()
+    }
+
+    /// The inverse of `to_position`. Longitude is in `(-180, 180]`, and is zero at the poles.
+    pub fn from_position(position: DVec3, globe_radius: f64) -> Self {
+        let distance = position.length();
+        let latitude = if distance > 0.0 {
+            (position.y / distance).clamp(-1.0, 1.0).asin()
+        } else {
+            0.0
+        };
+        Self {
+            latitude: latitude.to_degrees(),
+            longitude: position.x.atan2(position.z).to_degrees(),
+            altitude: distance - globe_radius,
+        }
+    }
+
+    /// The direction away from the center of the globe.
+    pub fn up(&self) -> DVec3 {
+        let (sin_lat, cos_lat) = self.latitude.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.longitude.to_radians().sin_cos();
+        DVec3::new(cos_lat * sin_lon, sin_lat, cos_lat * cos_lon)
+    }
+
+    /// The direction along the surface toward the north pole.
+    pub fn north(&self) -> DVec3 {
+        let (sin_lat, cos_lat) = self.latitude.to_radians().sin_cos();
+        let (sin_lon, cos_lon) = self.longitude.to_radians().sin_cos();
+        DVec3::new(-sin_lat * sin_lon, cos_lat, -sin_lat * cos_lon)
+    }
+}
+
+/// The lowest altitude `LookTransform::from_lat_lon_alt` puts the eye at, so it can't coincide with the surface point
+/// it looks at.
+pub const MIN_LOOK_ALTITUDE: f64 = 1e-3;
+
+impl LookTransform {
+    /// Looks straight down from `position` at the surface of a globe of `globe_radius` centered at the origin, with
+    /// north at the top of the screen. `up` can't point away from the globe while looking straight down, so it points
+    /// north; the orbit controller's `globe_center` makes it radial. Altitudes below `MIN_LOOK_ALTITUDE` are raised to
+    /// it.
+    pub fn from_lat_lon_alt(position: LatLonAlt, globe_radius: f64) -> Self {
+        let position = LatLonAlt {
+            altitude: position.altitude.max(MIN_LOOK_ALTITUDE),
+            ..position
+        };
+        let surface = LatLonAlt {
+            altitude: 0.0,
+            ..position
+        };
+        Self::new(
+            position.to_position(globe_radius),
+            surface.to_position(globe_radius),
+        )
+        .with_up(position.north())
+    }
+
+    /// Where the eye is on a globe of `globe_radius` centered at the origin.
+    pub fn lat_lon_alt(&self, globe_radius: f64) -> LatLonAlt {
+        LatLonAlt::from_position(self.eye, globe_radius)
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    const EARTH_RADIUS: f64 = 6_371_000.0;
+
+    #[test]
+    fn test_lat_lon_alt_round_trip() {
+        let position = LatLonAlt::new(48.8584, 2.2945, 330.0);
+        let transform = LookTransform::from_lat_lon_alt(position, EARTH_RADIUS);
+        let round_trip = transform.lat_lon_alt(EARTH_RADIUS);
+
+        assert_relative_eq!(round_trip.latitude, position.latitude, epsilon = 1e-9);
+        assert_relative_eq!(round_trip.longitude, position.longitude, epsilon = 1e-9);
+        assert_relative_eq!(round_trip.altitude, position.altitude, epsilon = 1e-6);
+        assert_relative_eq!(transform.radius(), 330.0, epsilon = 1e-6);
+        assert_relative_eq!(position.up().dot(position.north()), 0.0, epsilon = 1e-12);
+
+        let north_pole = LatLonAlt::new(90.0, 0.0, 0.0).to_position(EARTH_RADIUS);
+        assert!(north_pole.abs_diff_eq(DVec3::new(0.0, EARTH_RADIUS, 0.0), 1e-6));
+    }
+
+    #[test]
+    fn test_lat_lon_alt_below_surface() {
+        let position = LatLonAlt::new(10.0, 20.0, -5.0);
+        let transform = LookTransform::from_lat_lon_alt(position, 1000.0);
+
+        assert_relative_eq!(transform.radius(), MIN_LOOK_ALTITUDE, epsilon = 1e-9);
+        let down = transform.look_direction().unwrap();
+        assert_relative_eq!(down.dot(position.up()), -1.0, epsilon = 1e-6);
+    }
+}
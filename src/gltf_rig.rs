@@ -0,0 +1,108 @@
+use crate::{
+    controllers::{
+        fps::FpsCameraController, orbit::OrbitCameraController, unreal::UnrealCameraController,
+    },
+    LookTransform, LookTransformBundle, Smoother,
+};
+
+use bevy::{
+    app::prelude::*,
+    core::Name,
+    ecs::prelude::*,
+    hierarchy::{BuildChildren, Parent},
+    render::camera::Camera,
+    transform::{components::GlobalTransform, TransformSystem},
+};
+
+/// Turns cameras spawned from glTF scenes into `LookTransform` rigs, so cameras authored in tools like Blender can be
+/// smoothed and controlled.
+///
+/// A camera counts as imported if it was spawned as a child (like every glTF node) and doesn't already have a
+/// `LookTransform`. It is detached from its parent, keeping its world transform, since a `LookTransform` is in world
+/// space.
+#[derive(Clone, Debug, Resource)]
+pub struct GltfCameraRigPlugin {
+    /// Controller to attach to each imported camera.
+    pub controller: Option<GltfCameraController>,
+    /// Smoothing weight, if no controller is attached.
+    pub smoothing_weight: f64,
+    /// Distance from the eye to the target along the camera's view direction.
+    pub target_distance: f64,
+    /// Only convert cameras whose `Name` contains this string.
+    pub name_filter: Option<String>,
+}
+
+impl Default for GltfCameraRigPlugin {
+    fn default() -> Self {
+        Self {
+            controller: None,
+            smoothing_weight: 0.9,
+            target_distance: 10.0,
+            name_filter: None,
+        }
+    }
+}
+
+impl Plugin for GltfCameraRigPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.clone()).add_system_to_stage(
+            CoreStage::PostUpdate,
+            gltf_camera_rig_system.after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum GltfCameraController {
+    Fps(FpsCameraController),
+    Orbit(OrbitCameraController),
+    Unreal(UnrealCameraController),
+}
+
+impl GltfCameraController {
+    pub fn smoothing_weight(&self) -> f64 {
+        match self {
+            GltfCameraController::Fps(c) => c.smoothing_weight,
+            GltfCameraController::Orbit(c) => c.smoothing_weight,
+            GltfCameraController::Unreal(c) => c.smoothing_weight,
+        }
+    }
+}
+
+fn gltf_camera_rig_system(
+    mut commands: Commands,
+    settings: Res<GltfCameraRigPlugin>,
+    cameras: Query<
+        (Entity, &GlobalTransform, Option<&Name>),
+        (Added<Camera>, With<Parent>, Without<LookTransform>),
+    >,
+) {
+    for (entity, global_transform, name) in cameras.iter() {
+        if let Some(filter) = &settings.name_filter {
+            if !name.map_or(false, |n| n.as_str().contains(filter.as_str())) {
+                continue;
+            }
+        }
+
+        let eye = global_transform.translation();
+        let target = eye + settings.target_distance * global_transform.forward();
+        let smoothing_weight = settings
+            .controller
+            .map_or(settings.smoothing_weight, |c| c.smoothing_weight());
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands
+            .remove_parent()
+            .insert(global_transform.compute_transform())
+            .insert(LookTransformBundle {
+                transform: LookTransform::new(eye, target),
+                smoother: Smoother::new(smoothing_weight),
+            });
+        match settings.controller {
+            Some(GltfCameraController::Fps(c)) => entity_commands.insert(c),
+            Some(GltfCameraController::Orbit(c)) => entity_commands.insert(c),
+            Some(GltfCameraController::Unreal(c)) => entity_commands.insert(c),
+            None => &mut entity_commands,
+        };
+    }
+}
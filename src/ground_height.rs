@@ -0,0 +1,138 @@
+use crate::LookTransform;
+
+use bevy::{ecs::prelude::*, math::prelude::*};
+
+use std::sync::Arc;
+
+/// The height of the ground, for controllers that keep the eye above it.
+///
+/// Implement this with a raycast into your physics world, or use a closure that samples a heightmap; closures with the
+/// same signature as `height_below` implement it too.
+pub trait HeightProvider: Send + Sync + 'static {
+    /// Returns the height of the ground directly below (or above) `position`, measured along the camera's
+    /// `LookTransform::up` axis, or `None` if there is no ground there.
+    fn height_below(&self, position: DVec3) -> Option<f64>;
+}
+
+impl<F> HeightProvider for F
+where
+    F: Fn(DVec3) -> Option<f64> + Send + Sync + 'static,
+{
+    fn height_below(&self, position: DVec3) -> Option<f64> {
+        self(position)
+    }
+}
+
+/// Add to a camera to give controllers that support it a ground to stand on.
+#[derive(Clone, Component)]
+pub struct GroundHeight {
+    pub provider: Arc<dyn HeightProvider>,
+}
+
+impl GroundHeight {
+    pub fn new(provider: impl HeightProvider) -> Self {
+        Self {
+            provider: Arc::new(provider),
+        }
+    }
+
+    pub fn height_below(&self, position: DVec3) -> Option<f64> {
+        self.provider.height_below(position)
+    }
+}
+
+/// Keeps a camera's eye at least `min_eye_height` above the ground, whatever moves it. The ground comes from the
+/// camera's `GroundHeight` if it has one, or is the plane through `ground_point` perpendicular to `LookTransform::up`
+/// otherwise. Like `LookTransformLocks`, this is enforced after the controllers and before smoothing, so the correction
+/// is smoothed.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct GroundClamp {
+    pub min_eye_height: f64,
+    /// Move the target along with the eye, keeping the view direction, for first-person and flying cameras. Otherwise
+    /// only the eye moves, which suits orbiting.
+    pub move_target: bool,
+    /// A point on the ground for cameras without a `GroundHeight`
+    pub ground_point: DVec3,
+}
+
+impl Default for GroundClamp {
+    fn default() -> Self {
+        Self {
+            min_eye_height: 0.1,
+            move_target: false,
+            ground_point: DVec3::ZERO,
+        }
+    }
+}
+
+impl GroundClamp {
+    /// Returns `transform` with the eye pushed up to `min_eye_height` above `ground`, a height along
+    /// `LookTransform::up`.
+    pub fn apply(&self, mut transform: LookTransform, ground: f64) -> LookTransform {
+        let up = transform.up.normalize();
+        let depth = ground + self.min_eye_height - transform.eye.dot(up);
+        if depth > 0.0 {
+            transform.eye += depth * up;
+            if self.move_target {
+                transform.target += depth * up;
+            }
+        }
+        transform
+    }
+}
+
+pub(crate) fn ground_clamp_system(
+    mut cameras: Query<(&GroundClamp, &mut LookTransform, Option<&GroundHeight>)>,
+) {
+    for (clamp, mut transform, ground) in cameras.iter_mut() {
+        let ground = match ground {
+            Some(ground) => match ground.height_below(transform.eye) {
+                Some(height) => height,
+                None => continue,
+            },
+            None => clamp.ground_point.dot(transform.up.normalize()),
+        };
+        let clamped = clamp.apply(*transform, ground);
+        // Only write when something moved, so settled cameras stay unchanged.
+        if clamped != *transform {
+            *transform = clamped;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ground_clamp() {
+        let below = LookTransform::new(DVec3::new(0.0, -1.0, 5.0), DVec3::ZERO);
+        let clamp = GroundClamp {
+            min_eye_height: 0.5,
+            move_target: false,
+            ground_point: DVec3::ZERO,
+        };
+
+        let clamped = clamp.apply(below, 0.0);
+        assert_eq!(clamped.eye, DVec3::new(0.0, 0.5, 5.0));
+        assert_eq!(clamped.target, DVec3::ZERO);
+
+        let clamped = GroundClamp {
+            move_target: true,
+            ..clamp
+        }
+        .apply(below, 1.0);
+        assert_eq!(clamped.eye, DVec3::new(0.0, 1.5, 5.0));
+        assert_eq!(clamped.target, DVec3::new(0.0, 2.5, 0.0));
+
+        let above = LookTransform::new(DVec3::new(0.0, 3.0, 5.0), DVec3::ZERO);
+        assert_eq!(clamp.apply(above, 0.0), above);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::controllers::{
+    fps::{self, FpsCameraController},
+    CameraControlSystem, CameraInputState,
+};
+
+use bevy::{app::prelude::*, ecs::prelude::*, math::prelude::*};
+use std::f64::consts::{PI, TAU};
+
+/// Turns the enabled FPS camera with the orientation of the device, so a phone or tablet acts as a window into the world.
+///
+/// On the web, `deviceorientation` events are read automatically (some browsers only send them after the user grants
+/// permission). On other platforms, send `DeviceOrientation` events from your platform's motion sensor API.
+pub struct GyroscopePlugin;
+
+impl Plugin for GyroscopePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GyroscopeSettings>()
+            .init_resource::<CameraInputState>()
+            .add_event::<DeviceOrientation>()
+            .add_event::<fps::ControlEvent>()
+            .add_system(gyroscope_input_map.label(CameraControlSystem::Input));
+
+        #[cfg(target_arch = "wasm32")]
+        web::add_device_orientation_listener(app);
+    }
+}
+
+/// Sensitivity of the `GyroscopePlugin` input map.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct GyroscopeSettings {
+    /// Camera rotation per radian of device rotation. `1.0` keeps the view locked to the world.
+    pub sensitivity: DVec2,
+}
+
+impl Default for GyroscopeSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: DVec2::ONE,
+        }
+    }
+}
+
+/// The orientation of the device, as Tait-Bryan angles in radians like the web's `DeviceOrientationEvent`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DeviceOrientation {
+    /// Rotation around the axis out of the screen's back, in `[0, 2π)`. Increases turning left.
+    pub alpha: f64,
+    /// Front to back tilt, in `[-π, π)`. Increases tilting the top of the device toward the user.
+    pub beta: f64,
+    /// Left to right tilt, in `[-π/2, π/2)`.
+    pub gamma: f64,
+}
+
+impl DeviceOrientation {
+    /// The yaw and pitch from `previous` to `self`, taking the shortest way around.
+    pub fn delta_from(&self, previous: &Self) -> DVec2 {
+        let wrap = |angle: f64| (angle + PI).rem_euclid(TAU) - PI;
+        DVec2::new(
+            wrap(self.alpha - previous.alpha),
+            wrap(self.beta - previous.beta),
+        )
+    }
+}
+
+fn gyroscope_input_map(
+    mut events: EventWriter<fps::ControlEvent>,
+    mut orientation_events: EventReader<DeviceOrientation>,
+    settings: Res<GyroscopeSettings>,
+    controllers: Query<&FpsCameraController>,
+    input_state: Res<CameraInputState>,
+    mut previous: Local<Option<DeviceOrientation>>,
+) {
+    let mut delta = DVec2::ZERO;
+    for orientation in orientation_events.iter() {
+        if let Some(previous) = previous.as_ref() {
+            delta += orientation.delta_from(previous);
+        }
+        *previous = Some(*orientation);
+    }
+
+    // Can only control one camera at a time.
+    if input_state.is_blocked() || !controllers.iter().any(|c| c.enabled) {
+        return;
+    }
+
+    if delta != DVec2::ZERO {
+        // Turning or tilting the device left and up should look left and up.
+        events.send(fps::ControlEvent::Rotate(-settings.sensitivity * delta));
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::DeviceOrientation;
+
+    use bevy::{app::prelude::*, ecs::prelude::*};
+    use std::sync::{Arc, Mutex};
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    #[derive(Clone, Default, Resource)]
+    struct OrientationQueue(Arc<Mutex<Vec<DeviceOrientation>>>);
+
+    pub(super) fn add_device_orientation_listener(app: &mut App) {
+        let window = if let Some(window) = web_sys::window() {
+            window
+        } else {
+            return;
+        };
+
+        let queue = OrientationQueue::default();
+        let sender = queue.clone();
+        let listener = Closure::<dyn FnMut(web_sys::DeviceOrientationEvent)>::new(
+            move |event: web_sys::DeviceOrientationEvent| {
+                if let (Some(alpha), Some(beta), Some(gamma)) =
+                    (event.alpha(), event.beta(), event.gamma())
+                {
+                    sender.0.lock().unwrap().push(DeviceOrientation {
+                        alpha: alpha.to_radians(),
+                        beta: beta.to_radians(),
+                        gamma: gamma.to_radians(),
+                    });
+                }
+            },
+        );
+        let callback = listener.as_ref().unchecked_ref();
+        if window
+            .add_event_listener_with_callback("deviceorientation", callback)
+            .is_ok()
+        {
+            // Listen for the lifetime of the page.
+            listener.forget();
+            app.insert_resource(queue)
+                .add_system_to_stage(CoreStage::PreUpdate, send_device_orientation_system);
+        }
+    }
+
+    fn send_device_orientation_system(
+        queue: Res<OrientationQueue>,
+        mut events: EventWriter<DeviceOrientation>,
+    ) {
+        events.send_batch(queue.0.lock().unwrap().drain(..));
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta_wraps_around() {
+        let previous = DeviceOrientation {
+            alpha: 6.2,
+            ..Default::default()
+        };
+        let orientation = DeviceOrientation {
+            alpha: 0.1,
+            ..Default::default()
+        };
+        let delta = orientation.delta_from(&previous);
+        assert!((delta.x - (0.1 + TAU - 6.2)).abs() < 1e-9);
+        assert_eq!(delta.y, 0.0);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{add_transform_offset_system, LookTransformSystem, TransformOffset};
+
+use bevy::{app::prelude::*, ecs::prelude::*, math::prelude::*, time::Time};
+
+/// Drifts cameras with a `HandheldNoise`.
+pub struct HandheldNoisePlugin;
+
+impl Plugin for HandheldNoisePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            add_transform_offset_system::<HandheldNoise>,
+        )
+        .add_system(
+            handheld_noise_system
+                .after(LookTransformSystem::Smooth)
+                .before(LookTransformSystem::Offset),
+        );
+    }
+}
+
+/// A slow, low-amplitude drift of the camera from Perlin noise, like a handheld documentary shot. As a
+/// [`TransformOffset`](crate::TransformOffset), it works on top of any controller or `CameraPathPlayback`.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct HandheldNoise {
+    /// Picks a different, repeatable drift
+    pub seed: u32,
+    /// Scales both the rotation and translation; `0.0` turns the drift off
+    pub intensity: f64,
+    /// How many times per second the drift changes direction, roughly
+    pub frequency: f64,
+    /// Largest pitch, yaw and roll, in radians, at an intensity of `1.0`
+    pub max_rotation: DVec3,
+    /// Largest camera-local translation, in units, at an intensity of `1.0`
+    pub max_translation: DVec3,
+    time: f64,
+}
+
+impl Default for HandheldNoise {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl HandheldNoise {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            intensity: 1.0,
+            frequency: 0.3,
+            max_rotation: DVec3::new(0.01, 0.015, 0.005),
+            max_translation: DVec3::splat(0.01),
+            time: 0.0,
+        }
+    }
+
+    /// Advances the drift by `dt` seconds and returns it.
+    pub fn update(&mut self, dt: f64) -> TransformOffset {
+        self.time += dt * self.frequency;
+        // Six independent channels of noise.
+        let seed = self.seed.wrapping_mul(6);
+        let noise = |channel: u32| perlin_noise(seed.wrapping_add(channel), self.time);
+        let rotation = DVec3::new(noise(0), noise(1), noise(2));
+        let translation = DVec3::new(noise(3), noise(4), noise(5));
+        let rotation = self.intensity * self.max_rotation * rotation;
+        let translation = self.intensity * self.max_translation * translation;
+        TransformOffset {
+            translation,
+            rotation: DQuat::from_euler(EulerRot::YXZ, rotation.y, rotation.x, rotation.z),
+        }
+    }
+}
+
+fn handheld_noise_system(
+    time: Res<Time>,
+    mut cameras: Query<(&mut HandheldNoise, &mut TransformOffset)>,
+) {
+    for (mut noise, mut offset) in cameras.iter_mut() {
+        let drift = noise.update(time.delta_seconds_f64());
+        offset.translate(drift.translation);
+        offset.rotate(drift.rotation);
+    }
+}
+
+/// One-dimensional Perlin noise, roughly between `-1.0` and `1.0`, and `0.0` at integer `x`.
+pub fn perlin_noise(seed: u32, x: f64) -> f64 {
+    let cell = x.floor();
+    let t = x - cell;
+    let gradient0 = noise_gradient(seed, cell as i64);
+    let gradient1 = noise_gradient(seed, cell as i64 + 1);
+    let fade = t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let value0 = gradient0 * t;
+    let value1 = gradient1 * (t - 1.0);
+    2.0 * (value0 + fade * (value1 - value0))
+}
+
+/// A random gradient between `-1.0` and `1.0` for lattice point `i`.
+fn noise_gradient(seed: u32, i: i64) -> f64 {
+    // SplitMix64 finalizer.
+    let mut h = (i as u64) ^ ((seed as u64) << 32);
+    h = h.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^= h >> 31;
+    (h as f64 / u64::MAX as f64) * 2.0 - 1.0
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perlin_noise() {
+        let mut last = perlin_noise(7, 0.0);
+        assert_eq!(last, 0.0);
+        for i in 1..=1000 {
+            let value = perlin_noise(7, i as f64 * 0.01);
+            assert!(value.abs() <= 1.0);
+            // Continuous.
+            assert!((value - last).abs() < 0.05);
+            last = value;
+        }
+        assert_eq!(perlin_noise(7, 3.5), perlin_noise(7, 3.5));
+        assert_ne!(perlin_noise(7, 3.5), perlin_noise(8, 3.5));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{add_transform_offset_system, LookTransform, LookTransformSystem, TransformOffset};
+
+use bevy::{app::prelude::*, ecs::prelude::*, math::prelude::*, time::Time};
+use std::f64::consts::TAU;
+
+/// Bobs cameras with a `HeadBob` as they move.
+pub struct HeadBobPlugin;
+
+impl Plugin for HeadBobPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(CoreStage::PreUpdate, add_transform_offset_system::<HeadBob>)
+            .add_system(
+                head_bob_system
+                    .after(LookTransformSystem::Smooth)
+                    .before(LookTransformSystem::Offset),
+            );
+    }
+}
+
+/// A procedural bob of the camera while it moves, like the head of someone walking, for FPS cameras, applied as a
+/// [`TransformOffset`](crate::TransformOffset).
+///
+/// The bob advances with the distance moved across the ground, and fades in with speed and back out when movement
+/// stops.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct HeadBob {
+    /// Side to side and up and down amplitude, in units
+    pub amplitude: DVec2,
+    /// Bobs per unit of distance moved. Each bob is one step, and the camera sways side to side once every two steps.
+    pub frequency: f64,
+    /// Speed in units per second at which the bob reaches full amplitude
+    pub full_speed: f64,
+    /// How quickly the amplitude follows the speed, per second
+    pub fade_rate: f64,
+    phase: f64,
+    intensity: f64,
+    last_eye: Option<DVec3>,
+}
+
+impl Default for HeadBob {
+    fn default() -> Self {
+        Self::new(DVec2::new(0.03, 0.05), 0.6)
+    }
+}
+
+impl HeadBob {
+    pub fn new(amplitude: DVec2, frequency: f64) -> Self {
+        Self {
+            amplitude,
+            frequency,
+            full_speed: 4.0,
+            fade_rate: 8.0,
+            phase: 0.0,
+            intensity: 0.0,
+            last_eye: None,
+        }
+    }
+
+    /// Advances the bob by a frame of `dt` seconds in which the eye moved `distance` across the ground, and returns the
+    /// camera-local offset.
+    pub fn update(&mut self, distance: f64, dt: f64) -> DVec3 {
+        if dt > 0.0 {
+            let target_intensity = (distance / dt / self.full_speed).min(1.0);
+            let blend = 1.0 - (-self.fade_rate * dt).exp();
+            self.intensity += (target_intensity - self.intensity) * blend;
+        }
+        self.phase = (self.phase + distance * self.frequency).rem_euclid(2.0);
+
+        // Dips once per step, and sways once per two steps.
+        let step = (self.phase * TAU / 2.0).sin();
+        let offset = DVec3::new(
+            self.amplitude.x * step,
+            -self.amplitude.y * (1.0 - (self.phase * TAU).cos()) / 2.0,
+            0.0,
+        );
+        self.intensity * offset
+    }
+}
+
+fn head_bob_system(
+    time: Res<Time>,
+    mut cameras: Query<(&mut HeadBob, &LookTransform, &mut TransformOffset)>,
+) {
+    for (mut bob, look_transform, mut offset) in cameras.iter_mut() {
+        let eye = look_transform.eye;
+        let up = look_transform.up;
+        let distance = bob.last_eye.map_or(0.0, |last_eye| {
+            let moved = eye - last_eye;
+            (moved - moved.dot(up) * up).length()
+        });
+        bob.last_eye = Some(eye);
+        offset.translate(bob.update(distance, time.delta_seconds_f64()));
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_head_bob_fades_out() {
+        let mut bob = HeadBob::default();
+        for _ in 0..60 {
+            bob.update(0.1, 1.0 / 60.0);
+        }
+        assert!(bob.intensity > 0.9);
+
+        for _ in 0..120 {
+            bob.update(0.0, 1.0 / 60.0);
+        }
+        assert!(bob.update(0.0, 1.0 / 60.0).length() < 1e-3);
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::{
+    math::DVec2,
+    reflect::{FromReflect, Reflect},
+};
+use serde::{Deserialize, Serialize};
+
+const MAX_AVERAGE_FRAMES: usize = 16;
+
+/// A filter for noisy cursor deltas, applied before they turn into rotation.
+#[derive(Clone, Copy, Debug, Default, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub enum MouseFilter {
+    #[default]
+    None,
+    /// Average the deltas of the last `frames` frames (at most 16).
+    MovingAverage { frames: usize },
+    /// The [1€ filter](https://gery.casiez.net/1euro/): heavy smoothing for slow motion, little lag for fast motion.
+    OneEuro {
+        /// Cutoff frequency in Hz at zero speed. Lower removes more jitter.
+        min_cutoff: f64,
+        /// How quickly the cutoff rises with speed. Higher reduces lag.
+        beta: f64,
+    },
+}
+
+/// The state of a `MouseFilter` between frames.
+#[derive(Clone, Debug, Default)]
+pub struct MouseFilterState {
+    history: [DVec2; MAX_AVERAGE_FRAMES],
+    next: usize,
+    len: usize,
+    prev_value: Option<DVec2>,
+    prev_derivative: DVec2,
+}
+
+impl MouseFilterState {
+    /// Filters this frame's `delta`, where `dt` is the frame time in seconds.
+    pub fn filter(&mut self, filter: MouseFilter, delta: DVec2, dt: f64) -> DVec2 {
+        match filter {
+            MouseFilter::None => {
+                self.reset();
+                delta
+            }
+            MouseFilter::MovingAverage { frames } => {
+                let frames = frames.clamp(1, MAX_AVERAGE_FRAMES);
+                self.history[self.next] = delta;
+                self.next = (self.next + 1) % MAX_AVERAGE_FRAMES;
+                self.len = (self.len + 1).min(frames);
+
+                let sum: DVec2 = (0..self.len)
+                    .map(|i| {
+                        self.history[(self.next + MAX_AVERAGE_FRAMES - 1 - i) % MAX_AVERAGE_FRAMES]
+                    })
+                    .sum();
+                sum / self.len as f64
+            }
+            MouseFilter::OneEuro { min_cutoff, beta } => {
+                const DERIVATIVE_CUTOFF: f64 = 1.0;
+
+                let prev_value = if let Some(prev) = self.prev_value {
+                    prev
+                } else {
+                    self.prev_value = Some(delta);
+                    return delta;
+                };
+                if dt <= 0.0 {
+                    return prev_value;
+                }
+
+                let derivative = (delta - prev_value) / dt;
+                let derivative = self
+                    .prev_derivative
+                    .lerp(derivative, smoothing_factor(DERIVATIVE_CUTOFF, dt));
+                let cutoff = min_cutoff + beta * derivative.length();
+                let value = prev_value.lerp(delta, smoothing_factor(cutoff, dt));
+
+                self.prev_derivative = derivative;
+                self.prev_value = Some(value);
+                value
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn smoothing_factor(cutoff: f64, dt: f64) -> f64 {
+    let tau = 1.0 / (2.0 * std::f64::consts::PI * cutoff);
+    1.0 / (1.0 + tau / dt)
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_moving_average() {
+        let mut state = MouseFilterState::default();
+        let filter = MouseFilter::MovingAverage { frames: 2 };
+
+        assert_relative_eq!(state.filter(filter, DVec2::new(2.0, 0.0), 0.016).x, 2.0);
+        assert_relative_eq!(state.filter(filter, DVec2::new(4.0, 0.0), 0.016).x, 3.0);
+        assert_relative_eq!(state.filter(filter, DVec2::new(0.0, 0.0), 0.016).x, 2.0);
+    }
+
+    #[test]
+    fn test_one_euro_converges_on_constant_input() {
+        let mut state = MouseFilterState::default();
+        let filter = MouseFilter::OneEuro {
+            min_cutoff: 1.0,
+            beta: 0.0,
+        };
+
+        state.filter(filter, DVec2::ZERO, 0.016);
+        let mut value = DVec2::ZERO;
+        for _ in 0..1000 {
+            value = state.filter(filter, DVec2::ONE, 0.016);
+        }
+        assert_relative_eq!(value.x, 1.0, epsilon = 1e-6);
+    }
+}
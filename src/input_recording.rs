@@ -0,0 +1,291 @@
+use crate::controllers::{fps, map, orbit, photo_mode, spectator, unreal, CameraControlSystem};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{event::ManualEventReader, prelude::*},
+    time::Time,
+};
+use serde::{Deserialize, Serialize};
+
+/// Records the `ControlEvent`s of all built-in controllers frame by frame, and plays them back in place of live input.
+///
+/// Recording runs between `CameraControlSystem::Input` and `CameraControlSystem::Control`, so it captures the events of
+/// every input map, and since playback replaces the events consumed by each `control_system`, camera motion during
+/// playback is deterministic regardless of the devices attached.
+pub struct InputRecordingPlugin;
+
+impl Plugin for InputRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputRecorder>()
+            .add_system_to_stage(CoreStage::Last, advance_input_recorder_system);
+        add_recorded_event::<fps::ControlEvent>(app);
+        add_recorded_event::<fps::CameraControlEvent>(app);
+        add_recorded_event::<orbit::ControlEvent>(app);
+        add_recorded_event::<unreal::ControlEvent>(app);
+        add_recorded_event::<spectator::ControlEvent>(app);
+        add_recorded_event::<photo_mode::ControlEvent>(app);
+        add_recorded_event::<map::ControlEvent>(app);
+    }
+}
+
+fn add_recorded_event<E: RecordableControlEvent>(app: &mut App) {
+    app.add_event::<E>().add_system(
+        input_recording_system::<E>
+            .after(CameraControlSystem::Input)
+            .before(CameraControlSystem::Control),
+    );
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum RecordedControlEvent {
+    Fps(fps::ControlEvent),
+    /// An `fps::CameraControlEvent`, with its camera as `Entity::to_bits`
+    FpsCamera {
+        camera: u64,
+        event: fps::ControlEvent,
+    },
+    Orbit(orbit::ControlEvent),
+    Unreal(unreal::ControlEvent),
+    Spectator(spectator::ControlEvent),
+    PhotoMode(photo_mode::ControlEvent),
+    Map(map::ControlEvent),
+}
+
+/// A `ControlEvent` type that can be captured by the `InputRecorder`.
+pub trait RecordableControlEvent: Clone + Send + Sync + 'static {
+    fn wrap(self) -> RecordedControlEvent;
+    fn unwrap(event: &RecordedControlEvent) -> Option<Self>;
+}
+
+macro_rules! impl_recordable_control_event(($module:ident, $variant:ident) => {
+    impl RecordableControlEvent for $module::ControlEvent {
+        fn wrap(self) -> RecordedControlEvent {
+            RecordedControlEvent::$variant(self)
+        }
+
+        fn unwrap(event: &RecordedControlEvent) -> Option<Self> {
+            match event {
+                RecordedControlEvent::$variant(e) => Some(*e),
+                _ => None,
+            }
+        }
+    }
+});
+
+impl_recordable_control_event!(fps, Fps);
+impl_recordable_control_event!(orbit, Orbit);
+impl_recordable_control_event!(unreal, Unreal);
+impl_recordable_control_event!(spectator, Spectator);
+impl_recordable_control_event!(photo_mode, PhotoMode);
+impl_recordable_control_event!(map, Map);
+
+impl RecordableControlEvent for fps::CameraControlEvent {
+    fn wrap(self) -> RecordedControlEvent {
+        RecordedControlEvent::FpsCamera {
+            camera: self.camera.to_bits(),
+            event: self.event,
+        }
+    }
+
+    fn unwrap(event: &RecordedControlEvent) -> Option<Self> {
+        match *event {
+            RecordedControlEvent::FpsCamera { camera, event } => Some(Self {
+                camera: Entity::from_bits(camera),
+                event,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RecordedFrame {
+    /// Frames since the start of the recording.
+    pub frame: u64,
+    /// Seconds since the start of the recording.
+    pub time: f64,
+    pub events: Vec<RecordedControlEvent>,
+}
+
+/// A serializable stream of control events. Frames without events are omitted.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct InputRecording {
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    pub fn frame_count(&self) -> u64 {
+        self.frames.last().map_or(0, |f| f.frame + 1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum InputRecorderMode {
+    #[default]
+    Idle,
+    Recording,
+    Playing,
+}
+
+#[derive(Debug, Default, Resource)]
+pub struct InputRecorder {
+    pub recording: InputRecording,
+    mode: InputRecorderMode,
+    frame: u64,
+    elapsed: f64,
+    current: RecordedFrame,
+    /// Index of the next frame to play back.
+    cursor: usize,
+}
+
+impl InputRecorder {
+    pub fn mode(&self) -> InputRecorderMode {
+        self.mode
+    }
+
+    /// Start a new recording, discarding the previous one.
+    pub fn start_recording(&mut self) {
+        self.recording = InputRecording::default();
+        self.restart(InputRecorderMode::Recording);
+    }
+
+    /// Start playing back `recording` from its first frame.
+    pub fn start_playback(&mut self, recording: InputRecording) {
+        self.recording = recording;
+        self.restart(InputRecorderMode::Playing);
+    }
+
+    pub fn stop(&mut self) {
+        self.mode = InputRecorderMode::Idle;
+    }
+
+    fn restart(&mut self, mode: InputRecorderMode) {
+        self.mode = mode;
+        self.frame = 0;
+        self.elapsed = 0.0;
+        self.current = RecordedFrame::default();
+        self.cursor = 0;
+    }
+
+    fn playback_events(&self) -> &[RecordedControlEvent] {
+        match self.recording.frames.get(self.cursor) {
+            Some(f) if f.frame == self.frame => &f.events,
+            _ => &[],
+        }
+    }
+}
+
+fn input_recording_system<E: RecordableControlEvent>(
+    mut recorder: ResMut<InputRecorder>,
+    mut events: ResMut<Events<E>>,
+    mut reader: Local<ManualEventReader<E>>,
+) {
+    match recorder.mode {
+        InputRecorderMode::Idle => {
+            // Keep the reader up to date so we don't record stale events when recording starts.
+            reader.iter(&events).for_each(drop);
+        }
+        InputRecorderMode::Recording => {
+            let new_events: Vec<_> = reader.iter(&events).cloned().map(E::wrap).collect();
+            recorder.current.events.extend(new_events);
+        }
+        InputRecorderMode::Playing => {
+            // Replace live input with the recorded events.
+            events.clear();
+            for event in recorder.playback_events() {
+                if let Some(e) = E::unwrap(event) {
+                    events.send(e);
+                }
+            }
+            reader.iter(&events).for_each(drop);
+        }
+    }
+}
+
+fn advance_input_recorder_system(time: Res<Time>, mut recorder: ResMut<InputRecorder>) {
+    match recorder.mode {
+        InputRecorderMode::Idle => return,
+        InputRecorderMode::Recording => {
+            let frame = recorder.frame;
+            let elapsed = recorder.elapsed;
+            let mut current = std::mem::take(&mut recorder.current);
+            if !current.events.is_empty() {
+                current.frame = frame;
+                current.time = elapsed;
+                recorder.recording.frames.push(current);
+            }
+        }
+        InputRecorderMode::Playing => {
+            if !recorder.playback_events().is_empty() {
+                recorder.cursor += 1;
+            }
+            if recorder.cursor >= recorder.recording.frames.len() {
+                recorder.stop();
+            }
+        }
+    }
+    recorder.frame += 1;
+    recorder.elapsed += time.delta_seconds_f64();
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        controllers::orbit::{OrbitCameraBundle, OrbitCameraController, OrbitCameraPlugin},
+        test_utils::*,
+        LookTransform,
+    };
+
+    use bevy::math::{DVec2, DVec3};
+
+    #[test]
+    fn test_record_and_play_back() {
+        let mut app = headless_app();
+        app.add_plugin(OrbitCameraPlugin::new(true))
+            .add_plugin(InputRecordingPlugin);
+        let start = LookTransform::new(4.0 * DVec3::Z, DVec3::ZERO);
+        let camera = app
+            .world
+            .spawn(OrbitCameraBundle::new(
+                OrbitCameraController::default(),
+                start.eye,
+                start.target,
+            ))
+            .id();
+        step(&mut app, 1);
+
+        app.world.resource_mut::<InputRecorder>().start_recording();
+        send_control_event(&mut app, orbit::ControlEvent::Orbit(DVec2::new(0.5, 0.0)));
+        step(&mut app, 1);
+        send_control_event(&mut app, orbit::ControlEvent::Orbit(DVec2::new(0.0, 0.25)));
+        step(&mut app, 2);
+        let recorded = look_transform(&app, camera);
+        let mut recorder = app.world.resource_mut::<InputRecorder>();
+        recorder.stop();
+        let recording = recorder.recording.clone();
+        assert_eq!(recording.frames.len(), 2);
+
+        // Live input during playback is replaced by the recording.
+        *app.world.get_mut::<LookTransform>(camera).unwrap() = start;
+        app.world
+            .resource_mut::<InputRecorder>()
+            .start_playback(recording);
+        send_control_event(&mut app, orbit::ControlEvent::Orbit(DVec2::new(-1.0, 0.0)));
+        step(&mut app, 3);
+        assert_eq!(
+            app.world.resource::<InputRecorder>().mode(),
+            InputRecorderMode::Idle
+        );
+        assert!(look_transform(&app, camera)
+            .eye
+            .abs_diff_eq(recorded.eye, 1e-9));
+    }
+}
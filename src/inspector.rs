@@ -0,0 +1,180 @@
+use crate::{
+    controllers::{
+        fps::FpsCameraController, orbit::OrbitCameraController, register_controller_types,
+        spectator::SpectatorCameraController, unreal::UnrealCameraController,
+    },
+    LookTransform, Smoother, SmoothingMode,
+};
+
+use bevy::{app::prelude::*, ecs::prelude::*, math::prelude::*};
+use bevy_inspector_egui::{
+    bevy_egui::{EguiContext, EguiPlugin},
+    egui, DefaultInspectorConfigPlugin,
+};
+use std::ops::RangeInclusive;
+
+/// Registers every camera component for reflection, so they show up in `bevy-inspector-egui`'s inspectors, and adds
+/// `camera_tuning_panel`: a "Camera" window with live sliders for the sensitivities, smoothing and limits of the enabled
+/// controller, so designers can tune camera feel in-game.
+pub struct CameraTuningPlugin;
+
+impl Plugin for CameraTuningPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugin(EguiPlugin);
+        }
+        if !app.is_plugin_added::<DefaultInspectorConfigPlugin>() {
+            app.add_plugin(DefaultInspectorConfigPlugin);
+        }
+        register_controller_types(app);
+
+        app.register_type::<LookTransform>()
+            .register_type::<Smoother>()
+            .register_type::<SmoothingMode>()
+            .register_type::<FpsCameraController>()
+            .register_type::<OrbitCameraController>()
+            .register_type::<SpectatorCameraController>()
+            .register_type::<UnrealCameraController>()
+            .add_system(camera_tuning_panel);
+    }
+}
+
+type TunableControllers<'a> = (
+    Option<&'a mut FpsCameraController>,
+    Option<&'a mut OrbitCameraController>,
+    Option<&'a mut UnrealCameraController>,
+    Option<&'a mut SpectatorCameraController>,
+);
+
+/// Shows sliders for the first camera with an enabled controller. Controllers are only written when a slider moves, so
+/// change detection stays quiet otherwise.
+pub fn camera_tuning_panel(
+    mut egui_context: ResMut<EguiContext>,
+    mut cameras: Query<TunableControllers>,
+) {
+    egui::Window::new("Camera").show(egui_context.ctx_mut(), |ui| {
+        for (fps, orbit, unreal, spectator) in cameras.iter_mut() {
+            if let Some(mut controller) = fps.filter(|c| c.enabled) {
+                ui.heading("FPS");
+                let mut tuned = *controller;
+                if fps_sliders(ui, &mut tuned) {
+                    *controller = tuned;
+                }
+                return;
+            }
+            if let Some(mut controller) = orbit.filter(|c| c.enabled) {
+                ui.heading("Orbit");
+                let mut tuned = *controller;
+                if orbit_sliders(ui, &mut tuned) {
+                    *controller = tuned;
+                }
+                return;
+            }
+            if let Some(mut controller) = unreal.filter(|c| c.enabled) {
+                ui.heading("Unreal");
+                let mut tuned = *controller;
+                if unreal_sliders(ui, &mut tuned) {
+                    *controller = tuned;
+                }
+                return;
+            }
+            if let Some(mut controller) = spectator.filter(|c| c.enabled) {
+                ui.heading("Spectator");
+                let mut tuned = *controller;
+                if spectator_sliders(ui, &mut tuned) {
+                    *controller = tuned;
+                }
+                return;
+            }
+        }
+        ui.label("No enabled camera controller");
+    });
+}
+
+fn fps_sliders(ui: &mut egui::Ui, c: &mut FpsCameraController) -> bool {
+    let mut changed = false;
+    changed |= sensitivity2(ui, &mut c.mouse_rotate_sensitivity, "Mouse rotate");
+    changed |= sensitivity2(ui, &mut c.gamepad_rotate_sensitivity, "Gamepad rotate");
+    changed |= sensitivity(ui, &mut c.translate_sensitivity, "Translate");
+    changed |= slider(
+        ui,
+        &mut c.gamepad_sprint_multiplier,
+        1.0..=10.0,
+        "Sprint multiplier",
+    );
+    changed |= slider(ui, &mut c.acceleration, 0.0..=1.0, "Acceleration");
+    changed |= slider(ui, &mut c.friction, 0.0..=1.0, "Friction");
+    changed |= smoothing(ui, &mut c.smoothing_weight);
+
+    changed
+}
+
+fn orbit_sliders(ui: &mut egui::Ui, c: &mut OrbitCameraController) -> bool {
+    let mut changed = false;
+    changed |= sensitivity2(ui, &mut c.mouse_rotate_sensitivity, "Mouse rotate");
+    changed |= sensitivity2(ui, &mut c.mouse_translate_sensitivity, "Mouse translate");
+    changed |= sensitivity(ui, &mut c.mouse_wheel_zoom_sensitivity, "Wheel zoom");
+    changed |= smoothing(ui, &mut c.smoothing_weight);
+    ui.separator();
+    let half_pi = std::f64::consts::FRAC_PI_2;
+    changed |= slider(ui, &mut c.min_pitch, -half_pi..=half_pi, "Min pitch");
+    changed |= slider(ui, &mut c.max_pitch, -half_pi..=half_pi, "Max pitch");
+    if let Some((min_yaw, max_yaw)) = c.yaw_range.as_mut() {
+        let pi = std::f64::consts::PI;
+        changed |= slider(ui, min_yaw, -pi..=pi, "Min yaw");
+        changed |= slider(ui, max_yaw, -pi..=pi, "Max yaw");
+    }
+
+    changed
+}
+
+fn unreal_sliders(ui: &mut egui::Ui, c: &mut UnrealCameraController) -> bool {
+    let mut changed = false;
+    changed |= sensitivity2(ui, &mut c.rotate_sensitivity, "Rotate");
+    changed |= sensitivity2(ui, &mut c.mouse_translate_sensitivity, "Mouse translate");
+    changed |= sensitivity(ui, &mut c.wheel_translate_sensitivity, "Wheel translate");
+    changed |= sensitivity(ui, &mut c.keyboard_mvmt_sensitivity, "Keyboard movement");
+    changed |= smoothing(ui, &mut c.smoothing_weight);
+    if c.walk_mode {
+        ui.separator();
+        changed |= slider(ui, &mut c.eye_height, 0.1..=10.0, "Eye height");
+        changed |= slider(ui, &mut c.gravity, 0.0..=0.1, "Gravity");
+    }
+
+    changed
+}
+
+fn spectator_sliders(ui: &mut egui::Ui, c: &mut SpectatorCameraController) -> bool {
+    let mut changed = false;
+    changed |= sensitivity2(ui, &mut c.mouse_rotate_sensitivity, "Mouse rotate");
+    changed |= sensitivity(ui, &mut c.translate_sensitivity, "Translate");
+    changed |= slider(ui, &mut c.speed_step_factor, 1.0..=4.0, "Speed step factor");
+    changed |= slider(ui, &mut c.boost_multiplier, 1.0..=10.0, "Boost multiplier");
+    changed |= smoothing(ui, &mut c.smoothing_weight);
+
+    changed
+}
+
+fn slider(ui: &mut egui::Ui, value: &mut f64, range: RangeInclusive<f64>, text: &str) -> bool {
+    ui.add(egui::Slider::new(value, range).text(text)).changed()
+}
+
+/// Sensitivities span orders of magnitude, so their sliders are logarithmic.
+fn sensitivity(ui: &mut egui::Ui, value: &mut f64, text: &str) -> bool {
+    ui.add(
+        egui::Slider::new(value, 1e-4..=100.0)
+            .logarithmic(true)
+            .text(text),
+    )
+    .changed()
+}
+
+fn sensitivity2(ui: &mut egui::Ui, value: &mut DVec2, text: &str) -> bool {
+    sensitivity(ui, &mut value.x, &format!("{} X", text))
+        | sensitivity(ui, &mut value.y, &format!("{} Y", text))
+}
+
+fn smoothing(ui: &mut egui::Ui, value: &mut f64) -> bool {
+    // A lag weight of 1.0 would never move the camera.
+    slider(ui, value, 0.0..=0.99, "Smoothing")
+}
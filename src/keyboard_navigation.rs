@@ -0,0 +1,168 @@
+use bevy::{
+    ecs::prelude::*,
+    input::{keyboard::KeyCode, Input},
+    math::DVec2,
+    utils::HashMap,
+};
+
+/// Key bindings and step sizes for the keyboard-only input maps, which let every built-in controller be used without a
+/// pointing device. Enable them with `with_keyboard_navigation` on a controller plugin.
+///
+/// Each press moves the camera by one step. Holding a key repeats the step every `repeat_interval` seconds after an
+/// initial `repeat_delay`, like text input.
+#[derive(Clone, Debug, Resource)]
+pub struct KeyboardNavigation {
+    /// Rotate up, left, down and right.
+    pub rotate_keys: [KeyCode; 4],
+    /// Zoom (or move) in and out.
+    pub zoom_keys: [KeyCode; 2],
+    /// Pan up, left, down and right.
+    pub pan_keys: [KeyCode; 4],
+    /// Radians of rotation per step.
+    pub rotate_step: f64,
+    /// Zoom factor per step, for controllers that zoom by scaling the distance to the target.
+    pub zoom_step: f64,
+    /// Distance per step, for panning and for controllers that zoom by moving.
+    pub translate_step: f64,
+    /// Seconds a key must be held before it starts repeating.
+    pub repeat_delay: f64,
+    /// Seconds between repeats while a key is held.
+    pub repeat_interval: f64,
+}
+
+impl Default for KeyboardNavigation {
+    fn default() -> Self {
+        Self {
+            rotate_keys: [KeyCode::Up, KeyCode::Left, KeyCode::Down, KeyCode::Right],
+            zoom_keys: [KeyCode::Equals, KeyCode::Minus],
+            pan_keys: [KeyCode::I, KeyCode::J, KeyCode::K, KeyCode::L],
+            rotate_step: 5f64.to_radians(),
+            zoom_step: 1.1,
+            translate_step: 0.5,
+            repeat_delay: 0.4,
+            repeat_interval: 0.1,
+        }
+    }
+}
+
+/// The steps requested with `KeyboardNavigation` keys in one frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct KeyboardNavigationSteps {
+    /// Rotation in radians. Positive is right and down, like mouse motion.
+    pub rotate: DVec2,
+    /// Zoom steps. Positive is in.
+    pub zoom: i32,
+    /// Pan distance. Positive is right and up.
+    pub pan: DVec2,
+}
+
+/// Tracks which keys are held, for repeating `KeyboardNavigation` steps.
+#[derive(Debug, Default)]
+pub struct KeyRepeat {
+    next_repeat: HashMap<KeyCode, f64>,
+}
+
+impl KeyRepeat {
+    /// Returns how many steps `key` triggers at time `now`, in seconds: one when it's first pressed, then one per
+    /// `interval` after `delay` while it's held.
+    pub fn steps(
+        &mut self,
+        keyboard: &Input<KeyCode>,
+        key: KeyCode,
+        now: f64,
+        delay: f64,
+        interval: f64,
+    ) -> u32 {
+        if keyboard.just_pressed(key) {
+            self.next_repeat.insert(key, now + delay);
+            return 1;
+        }
+        if !keyboard.pressed(key) {
+            self.next_repeat.remove(&key);
+            return 0;
+        }
+        let next = if let Some(next) = self.next_repeat.get_mut(&key) {
+            next
+        } else {
+            return 0;
+        };
+        if now < *next {
+            return 0;
+        }
+        if interval <= 0.0 {
+            // Repeat every frame.
+            *next = now;
+            return 1;
+        }
+        let steps = 1 + ((now - *next) / interval) as u32;
+        *next += steps as f64 * interval;
+        steps
+    }
+
+    /// Reads all the `KeyboardNavigation` keys at time `now`, in seconds.
+    pub fn read(
+        &mut self,
+        settings: &KeyboardNavigation,
+        keyboard: &Input<KeyCode>,
+        now: f64,
+    ) -> KeyboardNavigationSteps {
+        let mut steps = |key| {
+            self.steps(
+                keyboard,
+                key,
+                now,
+                settings.repeat_delay,
+                settings.repeat_interval,
+            ) as f64
+        };
+        let [rotate_up, rotate_left, rotate_down, rotate_right] = settings.rotate_keys;
+        let rotate = DVec2::new(
+            steps(rotate_right) - steps(rotate_left),
+            steps(rotate_down) - steps(rotate_up),
+        );
+        let [zoom_in, zoom_out] = settings.zoom_keys;
+        let zoom = steps(zoom_in) - steps(zoom_out);
+        let [pan_up, pan_left, pan_down, pan_right] = settings.pan_keys;
+        let pan = DVec2::new(
+            steps(pan_right) - steps(pan_left),
+            steps(pan_up) - steps(pan_down),
+        );
+
+        KeyboardNavigationSteps {
+            rotate: settings.rotate_step * rotate,
+            zoom: zoom as i32,
+            pan: settings.translate_step * pan,
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_repeat() {
+        let mut keyboard = Input::<KeyCode>::default();
+        let mut repeat = KeyRepeat::default();
+        let mut steps =
+            |keyboard: &Input<KeyCode>, now| repeat.steps(keyboard, KeyCode::Up, now, 0.5, 0.1);
+
+        keyboard.press(KeyCode::Up);
+        assert_eq!(steps(&keyboard, 0.0), 1);
+        keyboard.clear();
+        assert_eq!(steps(&keyboard, 0.25), 0);
+        assert_eq!(steps(&keyboard, 0.5), 1);
+        assert_eq!(steps(&keyboard, 0.75), 2);
+
+        keyboard.release(KeyCode::Up);
+        keyboard.clear();
+        assert_eq!(steps(&keyboard, 1.0), 0);
+    }
+}
@@ -3,6 +3,10 @@
 //!
 //! A collection of exponentially-smoothed camera controllers for the Bevy Engine.
 //!
+//! This fork works in `f64` throughout, on a Bevy fork whose `Transform` is `f64`. There's no `f32` build, since the
+//! precision comes from Bevy's `Transform` rather than from this crate; projects that don't need doubles should use the
+//! upstream crate.
+//!
 //! # Look Transform
 //!
 //! All controllers are based on a `LookTransform` component, which is just an `eye` point that looks at a `target` point. By
@@ -75,26 +79,276 @@
 //! - `FpsCameraPlugin` + `FpsCameraBundle`
 //!   - WASD: Translate on the XZ plane
 //!   - Shift/Space: Translate along the Y axis
-//!   - Mouse: Rotate camera
+//!   - C: Crouch (with an `EyeHeight` component)
+//!   - Mouse: Rotate camera (while holding the right mouse button, with `hold_to_look`)
+//!   - Right gamepad stick: Rotate camera
+//!   - Left gamepad stick: Translate on the XZ plane, click to sprint
+//!   - Gamepad triggers: Move forward/back
 //! - `OrbitCameraPlugin` + `OrbitCameraBundle`
 //!   - CTRL + mouse drag: Rotate camera
 //!   - Right mouse drag: Pan camera
 //!   - Mouse wheel: Zoom
+//!   - Touchpad pinch / two-finger rotate: Zoom / Orbit (see `TouchpadGesture`)
+//!   - Gamepad triggers: Zoom in/out
+//!   - Cursor at the window edges: Pan camera (with `edge_pan` enabled)
+//!   - `align_to_surface_key` (+ Shift): Look along (across) the surface normal under the cursor
 //! - `UnrealCameraPlugin` + `UnrealCameraBundle`
 //!   Best use: hold Right mouse button to orbit the view while using WASD to navigate in the scene,
 //!   using scroll wheel to accelerate/decelerate.
 //!   - Left mouse drag: Locomotion
 //!   - Right mouse drag: Rotate camera
 //!   - Left and Right or Middle mouse drag: Pan camera
+//!   - Cursor at the window edges: Pan camera (with `edge_pan` enabled)
 //!   - While holding any mouse button, use A/D for panning left/right, Q/E for panning up/down
 //!   - While holding any mouse button, use W/S for locomotion forward/backward
 //!   - While holding any mouse button, use scroll wheel to increase/decrease locomotion and panning speeds
 //!   - While holding no mouse button, use scroll wheel for locomotion forward/backward
+//!   - Touchpad pinch / two-finger rotate: Locomotion forward/backward / Rotate camera (see `TouchpadGesture`)
+//!   - Send `SetUnrealViewport` to switch to the orthographic Top, Front and Side views, which only pan and zoom
+//! - `SpectatorCameraPlugin` + `SpectatorCameraBundle`
+//!   - WASD: Translate along the view direction
+//!   - Ctrl/Space: Translate along the Y axis
+//!   - Shift: Boost speed
+//!   - Mouse: Rotate camera
+//!   - Mouse wheel: Step through speeds
+//!   - G: Toggle passing through geometry
+//! - `FixedCameraPlugin` + `FixedCameraBundle`
+//!   - No input: the eye stays in place and turns to track `FixedCameraController::target`
+//! - `PhotoModePlugin` + `PhotoModeEvent::Enter`/`Exit` on an existing camera
+//!   - WASD: Translate along the view direction
+//!   - Shift/Space: Translate along the Y axis
+//!   - Mouse: Rotate camera
+//!   - Q/E: Roll, R: Reset roll
+//!   - Z/X: Narrow/widen the field of view
+//!   - Mouse wheel: Movement speed
+//!
+//! The FPS, orbit, unreal and spectator plugins can also add a keyboard-only input map with
+//! `with_keyboard_navigation(true)`: arrow keys rotate, +/- zoom, and I/J/K/L pan. Keys, step sizes and key repeat
+//! rates are set in the `KeyboardNavigation` resource.
+//!
+//! The mouse wheel of the FPS, orbit, unreal and spectator controllers can be rebound with their `wheel_action`
+//! field, to zoom, change the field of view, change the movement speed, or do nothing.
+//!
+//! Changing a controller's `smoothing_weight` after spawning takes effect on the next frame, so it can be bound to a
+//! settings slider.
+//!
+//! `Smoother::set_radius_lag_weights` picks the lag weight from the distance to the target instead, so close-up
+//! inspection is crisp while distant navigation stays smooth.
+//!
+//! With the `inspector` feature, `CameraTuningPlugin` registers the camera components for `bevy-inspector-egui` and
+//! shows live sliders for the sensitivities, smoothing and limits of the enabled controller (see the `inspector`
+//! example).
+//!
+//! `CameraDebugPlugin` draws the rig of every `LookTransform` as lines: eye, target, look ray, the sphere the eye
+//! moves on, and the pitch and yaw limits of orbit cameras.
+//!
+//! `CameraTelemetryPlugin` reports the active controller, eye, target, radius, eye speed and smoothing error of the
+//! active camera in the `CameraTelemetry` resource, and the numbers as `Diagnostics` for `LogDiagnosticsPlugin` or an
+//! on-screen overlay.
+//!
+//! If the eye and target of a controlled camera end up at the same point, the controller points the camera along its
+//! smoothed direction from the previous frame (or the `FallbackLookDirection`) and sends a `CameraError`, instead of
+//! panicking or ignoring input.
+//!
+//! The FPS and unreal controllers keep the distance from the eye to the target while moving and turning. Their
+//! `focus_policy` chooses whether the camera turns around the eye or the target, and `focus_distance` pins the distance.
+//!
+//! `LookTransform::fit_aabb` computes where to put the eye to frame a bounding box, for perspective and orthographic
+//! projections. The orbit controller's `frame_selected_key` uses it to frame the entities marked with `FrameSelection`.
+//!
+//! Setting the orbit controller's `precision_scale` scales all of its sensitivities down while `precision_key` is held,
+//! for precise framing.
+//!
+//! The orbit controller's `pivot_offset` rotates the camera around a point offset from the target, e.g. a character's
+//! head rather than their feet, while it keeps looking at the target.
+//!
+//! With `OrbitRotationMode::ElevationRing`, dragging sideways circles the eye around the target at a constant height,
+//! and dragging up and down changes the height, like a drone circling a building.
+//!
+//! The `MapCameraController` behaves like the controls of a web map: dragging keeps the point of the ground under the
+//! cursor there, scrolling zooms toward it, and Ctrl+dragging rotates and tilts around it, precisely even at planet
+//! scale.
+//!
+//! `LookTransform::from_lat_lon_alt` and `LookTransform::lat_lon_alt` convert between camera poses and `LatLonAlt`
+//! positions on a globe, and the orbit controller's `globe_center` keeps its up axis pointing away from the globe.
+//!
+//! `ViewLocked` turns a light, or a rig of them, around the target along with the camera, so the model stays lit from
+//! the same direction on screen while orbiting, like the turntables of CAD and product viewers.
+//!
+//! `CameraCommand` events such as `SetPos`, `SetAngles`, `SetSpeed` and `NoClip` drive whichever camera has an enabled
+//! controller, for in-game consoles and remote debuggers. With the `console_commands` feature, they parse from strings
+//! like `setpos 0 10 -5`.
+//!
+//! With the `remote_control` feature, the `RemoteControlPlugin` accepts `CameraCommand`s in RON over a local TCP port,
+//! one per line, so external tools like Blender add-ons and test scripts can move the camera live. `SetTransform`, or
+//! a bare `LookTransform`, moves the camera to a whole `LookTransform` at once.
+//!
+//! A `MirrorSource` publishes its camera's smoothed pose each frame it changes, as an event and to an optional
+//! callback, and `MirrorTarget` cameras on the same channel follow it, for video walls and co-review sessions. With the
+//! `remote_control` feature, `MirrorSource::with_remote` sends it to the `RemoteControlPlugin` of another instance.
+//!
+//! A `StereoRig` anchors a VR head to the entity a controller drives: locomotion is smoothed like any camera, while
+//! the tracked head pose and the two eye cameras under it, `ipd` apart, follow the headset without any smoothing.
+//!
+//! `FollowTarget` keeps a camera's target on an entity while any controller orbits or looks around it, and
+//! `FollowTarget::switch_to` hands the camera off to another entity along an eased transition, optionally arcing the
+//! eye over the geometry in between, e.g. for spectator modes cycling between players.
+//!
+//! A `SpringArm` pulls a third-person camera in front of whatever its `CameraObstruction` finds between it and the
+//! target. It retracts quickly, and extends slowly once the way is clear, after a hold time and with some hysteresis,
+//! so it doesn't oscillate near thin obstacles.
+//!
+//! While something blocks a spring arm, it sends `SpringArmOccluded` events with the obstructing entity, when the
+//! `ObstructionProvider` implements `sweep_hit`, and how much of the arm is blocked, so games can fade or dither it.
+//! With the `obstruction_fade` feature, the `ObstructionFadePlugin` fades the entity's `StandardMaterial`.
+//!
+//! `ScreenSpaceLimits` keeps a rectangle of the ground covering at least a given fraction of the view, limiting zoom
+//! and panning of top-down cameras in screen space rather than world units, so the limits follow zoom and window
+//! resizes.
+//!
+//! `CameraTourPlugin` plays a `CameraTour`: it frames a list of entities or bookmarked `LookTransform`s one after the
+//! other, with a configurable dwell time and transition easing, for product configurators and kiosks.
+//!
+//! `CinematicPlugin` takes a camera away from the player for a cutscene on `CinematicEvent::Start`: it blocks
+//! controller input, plays a `CameraSequence` or the `CameraPath` on the camera, and shows letterbox bars with the
+//! `letterbox` feature. When the cinematic ends, the `LookTransform`, `Smoother` and controllers are restored as they
+//! were.
+//!
+//! With the `depth_of_field` feature, `DepthOfFieldFocusPlugin` keeps a smoothed `FocalDistance` on the target of each
+//! camera with a `DepthOfFieldFocus`, or on a tracked entity. Bevy has no depth of field pass yet, so this is for a
+//! post-process pass of your own to read.
+//!
+//! The `LookTransform`, the `Smoother` and all of the controllers are reflected, so camera rigs can be saved in a
+//! `DynamicScene` and reloaded mid-motion, smoothing included. Outside of scenes, `Smoother::snapshot` captures the
+//! same state with serde.
+//!
+//! To let users pick their navigation style at runtime, add `SmoothCamerasPlugins` instead of each plugin: it adds the
+//! `LookTransformPlugin` and every controller plugin, which can be configured with `set` or left out with `disable`.
+//!
+//! On touch screens, `FpsCameraPlugin::with_touch_joystick(true)` adds a virtual stick on the left half of the window
+//! for moving, and dragging on the right half looks around. Zones and sensitivities are set in the `TouchJoystick`
+//! resource.
+//!
+//! For split screen, give each FPS camera a `SplitScreenPlayer` with its own input device and add the
+//! `SplitScreenPlugin`, which lays out the viewports. Input for one camera is sent as a `fps::CameraControlEvent`.
 
 pub mod controllers;
+pub mod test_utils;
 
+mod camera_command;
+mod camera_debug;
+mod camera_listener;
+mod camera_mirror;
+mod camera_path;
+mod camera_state_machine;
+mod camera_telemetry;
+mod camera_tour;
+mod cinematic;
+#[cfg(feature = "config")]
+mod config;
+#[cfg(feature = "depth_of_field")]
+mod depth_of_field;
+mod dolly_zoom;
+mod floating_origin;
+mod follow_target;
+mod fov_zoom;
+#[cfg(feature = "gyroscope")]
+mod gyroscope;
+mod globe;
+mod gltf_rig;
+mod ground_height;
+mod handheld_noise;
+mod head_bob;
+#[cfg(feature = "inspector")]
+mod inspector;
+mod input_filter;
+mod input_recording;
+mod keyboard_navigation;
 mod look_angles;
 mod look_transform;
+mod look_transform_commands;
+mod look_transform_locks;
+mod minimap;
+mod obstruction;
+#[cfg(feature = "obstruction_fade")]
+mod obstruction_fade;
+#[cfg(feature = "picking")]
+mod picking;
+mod quad_viewport_rig;
+mod ray_hits;
+#[cfg(feature = "remote_control")]
+mod remote_control;
+mod replay_camera;
+mod response_curve;
+mod screen_space_limits;
+mod sequence;
+mod shoulder_offset;
+mod smooth_cameras_plugins;
+#[cfg(feature = "space_mouse")]
+mod space_mouse;
+mod split_screen;
+mod spring_arm;
+mod stereo_rig;
+mod touch_joystick;
+mod touchpad_gesture;
+mod view_history;
+mod view_locked;
 
+pub use camera_command::*;
+pub use camera_debug::*;
+pub use camera_listener::*;
+pub use camera_mirror::*;
+pub use camera_path::*;
+pub use camera_state_machine::*;
+pub use camera_telemetry::*;
+pub use camera_tour::*;
+pub use cinematic::*;
+#[cfg(feature = "config")]
+pub use config::*;
+#[cfg(feature = "depth_of_field")]
+pub use depth_of_field::*;
+pub use dolly_zoom::*;
+pub use floating_origin::*;
+pub use follow_target::*;
+pub use fov_zoom::*;
+#[cfg(feature = "gyroscope")]
+pub use gyroscope::*;
+pub use globe::*;
+pub use gltf_rig::*;
+pub use ground_height::*;
+pub use handheld_noise::*;
+pub use head_bob::*;
+#[cfg(feature = "inspector")]
+pub use inspector::*;
+pub use input_filter::*;
+pub use input_recording::*;
+pub use keyboard_navigation::*;
 pub use look_angles::*;
 pub use look_transform::*;
+pub use look_transform_commands::*;
+pub use look_transform_locks::*;
+pub use minimap::*;
+pub use obstruction::*;
+#[cfg(feature = "obstruction_fade")]
+pub use obstruction_fade::*;
+#[cfg(feature = "picking")]
+pub use picking::*;
+pub use quad_viewport_rig::*;
+pub use ray_hits::*;
+#[cfg(feature = "remote_control")]
+pub use remote_control::*;
+pub use replay_camera::*;
+pub use response_curve::*;
+pub use screen_space_limits::*;
+pub use sequence::*;
+pub use shoulder_offset::*;
+pub use smooth_cameras_plugins::*;
+#[cfg(feature = "space_mouse")]
+pub use space_mouse::*;
+pub use split_screen::*;
+pub use spring_arm::*;
+pub use stereo_rig::*;
+pub use touch_joystick::*;
+pub use touchpad_gesture::*;
+pub use view_history::*;
+pub use view_locked::*;
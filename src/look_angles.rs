@@ -19,10 +19,20 @@ impl LookAngles {
         p
     }
 
+    /// Like `from_vector`, in a world whose up axis is `up` instead of +Y.
+    pub fn from_vector_with_up(v: DVec3, up: DVec3) -> Self {
+        Self::from_vector(up_axis_rotation(up).inverse() * v)
+    }
+
     pub fn unit_vector(self) -> DVec3 {
         unit_vector_from_yaw_and_pitch(self.yaw, self.pitch)
     }
 
+    /// Like `unit_vector`, in a world whose up axis is `up` instead of +Y.
+    pub fn unit_vector_with_up(self, up: DVec3) -> DVec3 {
+        up_axis_rotation(up) * self.unit_vector()
+    }
+
     pub fn set_direction(&mut self, v: DVec3) {
         let (yaw, pitch) = yaw_and_pitch_from_vector(v);
         self.set_yaw(yaw);
@@ -55,6 +65,28 @@ impl LookAngles {
         self.set_pitch(self.get_pitch() + delta);
     }
 
+    /// Keeps the pitch between `min` and `max` radians.
+    pub fn clamp_pitch(&mut self, min: f64, max: f64) {
+        self.set_pitch(self.pitch.min(max).max(min));
+    }
+
+    /// Keeps the yaw within the arc from `min` to `max` radians, moving it to the nearer end of the arc when outside.
+    pub fn clamp_yaw(&mut self, min: f64, max: f64) {
+        let wrap = |angle: f64| (angle + PI).rem_euclid(2.0 * PI) - PI;
+        let width = (max - min).rem_euclid(2.0 * PI);
+        let from_min = (self.yaw - min).rem_euclid(2.0 * PI);
+        if from_min <= width {
+            return;
+        }
+        // Outside the arc, compare the distance past its end with the distance before its start.
+        let yaw = if from_min - width < 2.0 * PI - from_min {
+            min + width
+        } else {
+            min
+        };
+        self.set_yaw(wrap(yaw));
+    }
+
     pub fn assert_not_looking_up(&self) {
         let is_looking_up = relative_eq!(self.unit_vector().dot(DVec3::Y).abs(), 1.0);
 
@@ -66,6 +98,12 @@ impl LookAngles {
     }
 }
 
+/// Returns the rotation from the +Y up frame that `LookAngles` work in to a world whose up axis is `up`. Yaw is about
+/// `up`, and a yaw of zero faces the direction +Z is rotated to.
+pub fn up_axis_rotation(up: DVec3) -> DQuat {
+    DQuat::from_rotation_arc(DVec3::Y, up.normalize())
+}
+
 /// Returns pitch and yaw angles that rotates z unit vector to v. The yaw is applied first to z about the y axis to get z'. Then
 /// the pitch is applied about some axis orthogonal to z' in the XZ plane to get v.
 fn yaw_and_pitch_from_vector(v: DVec3) -> (f64, f64) {
@@ -161,4 +199,30 @@ mod tests {
         assert_relative_eq!(yaw, -PI / 4.0, epsilon = 1e-6f64);
         assert_relative_eq!(pitch, -PI / 4.0);
     }
+
+    #[test]
+    fn test_clamp_yaw() {
+        let mut angles = LookAngles::default();
+        angles.set_yaw(PI / 2.0);
+        angles.clamp_yaw(-PI / 4.0, PI / 4.0);
+        assert_relative_eq!(angles.get_yaw(), PI / 4.0);
+
+        // An arc across the back, from 135 to -135 degrees.
+        angles.set_yaw(-PI / 2.0);
+        angles.clamp_yaw(3.0 * PI / 4.0, -3.0 * PI / 4.0);
+        assert_relative_eq!(angles.get_yaw(), -3.0 * PI / 4.0);
+
+        angles.set_yaw(PI);
+        angles.clamp_yaw(3.0 * PI / 4.0, -3.0 * PI / 4.0);
+        assert_relative_eq!(angles.get_yaw(), PI);
+    }
+
+    #[test]
+    fn test_z_up() {
+        let mut angles = LookAngles::from_vector_with_up(DVec3::new(1.0, 1.0, 1.0), DVec3::Z);
+        assert_relative_eq!(angles.get_pitch(), (1.0 / 3.0f64.sqrt()).asin(), epsilon = 1e-6);
+
+        angles.set_pitch(0.0);
+        assert_relative_eq!(angles.unit_vector_with_up(DVec3::Z).z, 0.0, epsilon = 1e-6);
+    }
 }
@@ -1,18 +1,121 @@
+use crate::{
+    controllers::{is_primary_window_unfocused, CameraControlSystem},
+    ground_clamp_system, look_transform_locks_system, screen_space_limits_system,
+};
+
 use bevy::{
     app::prelude::*,
     ecs::{bundle::Bundle, prelude::*},
     math::prelude::*,
-    transform::components::Transform,
+    reflect::{FromReflect, Reflect},
+    render::{
+        camera::{Camera, Projection},
+        primitives::Aabb,
+    },
+    transform::components::{GlobalTransform, Transform},
+    window::Windows,
 };
+use serde::{Deserialize, Serialize};
 
 pub struct LookTransformPlugin;
 
 impl Plugin for LookTransformPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(look_transform_system);
+        app.init_resource::<SmoothingSettings>()
+            .register_type::<DVec2>()
+            .register_type::<DVec3>()
+            .register_type::<LookTransform>()
+            .register_type::<Option<LookTransform>>()
+            .register_type::<Smoother>()
+            .register_type::<SmoothingMode>()
+            .register_type::<OrbitLagWeights>()
+            .register_type::<Option<OrbitLagWeights>>()
+            .register_type::<RadiusLagWeights>()
+            .register_type::<Option<RadiusLagWeights>>()
+            .add_system(
+                look_transform_locks_system
+                    .label(LookTransformSystem::Locks)
+                    .after(CameraControlSystem::Control)
+                    .before(LookTransformSystem::Smooth),
+            )
+            .add_system(
+                ground_clamp_system
+                    .label(LookTransformSystem::Locks)
+                    .after(look_transform_locks_system)
+                    .before(LookTransformSystem::Smooth),
+            )
+            .add_system(
+                screen_space_limits_system
+                    .label(LookTransformSystem::Locks)
+                    .after(ground_clamp_system)
+                    .before(LookTransformSystem::Smooth),
+            )
+            .add_system(look_transform_system.label(LookTransformSystem::Smooth))
+            .add_system(
+                transform_offset_system
+                    .label(LookTransformSystem::Offset)
+                    .after(LookTransformSystem::Smooth),
+            );
+    }
+}
+
+/// Global settings for the smoothing done by the `LookTransformPlugin`.
+#[derive(Clone, Copy, Debug, Default, Resource)]
+pub struct SmoothingSettings {
+    /// Smooth cameras in parallel, with this many cameras per task. `None` smooths all cameras on one thread, which is
+    /// cheaper unless there are many cameras.
+    pub parallel_batch_size: Option<usize>,
+    /// Stop smoothing while the primary window doesn't have focus, so cameras resume from where they were frozen.
+    pub freeze_when_unfocused: bool,
+    /// How the smoothed `LookTransform` is written into the `Transform`.
+    pub conversion: TransformConversion,
+}
+
+/// How the `LookTransformPlugin` writes a camera's `LookTransform` into its `Transform`. Everything is computed in `f64`,
+/// but positions far from the origin still lose precision once rendering converts them to `f32`, so large worlds can
+/// keep the `Transform`s near the origin instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TransformConversion {
+    /// Write the eye position as is.
+    #[default]
+    Direct,
+    /// Subtract this origin from the eye position, for scenes whose `Transform`s are relative to a render origin while
+    /// `LookTransform`s stay in world coordinates.
+    OriginOffset(DVec3),
+    /// Put the camera at the origin and only write its rotation, for scenes rendered relative to the camera.
+    CameraRelative,
+}
+
+impl TransformConversion {
+    pub fn transform(&self, look_transform: LookTransform) -> Transform {
+        let offset = match *self {
+            Self::Direct => DVec3::ZERO,
+            Self::OriginOffset(origin) => origin,
+            Self::CameraRelative => look_transform.eye,
+        };
+        LookTransform {
+            eye: look_transform.eye - offset,
+            target: look_transform.target - offset,
+            up: look_transform.up,
+        }
+        .into()
     }
 }
 
+/// Labels for ordering against the systems of the `LookTransformPlugin`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, SystemLabel)]
+pub enum LookTransformSystem {
+    /// Writes the smoothed `LookTransform` into the scene graph `Transform`. Systems that modify a `LookTransform` should
+    /// run before this, and systems that read the final `Transform` after it.
+    Smooth,
+    /// Enforces `LookTransformLocks`, `GroundClamp` and `ScreenSpaceLimits`, after the controllers and before
+    /// `Smooth`.
+    Locks,
+    /// Layers each `TransformOffset` onto the smoothed `Transform`. Systems that add to a `TransformOffset` should run
+    /// after `Smooth` and before this.
+    Offset,
+}
+
 #[derive(Bundle)]
 pub struct LookTransformBundle {
     pub transform: LookTransform,
@@ -21,21 +124,57 @@ pub struct LookTransformBundle {
 
 /// An eye and the target it's looking at. As a component, this can be modified in place of bevy's `Transform`, and the two will
 /// stay in sync.
-#[derive(Clone, Component, Copy, Debug, Resource)]
+#[derive(
+    Clone,
+    Component,
+    Copy,
+    Debug,
+    Deserialize,
+    FromReflect,
+    PartialEq,
+    Reflect,
+    Resource,
+    Serialize,
+)]
+#[reflect(Component)]
 pub struct LookTransform {
     pub eye: DVec3,
     pub target: DVec3,
+    /// The world's up axis, +Y by default. Controllers yaw around it and keep the camera upright relative to it.
+    #[serde(default = "default_up")]
+    pub up: DVec3,
+}
+
+fn default_up() -> DVec3 {
+    DVec3::Y
+}
+
+impl Default for LookTransform {
+    /// Looking down -Z from the origin, like a default `Transform`.
+    fn default() -> Self {
+        Self::new(DVec3::ZERO, -DVec3::Z)
+    }
 }
 
 impl From<LookTransform> for Transform {
     fn from(t: LookTransform) -> Self {
-        eye_look_at_target_transform(t.eye, t.target)
+        eye_look_at_target_transform(t.eye, t.target, t.up)
     }
 }
 
 impl LookTransform {
     pub fn new(eye: DVec3, target: DVec3) -> Self {
-        Self { eye, target }
+        Self {
+            eye,
+            target,
+            up: default_up(),
+        }
+    }
+
+    /// Use `up` as the world's up axis, for example `DVec3::Z` in Z-up CAD or GIS scenes.
+    pub fn with_up(mut self, up: DVec3) -> Self {
+        self.up = up;
+        self
     }
 
     pub fn radius(&self) -> f64 {
@@ -45,22 +184,343 @@ impl LookTransform {
     pub fn look_direction(&self) -> Option<DVec3> {
         (self.target - self.eye).try_normalize()
     }
+
+    /// The orientation of the camera, looking from the eye at the target with its +Y as close to `up` as possible.
+    pub fn rotation(&self) -> DQuat {
+        Transform::from(*self).rotation
+    }
+
+    /// Points the camera along `rotation`, keeping the eye and radius. `up` is set to the camera's +Y, so unlike going
+    /// through `LookAngles`, any orientation works, including looking straight up or down.
+    pub fn set_rotation(&mut self, rotation: DQuat) {
+        self.target = self.eye + self.radius() * (rotation * -DVec3::Z);
+        self.up = rotation * DVec3::Y;
+    }
+
+    /// The world-space ray through `viewport_position` (in logical pixels from the bottom left of the `camera`'s
+    /// viewport) for a camera at this transform, computed in `f64` from the eye rather than from the `f32`
+    /// `GlobalTransform`. Use the `SmoothedLookTransform` for the ray through what's on screen, or the
+    /// `LookTransform` for where the camera is heading.
+    pub fn viewport_to_world_ray(
+        &self,
+        camera: &Camera,
+        viewport_position: DVec2,
+    ) -> Option<CameraRay> {
+        let size = camera.logical_viewport_size()?.as_dvec2();
+        self.ray_from_ndc(
+            camera.projection_matrix().as_dmat4(),
+            2.0 * viewport_position / size - DVec2::ONE,
+        )
+    }
+
+    /// Where `point` appears in the `camera`'s viewport, in logical pixels from the bottom left, for a camera at this
+    /// transform. `None` if the point is behind the camera.
+    pub fn world_to_viewport(&self, camera: &Camera, point: DVec3) -> Option<DVec2> {
+        let size = camera.logical_viewport_size()?.as_dvec2();
+        let ndc = self.point_to_ndc(camera.projection_matrix().as_dmat4(), point)?;
+        Some((ndc + DVec2::ONE) / 2.0 * size)
+    }
+
+    /// Frames the world-space `aabb`, looking at its center from the current view direction, so all of it is in view.
+    /// `padding` grows the box by that fraction of its size first, to leave a margin.
+    pub fn fit_aabb(&self, aabb: &Aabb, projection: &Projection, padding: f64) -> AabbFit {
+        self.fit_bounds(
+            aabb.center.as_dvec3(),
+            aabb.half_extents.as_dvec3(),
+            projection,
+            padding,
+        )
+    }
+
+    /// Like `fit_aabb`, for a box given in f64, so it stays precise far from the origin.
+    pub fn fit_bounds(
+        &self,
+        center: DVec3,
+        half_extents: DVec3,
+        projection: &Projection,
+        padding: f64,
+    ) -> AabbFit {
+        let rotation = if self.look_direction().is_some() {
+            self.rotation()
+        } else {
+            DQuat::IDENTITY
+        };
+        let right = rotation * DVec3::X;
+        let screen_up = rotation * DVec3::Y;
+        let forward = rotation * -DVec3::Z;
+        let half_extents = (1.0 + padding.max(0.0)) * half_extents.abs();
+        // The corners in view space, relative to the center of the box.
+        let corners = (0..8).map(|i| {
+            let sign = |bit| if i & bit == 0 { -1.0 } else { 1.0 };
+            let corner = half_extents * DVec3::new(sign(1), sign(2), sign(4));
+            DVec3::new(
+                corner.dot(right).abs(),
+                corner.dot(screen_up).abs(),
+                corner.dot(forward),
+            )
+        });
+        // Far enough back that the nearest corner is past the near plane.
+        let depth = |near: f32| corners.clone().map(|c| -c.z).fold(0.0, f64::max) + near as f64;
+
+        let (distance, orthographic_scale) = match projection {
+            Projection::Perspective(perspective) => {
+                let tan_y = (perspective.fov as f64 / 2.0).tan();
+                let tan_x = tan_y * perspective.aspect_ratio as f64;
+                let distance = corners
+                    .clone()
+                    .map(|c| (c.x / tan_x).max(c.y / tan_y) - c.z)
+                    .fold(0.0, f64::max);
+                (distance.max(depth(perspective.near)), None)
+            }
+            Projection::Orthographic(orthographic) => {
+                // The extents of the view at a scale of 1.
+                let half_width = (orthographic.right - orthographic.left) as f64 / 2.0;
+                let half_height = (orthographic.top - orthographic.bottom) as f64 / 2.0;
+                let scale = corners
+                    .clone()
+                    .map(|c| (c.x / half_width).max(c.y / half_height))
+                    .fold(0.0, f64::max);
+                let scale = (scale.is_finite() && scale > 0.0).then_some(scale as f32);
+                (self.radius().max(depth(orthographic.near)), scale)
+            }
+        };
+
+        AabbFit {
+            transform: LookTransform {
+                eye: center - distance * forward,
+                target: center,
+                up: self.up,
+            },
+            orthographic_scale,
+        }
+    }
+
+    fn ray_from_ndc(&self, projection: DMat4, ndc: DVec2) -> Option<CameraRay> {
+        // Bevy's projections use reversed depth, so the near plane is at 1 and infinity at 0.
+        let inverse = projection.inverse();
+        let near = inverse.project_point3(ndc.extend(1.0));
+        let far = inverse.project_point3(ndc.extend(f32::EPSILON as f64));
+        let rotation = self.rotation();
+        Some(CameraRay {
+            origin: self.eye + rotation * near,
+            direction: (rotation * (far - near)).try_normalize()?,
+        })
+    }
+
+    fn point_to_ndc(&self, projection: DMat4, point: DVec3) -> Option<DVec2> {
+        let local = self.rotation().inverse() * (point - self.eye);
+        let clip = projection * local.extend(1.0);
+        (clip.w > 0.0).then_some(clip.truncate().truncate() / clip.w)
+    }
+}
+
+/// Where a camera is now, as opposed to its `LookTransform`, which is where it's heading. Insert one, starting from the
+/// camera's `LookTransform`, and `LookTransformSystem::Smooth` keeps it up to date with the camera's `Smoother`. Without
+/// a `Smoother`, or while it's disabled, it follows the `LookTransform`.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SmoothedLookTransform(pub LookTransform);
+
+impl From<LookTransform> for SmoothedLookTransform {
+    fn from(transform: LookTransform) -> Self {
+        Self(transform)
+    }
+}
+
+/// A world-space ray from a camera, from `origin` along the unit vector `direction`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraRay {
+    pub origin: DVec3,
+    pub direction: DVec3,
+}
+
+impl CameraRay {
+    /// The point `distance` along the ray.
+    pub fn at(&self, distance: f64) -> DVec3 {
+        self.origin + distance * self.direction
+    }
+}
+
+/// How to frame a bounding box, from `LookTransform::fit_aabb`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AabbFit {
+    /// Looks at the center of the box from the same direction as before, far enough away to see all of it
+    pub transform: LookTransform,
+    /// For orthographic projections, the `OrthographicProjection::scale` at which the box fills the view
+    pub orthographic_scale: Option<f32>,
+}
+
+/// The world-space corners of the entity-space `aabb`, reduced to their minimum and maximum.
+pub(crate) fn world_aabb_min_max(aabb: &Aabb, global: &GlobalTransform) -> (DVec3, DVec3) {
+    let (center, half_extents) = (aabb.center.as_dvec3(), aabb.half_extents.as_dvec3());
+    let mut min = DVec3::splat(f64::INFINITY);
+    let mut max = DVec3::splat(f64::NEG_INFINITY);
+    for i in 0..8 {
+        let sign = |bit| if i & bit == 0 { -1.0 } else { 1.0 };
+        let corner = center + half_extents * DVec3::new(sign(1), sign(2), sign(4));
+        let corner = global.transform_point(corner);
+        min = min.min(corner);
+        max = max.max(corner);
+    }
+
+    (min, max)
 }
 
-fn eye_look_at_target_transform(eye: DVec3, target: DVec3) -> Transform {
+fn eye_look_at_target_transform(eye: DVec3, target: DVec3, up: DVec3) -> Transform {
     // If eye and target are very close, we avoid imprecision issues by keeping the look vector a unit vector.
     let look_vector = (target - eye).normalize();
     let look_at = eye + look_vector;
 
-    Transform::from_translation(eye).looking_at(look_at, DVec3::Y)
+    Transform::from_translation(eye).looking_at(look_at, up)
+}
+
+/// Procedural motion, like head bob, layered onto the smoothed `Transform` of a camera without feeding back into its
+/// `LookTransform` or controller. Effects add to it every frame between `LookTransformSystem::Smooth` and
+/// `LookTransformSystem::Offset`, which applies it and resets it.
+///
+/// The plugins of the effects in this crate, like `HeadBob`, `EyeHeight` and `SpringArm`, add a `TransformOffset` to
+/// their cameras automatically.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct TransformOffset {
+    /// Translation in the camera's local space
+    pub translation: DVec3,
+    /// Rotation in the camera's local space
+    pub rotation: DQuat,
+}
+
+impl Default for TransformOffset {
+    fn default() -> Self {
+        Self {
+            translation: DVec3::ZERO,
+            rotation: DQuat::IDENTITY,
+        }
+    }
+}
+
+impl TransformOffset {
+    /// Adds `translation`, in the camera's local space.
+    pub fn translate(&mut self, translation: DVec3) {
+        self.translation += translation;
+    }
+
+    /// Adds `rotation`, in the camera's local space.
+    pub fn rotate(&mut self, rotation: DQuat) {
+        self.rotation = self.rotation * rotation;
+    }
+}
+
+/// Adds a `TransformOffset` to entities with the effect component `C` that don't have one.
+pub(crate) fn add_transform_offset_system<C: Component>(
+    mut commands: Commands,
+    cameras: Query<Entity, (With<C>, Without<TransformOffset>)>,
+) {
+    for entity in cameras.iter() {
+        commands.entity(entity).insert(TransformOffset::default());
+    }
+}
+
+fn transform_offset_system(
+    settings: Res<SmoothingSettings>,
+    mut cameras: Query<(
+        &LookTransform,
+        Option<&Smoother>,
+        &mut TransformOffset,
+        &mut Transform,
+    )>,
+) {
+    for (look_transform, smoother, mut offset, mut transform) in cameras.iter_mut() {
+        let smoothed = smoother
+            .and_then(|s| s.smoothed_transform())
+            .unwrap_or(*look_transform);
+        let base = settings.conversion.transform(smoothed);
+        let new_transform = Transform {
+            translation: base.translation + base.rotation * offset.translation,
+            rotation: base.rotation * offset.rotation,
+            scale: base.scale,
+        };
+        // Avoid marking a settled camera's `Transform` as changed every frame.
+        if *transform != new_transform {
+            *transform = new_transform;
+        }
+        if *offset != TransformOffset::default() {
+            *offset = TransformOffset::default();
+        }
+    }
+}
+
+/// How a `Smoother` interpolates toward the latest `LookTransform`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub enum SmoothingMode {
+    /// Interpolate the eye and target positions. Fast rotations around the target cut across the orbit, briefly shrinking
+    /// the radius.
+    #[default]
+    Linear,
+    /// Interpolate the target position, the radius, and the direction from the target to the eye (with a quaternion
+    /// slerp) separately, so rotating around the target keeps a constant radius. Best for orbit cameras.
+    Orbit,
+}
+
+/// Separate lag weights for the parts of an orbit, used in `SmoothingMode::Orbit`.
+#[derive(Clone, Copy, Debug, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub struct OrbitLagWeights {
+    /// Distance from the target to the eye
+    pub radius: f64,
+    /// Direction from the target to the eye
+    pub rotation: f64,
+    /// Target position
+    pub target: f64,
+}
+
+impl OrbitLagWeights {
+    pub fn splat(lag_weight: f64) -> Self {
+        Self {
+            radius: lag_weight,
+            rotation: lag_weight,
+            target: lag_weight,
+        }
+    }
+}
+
+/// Lag weights that follow the distance from the eye to the target, so close-up inspection is crisp while distant
+/// navigation stays smooth. The weight is interpolated linearly between the near and far radii, and held beyond them.
+#[derive(Clone, Copy, Debug, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub struct RadiusLagWeights {
+    pub near_radius: f64,
+    /// Lag weight at and below `near_radius`
+    pub near: f64,
+    pub far_radius: f64,
+    /// Lag weight at and beyond `far_radius`
+    pub far: f64,
+}
+
+impl RadiusLagWeights {
+    pub fn lag_weight(&self, radius: f64) -> f64 {
+        let span = self.far_radius - self.near_radius;
+        if span <= 0.0 {
+            return if radius < self.far_radius { self.near } else { self.far };
+        }
+        let t = ((radius - self.near_radius) / span).clamp(0.0, 1.0);
+
+        self.near + (self.far - self.near) * t
+    }
 }
 
 /// Preforms exponential smoothing on a `LookTransform`. Set the `lag_weight` between `0.0` and `1.0`, where higher is smoother.
-#[derive(Component)]
+#[derive(Component, FromReflect, Reflect)]
+#[reflect(Component)]
 pub struct Smoother {
     lag_weight: f64,
     lerp_tfm: Option<LookTransform>,
     enabled: bool,
+    convergence_epsilon: f64,
+    mode: SmoothingMode,
+    orbit_lag_weights: Option<OrbitLagWeights>,
+    radius_lag_weights: Option<RadiusLagWeights>,
+}
+
+impl Default for Smoother {
+    fn default() -> Self {
+        Self::new(0.8)
+    }
 }
 
 impl Smoother {
@@ -69,9 +529,37 @@ impl Smoother {
             lag_weight,
             lerp_tfm: None,
             enabled: true,
+            convergence_epsilon: 1e-6,
+            mode: SmoothingMode::Linear,
+            orbit_lag_weights: None,
+            radius_lag_weights: None,
         }
     }
 
+    pub fn with_mode(mut self, mode: SmoothingMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn set_mode(&mut self, mode: SmoothingMode) {
+        self.mode = mode;
+    }
+
+    /// Smooth the radius, rotation and target with separate weights in `SmoothingMode::Orbit`. `None` uses the
+    /// `lag_weight` for all of them.
+    pub fn set_orbit_lag_weights(&mut self, weights: Option<OrbitLagWeights>) {
+        self.orbit_lag_weights = weights;
+    }
+
+    /// Pick the lag weight from the radius of the `LookTransform` being smoothed. `None` uses the `lag_weight`.
+    pub fn set_radius_lag_weights(&mut self, weights: Option<RadiusLagWeights>) {
+        self.radius_lag_weights = weights;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
     pub(crate) fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
         if self.enabled {
@@ -81,41 +569,372 @@ impl Smoother {
         }
     }
 
+    pub fn lag_weight(&self) -> f64 {
+        self.lag_weight
+    }
+
     pub fn set_lag_weight(&mut self, lag_weight: f64) {
         self.lag_weight = lag_weight;
     }
 
+    /// Once the smoothed eye and target are both within this distance of the `LookTransform`, they snap to it and the
+    /// camera is considered settled.
+    pub fn set_convergence_epsilon(&mut self, epsilon: f64) {
+        self.convergence_epsilon = epsilon;
+    }
+
+    /// Returns `true` if the smoothed transform has converged on `tfm`, so smoothing it again would have no effect.
+    pub fn is_settled(&self, tfm: &LookTransform) -> bool {
+        self.lerp_tfm.as_ref() == Some(tfm)
+    }
+
     pub fn smooth_transform(&mut self, new_tfm: &LookTransform) -> LookTransform {
-        debug_assert!(0.0 <= self.lag_weight);
-        debug_assert!(self.lag_weight < 1.0);
+        let lag_weight = self
+            .radius_lag_weights
+            .map_or(self.lag_weight, |w| w.lag_weight(new_tfm.radius()));
+        debug_assert!(0.0 <= lag_weight);
+        debug_assert!(lag_weight < 1.0);
 
         let old_lerp_tfm = self.lerp_tfm.unwrap_or(*new_tfm);
 
-        let lead_weight = 1.0 - self.lag_weight;
-        let lerp_tfm = LookTransform {
-            eye: old_lerp_tfm.eye * self.lag_weight + new_tfm.eye * lead_weight,
-            target: old_lerp_tfm.target * self.lag_weight + new_tfm.target * lead_weight,
+        let (target, eye) = match self.mode {
+            SmoothingMode::Linear => {
+                let lead_weight = 1.0 - lag_weight;
+                (
+                    old_lerp_tfm.target * lag_weight + new_tfm.target * lead_weight,
+                    old_lerp_tfm.eye * lag_weight + new_tfm.eye * lead_weight,
+                )
+            }
+            SmoothingMode::Orbit => {
+                let weights = self
+                    .orbit_lag_weights
+                    .unwrap_or_else(|| OrbitLagWeights::splat(lag_weight));
+                let target = old_lerp_tfm.target.lerp(new_tfm.target, 1.0 - weights.target);
+                let old_offset = old_lerp_tfm.eye - old_lerp_tfm.target;
+                let new_offset = new_tfm.eye - new_tfm.target;
+                let radius = old_offset.length() * weights.radius
+                    + new_offset.length() * (1.0 - weights.radius);
+                let direction = match (old_offset.try_normalize(), new_offset.try_normalize()) {
+                    (Some(old_dir), Some(new_dir)) => {
+                        let rotation = DQuat::from_rotation_arc(old_dir, new_dir);
+                        DQuat::IDENTITY.slerp(rotation, 1.0 - weights.rotation) * old_dir
+                    }
+                    _ => new_offset.normalize_or_zero(),
+                };
+                (target, target + radius * direction)
+            }
         };
+        let mut lerp_tfm = LookTransform {
+            eye,
+            target,
+            up: new_tfm.up,
+        };
+        if lerp_tfm.eye.distance_squared(new_tfm.eye) <= self.convergence_epsilon.powi(2)
+            && lerp_tfm.target.distance_squared(new_tfm.target) <= self.convergence_epsilon.powi(2)
+        {
+            lerp_tfm = *new_tfm;
+        }
 
         self.lerp_tfm = Some(lerp_tfm);
 
         lerp_tfm
     }
 
+    /// The most recent smoothed transform, if smoothing has run since the last reset.
+    pub fn smoothed_transform(&self) -> Option<LookTransform> {
+        self.lerp_tfm
+    }
+
     pub fn reset(&mut self) {
         self.lerp_tfm = None;
     }
+
+    /// The full state of this smoother, including smoothing in flight, to save and `restore` later.
+    pub fn snapshot(&self) -> SmootherSnapshot {
+        SmootherSnapshot {
+            lag_weight: self.lag_weight,
+            smoothed: self.lerp_tfm,
+            enabled: self.enabled,
+            convergence_epsilon: self.convergence_epsilon,
+            mode: self.mode,
+            orbit_lag_weights: self.orbit_lag_weights,
+            radius_lag_weights: self.radius_lag_weights,
+        }
+    }
+
+    /// Continues smoothing from a `snapshot`. Restore the `LookTransform` saved with it as well, and the camera picks up
+    /// exactly where it left off, without a pop or a sweep from wherever it was before.
+    pub fn restore(&mut self, snapshot: &SmootherSnapshot) {
+        *self = Self::from(*snapshot);
+    }
+
+    /// Moves the smoothed state by `offset`, so that moving a camera's `LookTransform` by the same offset doesn't smooth
+    /// across the jump.
+    pub fn translate(&mut self, offset: DVec3) {
+        if let Some(lerp_tfm) = self.lerp_tfm.as_mut() {
+            lerp_tfm.eye += offset;
+            lerp_tfm.target += offset;
+        }
+    }
+}
+
+/// The state of a `Smoother`, for game saves and scene switches. See `Smoother::snapshot`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SmootherSnapshot {
+    pub lag_weight: f64,
+    /// The smoothed transform, or `None` if smoothing hasn't run since the last reset
+    pub smoothed: Option<LookTransform>,
+    pub enabled: bool,
+    pub convergence_epsilon: f64,
+    pub mode: SmoothingMode,
+    pub orbit_lag_weights: Option<OrbitLagWeights>,
+    #[serde(default)]
+    pub radius_lag_weights: Option<RadiusLagWeights>,
+}
+
+impl From<SmootherSnapshot> for Smoother {
+    fn from(snapshot: SmootherSnapshot) -> Self {
+        Self {
+            lag_weight: snapshot.lag_weight,
+            lerp_tfm: snapshot.smoothed,
+            enabled: snapshot.enabled,
+            convergence_epsilon: snapshot.convergence_epsilon,
+            mode: snapshot.mode,
+            orbit_lag_weights: snapshot.orbit_lag_weights,
+            radius_lag_weights: snapshot.radius_lag_weights,
+        }
+    }
 }
 
 fn look_transform_system(
-    mut cameras: Query<(&LookTransform, &mut Transform, Option<&mut Smoother>)>,
+    settings: Res<SmoothingSettings>,
+    windows: Option<Res<Windows>>,
+    mut cameras: Query<(
+        &LookTransform,
+        &mut Transform,
+        Option<&mut Smoother>,
+        Option<&mut SmoothedLookTransform>,
+    )>,
 ) {
-    for (look_transform, mut scene_transform, smoother) in cameras.iter_mut() {
-        match smoother {
-            Some(mut s) if s.enabled => {
-                *scene_transform = s.smooth_transform(look_transform).into()
-            }
-            _ => (),
+    if settings.freeze_when_unfocused && is_primary_window_unfocused(windows.as_deref()) {
+        return;
+    }
+
+    // Settled cameras still need rewriting when the conversion changes.
+    let conversion = settings.conversion;
+    let rewrite_settled = settings.is_changed();
+    let smooth = |camera| smooth_camera(camera, conversion, rewrite_settled);
+    match settings.parallel_batch_size {
+        Some(batch_size) => cameras.par_for_each_mut(batch_size, smooth),
+        None => cameras.for_each_mut(smooth),
+    }
+}
+
+fn smooth_camera(
+    (look_transform, mut scene_transform, smoother, smoothed): (
+        &LookTransform,
+        Mut<Transform>,
+        Option<Mut<Smoother>>,
+        Option<Mut<SmoothedLookTransform>>,
+    ),
+    conversion: TransformConversion,
+    rewrite_settled: bool,
+) {
+    let current = match smoother {
+        // Skip settled cameras so their `Transform` isn't marked as changed every frame, unless the smoother was just
+        // restored or reset.
+        Some(s)
+            if s.enabled && !rewrite_settled && !s.is_changed() && s.is_settled(look_transform) =>
+        {
+            *look_transform
+        }
+        Some(mut s) if s.enabled => {
+            let current = s.smooth_transform(look_transform);
+            *scene_transform = conversion.transform(current);
+            current
+        }
+        _ => *look_transform,
+    };
+    if let Some(mut smoothed) = smoothed {
+        if smoothed.0 != current {
+            smoothed.0 = current;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smoother_settles() {
+        let mut smoother = Smoother::new(0.5);
+        let start = LookTransform::new(DVec3::ZERO, DVec3::Z);
+        let end = LookTransform::new(DVec3::X, DVec3::X + DVec3::Z);
+
+        smoother.smooth_transform(&start);
+        assert!(smoother.is_settled(&start));
+
+        let mut frames = 0;
+        while !smoother.is_settled(&end) {
+            smoother.smooth_transform(&end);
+            frames += 1;
+            assert!(frames < 100, "Smoother never settled");
+        }
+        assert_eq!(smoother.smoothed_transform(), Some(end));
+    }
+
+    #[test]
+    fn test_set_rotation_straight_up() {
+        let mut transform = LookTransform::new(DVec3::ZERO, DVec3::Z);
+        transform.set_rotation(DQuat::from_rotation_x(std::f64::consts::FRAC_PI_2));
+
+        assert!(transform.target.abs_diff_eq(DVec3::Y, 1e-9));
+        assert!(Transform::from(transform).rotation.is_finite());
+    }
+
+    #[test]
+    fn test_orbit_smoothing_keeps_radius() {
+        let mut smoother = Smoother::new(0.5).with_mode(SmoothingMode::Orbit);
+        smoother.smooth_transform(&LookTransform::new(DVec3::Z, DVec3::ZERO));
+
+        let smoothed = smoother.smooth_transform(&LookTransform::new(-DVec3::X, DVec3::ZERO));
+        assert!((smoothed.radius() - 1.0).abs() < 1e-9);
+        assert!(smoothed.eye.abs_diff_eq(
+            DVec3::new(-(0.5f64.sqrt()), 0.0, 0.5f64.sqrt()),
+            1e-9
+        ));
+    }
+
+    #[test]
+    fn test_orbit_lag_weights() {
+        let mut smoother = Smoother::new(0.5).with_mode(SmoothingMode::Orbit);
+        smoother.set_orbit_lag_weights(Some(OrbitLagWeights {
+            radius: 0.0,
+            rotation: 0.5,
+            target: 0.5,
+        }));
+        smoother.smooth_transform(&LookTransform::new(DVec3::Z, DVec3::ZERO));
+
+        let smoothed = smoother.smooth_transform(&LookTransform::new(-3.0 * DVec3::X, DVec3::ZERO));
+        assert!((smoothed.radius() - 3.0).abs() < 1e-9);
+        assert!(smoothed.eye.normalize().abs_diff_eq(
+            DVec3::new(-(0.5f64.sqrt()), 0.0, 0.5f64.sqrt()),
+            1e-9
+        ));
+    }
+
+    #[test]
+    fn test_radius_lag_weights() {
+        let weights = RadiusLagWeights {
+            near_radius: 1.0,
+            near: 0.0,
+            far_radius: 3.0,
+            far: 0.8,
         };
+        assert_eq!(weights.lag_weight(0.5), 0.0);
+        assert!((weights.lag_weight(2.0) - 0.4).abs() < 1e-9);
+        assert_eq!(weights.lag_weight(10.0), 0.8);
+
+        let mut smoother = Smoother::new(0.5);
+        smoother.set_radius_lag_weights(Some(weights));
+        smoother.smooth_transform(&LookTransform::new(DVec3::Z, DVec3::ZERO));
+        let smoothed = smoother.smooth_transform(&LookTransform::new(0.5 * DVec3::X, DVec3::ZERO));
+        assert_eq!(smoothed.eye, 0.5 * DVec3::X);
+    }
+
+    #[test]
+    fn test_translate_smoother() {
+        let mut smoother = Smoother::new(0.5);
+        smoother.smooth_transform(&LookTransform::new(DVec3::Z, DVec3::ZERO));
+        let offset = DVec3::new(-100.0, 0.0, 0.0);
+        smoother.translate(offset);
+
+        let smoothed =
+            smoother.smooth_transform(&LookTransform::new(DVec3::Z + offset, DVec3::ZERO + offset));
+        assert!(smoothed.eye.abs_diff_eq(DVec3::Z + offset, 1e-9));
+    }
+
+    #[test]
+    fn test_fit_bounds() {
+        use bevy::render::camera::{OrthographicProjection, PerspectiveProjection};
+
+        let transform = LookTransform::new(10.0 * DVec3::Z, DVec3::ZERO);
+        let perspective = Projection::Perspective(PerspectiveProjection {
+            fov: std::f32::consts::FRAC_PI_2,
+            aspect_ratio: 1.0,
+            ..Default::default()
+        });
+        let fit = transform.fit_bounds(DVec3::X, DVec3::ONE, &perspective, 0.0);
+        // The near face is 1 unit in front of the center and as wide as it is far from the eye.
+        assert!(fit.transform.eye.abs_diff_eq(DVec3::new(1.0, 0.0, 2.0), 1e-9));
+        assert_eq!(fit.transform.target, DVec3::X);
+        assert_eq!(fit.orthographic_scale, None);
+
+        let orthographic = Projection::Orthographic(OrthographicProjection {
+            left: -2.0,
+            right: 2.0,
+            bottom: -1.0,
+            top: 1.0,
+            ..Default::default()
+        });
+        let fit = transform.fit_bounds(DVec3::ZERO, DVec3::ONE, &orthographic, 1.0);
+        assert_eq!(fit.orthographic_scale, Some(2.0));
+        assert_eq!(fit.transform.radius(), 10.0);
+    }
+
+    #[test]
+    fn test_viewport_ray_round_trip() {
+        let transform = LookTransform::new(DVec3::new(1e9, 2.0, 3.0), DVec3::new(1e9, 0.0, 0.0));
+        let projection = DMat4::perspective_infinite_reverse_rh(1.0, 1.5, 0.1);
+
+        let center = transform.ray_from_ndc(projection, DVec2::ZERO).unwrap();
+        assert!(center
+            .direction
+            .abs_diff_eq(transform.look_direction().unwrap(), 1e-9));
+
+        let ndc = DVec2::new(0.25, -0.5);
+        let ray = transform.ray_from_ndc(projection, ndc).unwrap();
+        let projected = transform.point_to_ndc(projection, ray.at(10.0)).unwrap();
+        assert!(projected.abs_diff_eq(ndc, 1e-6));
+        assert!(transform.point_to_ndc(projection, ray.at(-10.0)).is_none());
+    }
+
+    #[test]
+    fn test_smoother_snapshot() {
+        let start = LookTransform::new(DVec3::ZERO, DVec3::Z);
+        let end = LookTransform::new(DVec3::X, DVec3::X + DVec3::Z);
+        let mut smoother = Smoother::new(0.5).with_mode(SmoothingMode::Orbit);
+        smoother.smooth_transform(&start);
+        smoother.smooth_transform(&end);
+
+        let snapshot = smoother.snapshot();
+        let mut restored = Smoother::new(0.9);
+        restored.restore(&snapshot);
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(
+            restored.smooth_transform(&end),
+            smoother.smooth_transform(&end)
+        );
+    }
+
+    #[test]
+    fn test_transform_conversion() {
+        let origin = DVec3::new(1e9, 0.0, 0.0);
+        let look_transform = LookTransform::new(origin + DVec3::Z, origin);
+
+        let transform = TransformConversion::OriginOffset(origin).transform(look_transform);
+        assert_eq!(transform.translation, DVec3::Z);
+
+        let transform = TransformConversion::CameraRelative.transform(look_transform);
+        assert_eq!(transform.translation, DVec3::ZERO);
+        assert!((transform.rotation * -DVec3::Z).abs_diff_eq(-DVec3::Z, 1e-9));
     }
 }
@@ -0,0 +1,178 @@
+use crate::{LookAngles, LookTransform};
+
+use bevy::{
+    ecs::{
+        prelude::*,
+        system::{Command, EntityCommands},
+    },
+    math::prelude::*,
+};
+
+/// Imperative control over an entity's `LookTransform`, independent of any controller. Changes go through the
+/// `LookTransform`, so they are smoothed like any other motion.
+pub trait LookTransformCommands {
+    /// Point the camera at `target`, keeping the eye in place.
+    fn look_at_smooth(&mut self, target: DVec3) -> &mut Self;
+
+    /// Move the eye `distance` units toward the target (negative moves away), keeping the target in place.
+    fn dolly(&mut self, distance: f64) -> &mut Self;
+
+    /// Rotate the eye around the target by the given angles in radians.
+    fn orbit_by(&mut self, yaw: f64, pitch: f64) -> &mut Self;
+}
+
+impl<'w, 's, 'a> LookTransformCommands for EntityCommands<'w, 's, 'a> {
+    fn look_at_smooth(&mut self, target: DVec3) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(LookAtSmooth { entity, target });
+        self
+    }
+
+    fn dolly(&mut self, distance: f64) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(Dolly { entity, distance });
+        self
+    }
+
+    fn orbit_by(&mut self, yaw: f64, pitch: f64) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(OrbitBy { entity, yaw, pitch });
+        self
+    }
+}
+
+struct LookAtSmooth {
+    entity: Entity,
+    target: DVec3,
+}
+
+impl Command for LookAtSmooth {
+    fn write(self, world: &mut World) {
+        if let Some(mut transform) = world.get_mut::<LookTransform>(self.entity) {
+            transform.target = self.target;
+        }
+    }
+}
+
+struct Dolly {
+    entity: Entity,
+    distance: f64,
+}
+
+impl Command for Dolly {
+    fn write(self, world: &mut World) {
+        if let Some(mut transform) = world.get_mut::<LookTransform>(self.entity) {
+            if let Some(look_direction) = transform.look_direction() {
+                // Never dolly through the target.
+                let radius = (transform.radius() - self.distance).max(0.001);
+                transform.eye = transform.target - radius * look_direction;
+            }
+        }
+    }
+}
+
+struct OrbitBy {
+    entity: Entity,
+    yaw: f64,
+    pitch: f64,
+}
+
+impl Command for OrbitBy {
+    fn write(self, world: &mut World) {
+        if let Some(mut transform) = world.get_mut::<LookTransform>(self.entity) {
+            if let Some(look_direction) = transform.look_direction() {
+                let up = transform.up;
+                let mut look_angles = LookAngles::from_vector_with_up(-look_direction, up);
+                look_angles.add_yaw(self.yaw);
+                look_angles.add_pitch(self.pitch);
+                transform.eye =
+                    transform.target + transform.radius() * look_angles.unit_vector_with_up(up);
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    use approx::assert_relative_eq;
+    use bevy::{app::App, ecs::system::CommandQueue};
+
+    fn camera_app() -> (App, Entity) {
+        let mut app = headless_app();
+        let camera = app
+            .world
+            .spawn(LookTransform::new(-10.0 * DVec3::Z, DVec3::ZERO))
+            .id();
+        (app, camera)
+    }
+
+    fn run(app: &mut App, camera: Entity, command: impl FnOnce(&mut EntityCommands)) {
+        let mut queue = CommandQueue::default();
+        command(&mut Commands::new(&mut queue, &app.world).entity(camera));
+        queue.apply(&mut app.world);
+    }
+
+    #[test]
+    fn test_look_at_smooth() {
+        let (mut app, camera) = camera_app();
+        run(&mut app, camera, |c| {
+            c.look_at_smooth(DVec3::X);
+        });
+
+        let transform = look_transform(&app, camera);
+        assert_eq!(transform.eye, -10.0 * DVec3::Z);
+        assert_eq!(transform.target, DVec3::X);
+    }
+
+    #[test]
+    fn test_dolly() {
+        let (mut app, camera) = camera_app();
+        run(&mut app, camera, |c| {
+            c.dolly(2.0);
+        });
+        assert_relative_eq!(look_transform(&app, camera).eye.z, -8.0);
+
+        run(&mut app, camera, |c| {
+            c.dolly(-5.0);
+        });
+        assert_relative_eq!(look_transform(&app, camera).eye.z, -13.0);
+
+        // Never through the target.
+        run(&mut app, camera, |c| {
+            c.dolly(100.0);
+        });
+        let transform = look_transform(&app, camera);
+        assert_relative_eq!(transform.radius(), 0.001);
+        assert_eq!(transform.target, DVec3::ZERO);
+        assert!(transform.eye.z < 0.0);
+    }
+
+    #[test]
+    fn test_orbit_by() {
+        let (mut app, camera) = camera_app();
+        run(&mut app, camera, |c| {
+            c.orbit_by(std::f64::consts::FRAC_PI_2, 0.0);
+        });
+        let transform = look_transform(&app, camera);
+        assert_relative_eq!(transform.radius(), 10.0, epsilon = 1e-9);
+        assert_relative_eq!(transform.eye.x.abs(), 10.0, epsilon = 1e-9);
+        assert_eq!(transform.target, DVec3::ZERO);
+
+        run(&mut app, camera, |c| {
+            c.orbit_by(0.0, 0.5);
+        });
+        let transform = look_transform(&app, camera);
+        assert_relative_eq!(transform.radius(), 10.0, epsilon = 1e-9);
+        assert_relative_eq!(transform.eye.y.abs(), 10.0 * 0.5f64.sin(), epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::LookTransform;
+
+use bevy::{ecs::prelude::*, math::prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// Constrains a camera's `LookTransform` no matter what moves it. The `LookTransformPlugin` enforces the locks after the
+/// controllers run and before smoothing, so input can never push the camera out of them.
+#[derive(Clone, Component, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct LookTransformLocks {
+    /// Keep the eye at this Y coordinate
+    pub eye_y: Option<f64>,
+    /// Keep the target at this Y coordinate
+    pub target_y: Option<f64>,
+    /// Keep the eye on this plane, moving the target along with it so the view direction is unchanged
+    pub plane: Option<LockPlane>,
+}
+
+/// A plane of points `p` where `normal.dot(p) == distance`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LockPlane {
+    pub normal: DVec3,
+    pub distance: f64,
+}
+
+impl LockPlane {
+    /// The plane through `point` perpendicular to `normal`.
+    pub fn new(point: DVec3, normal: DVec3) -> Self {
+        let normal = normal.normalize();
+        Self {
+            normal,
+            distance: normal.dot(point),
+        }
+    }
+}
+
+impl LookTransformLocks {
+    /// Locks the eye and target heights at their current values in `transform`.
+    pub fn freeze_heights(transform: &LookTransform) -> Self {
+        Self {
+            eye_y: Some(transform.eye.y),
+            target_y: Some(transform.target.y),
+            plane: None,
+        }
+    }
+
+    /// Keeps the eye on the plane through its current position in `transform`, perpendicular to `normal`.
+    pub fn lock_to_plane(transform: &LookTransform, normal: DVec3) -> Self {
+        Self {
+            plane: Some(LockPlane::new(transform.eye, normal)),
+            ..Default::default()
+        }
+    }
+
+    /// Returns `transform` with the locks applied.
+    pub fn apply(&self, mut transform: LookTransform) -> LookTransform {
+        if let Some(plane) = self.plane {
+            let correction = (plane.distance - plane.normal.dot(transform.eye)) * plane.normal;
+            transform.eye += correction;
+            transform.target += correction;
+        }
+        if let Some(y) = self.eye_y {
+            transform.eye.y = y;
+        }
+        if let Some(y) = self.target_y {
+            transform.target.y = y;
+        }
+        transform
+    }
+
+    /// Moves the locked heights and plane by `offset`, e.g. when the `FloatingOriginPlugin` shifts the scene.
+    pub fn translate(&mut self, offset: DVec3) {
+        if let Some(y) = self.eye_y.as_mut() {
+            *y += offset.y;
+        }
+        if let Some(y) = self.target_y.as_mut() {
+            *y += offset.y;
+        }
+        if let Some(plane) = self.plane.as_mut() {
+            plane.distance += plane.normal.dot(offset);
+        }
+    }
+}
+
+pub(crate) fn look_transform_locks_system(
+    mut cameras: Query<(&LookTransformLocks, &mut LookTransform)>,
+) {
+    for (locks, mut transform) in cameras.iter_mut() {
+        let locked = locks.apply(*transform);
+        // Only write when something moved, so settled cameras stay unchanged.
+        if locked != *transform {
+            *transform = locked;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locks() {
+        let start = LookTransform::new(DVec3::new(0.0, 10.0, 5.0), DVec3::ZERO);
+        let moved = LookTransform::new(DVec3::new(3.0, 12.0, 7.0), DVec3::new(1.0, 1.0, 1.0));
+
+        let locked = LookTransformLocks::freeze_heights(&start).apply(moved);
+        assert_eq!(locked.eye, DVec3::new(3.0, 10.0, 7.0));
+        assert_eq!(locked.target, DVec3::new(1.0, 0.0, 1.0));
+
+        let locked = LookTransformLocks::lock_to_plane(&start, DVec3::Z).apply(moved);
+        assert_eq!(locked.eye, DVec3::new(3.0, 12.0, 5.0));
+        assert_eq!(locked.target, DVec3::new(1.0, 1.0, -1.0));
+    }
+
+    #[test]
+    fn test_translate_locks() {
+        let start = LookTransform::new(DVec3::new(0.0, 10.0, 5.0), DVec3::ZERO);
+        let offset = DVec3::new(-100.0, -20.0, -50.0);
+        let moved = LookTransform::new(start.eye + offset, start.target + offset);
+
+        let mut locks = LookTransformLocks::freeze_heights(&start);
+        locks.translate(offset);
+        assert_eq!(locks.apply(moved), moved);
+
+        let mut locks = LookTransformLocks::lock_to_plane(&start, DVec3::Z);
+        locks.translate(offset);
+        assert_eq!(locks.apply(moved), moved);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{up_axis_rotation, LookTransform, LookTransformSystem};
+
+use bevy::{
+    app::prelude::*,
+    ecs::prelude::*,
+    math::prelude::*,
+    render::camera::{Projection, ScalingMode},
+    transform::components::Transform,
+};
+
+/// Moves every `MinimapCamera` over the camera it follows, after smoothing.
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(minimap_camera_system.after(LookTransformSystem::Smooth));
+    }
+}
+
+/// A top-down companion camera, such as a minimap or picture-in-picture view, kept centered over the `LookTransform`
+/// target of another camera.
+///
+/// Add it to a camera with an orthographic `Projection`, and render that to a corner of the window with its
+/// `Camera::viewport`, or to an image with its `Camera::target`.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct MinimapCamera {
+    /// The camera to follow
+    pub follow: Entity,
+    /// Distance above the target, along the followed camera's `LookTransform::up`
+    pub height: f64,
+    /// Half the height of the area shown, in world units
+    pub zoom: f32,
+    /// Turn the map so that the followed camera's view direction points up on it. Otherwise -Z (in the world rotated to
+    /// the up axis) points up.
+    pub rotate_with_view: bool,
+}
+
+impl MinimapCamera {
+    pub fn new(follow: Entity) -> Self {
+        Self {
+            follow,
+            height: 50.0,
+            zoom: 20.0,
+            rotate_with_view: false,
+        }
+    }
+
+    /// The minimap camera's transform for the followed camera's `transform`.
+    pub fn transform(&self, transform: &LookTransform) -> Transform {
+        let up = transform.up;
+        let north = up_axis_rotation(up) * -DVec3::Z;
+        let screen_up = transform
+            .look_direction()
+            .filter(|_| self.rotate_with_view)
+            .and_then(|dir| (dir - dir.dot(up) * up).try_normalize())
+            .unwrap_or(north);
+        Transform::from_translation(transform.target + self.height * up)
+            .looking_at(transform.target, screen_up)
+    }
+}
+
+fn minimap_camera_system(
+    followed: Query<&LookTransform>,
+    mut minimaps: Query<(&MinimapCamera, &mut Transform, Option<&mut Projection>)>,
+) {
+    for (minimap, mut transform, projection) in minimaps.iter_mut() {
+        let look_transform = if let Ok(look_transform) = followed.get(minimap.follow) {
+            look_transform
+        } else {
+            continue;
+        };
+        let new_transform = minimap.transform(look_transform);
+        if *transform != new_transform {
+            *transform = new_transform;
+        }
+
+        if let Some(mut projection) = projection {
+            let needs_update = match projection.as_ref() {
+                Projection::Orthographic(orthographic) => orthographic.scale != minimap.zoom,
+                Projection::Perspective(_) => false,
+            };
+            if needs_update {
+                if let Projection::Orthographic(orthographic) = projection.as_mut() {
+                    orthographic.scale = minimap.zoom;
+                    orthographic.scaling_mode = ScalingMode::FixedVertical(2.0);
+                }
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimap_transform() {
+        let followed = LookTransform::new(DVec3::new(0.0, 2.0, 0.0), DVec3::new(10.0, 0.0, 0.0));
+        let mut minimap = MinimapCamera::new(Entity::from_raw(0));
+
+        let transform = minimap.transform(&followed);
+        assert!(transform.translation.abs_diff_eq(DVec3::new(10.0, 50.0, 0.0), 1e-9));
+        assert!((transform.rotation * DVec3::Y).abs_diff_eq(-DVec3::Z, 1e-9));
+
+        minimap.rotate_with_view = true;
+        let transform = minimap.transform(&followed);
+        assert!((transform.rotation * DVec3::Y).abs_diff_eq(DVec3::X, 1e-9));
+    }
+}
@@ -0,0 +1,65 @@
+use bevy::{ecs::prelude::*, math::prelude::*};
+
+use std::sync::Arc;
+
+/// Scene geometry that can block camera movement.
+///
+/// Like `CameraRayHits`, this crate doesn't do any collision detection itself. Implement this on top of your physics or
+/// picking system; closures with the same signature as `sweep` implement it too.
+pub trait ObstructionProvider: Send + Sync + 'static {
+    /// Sweeps a sphere of `radius` from `from` to `to`, returning the fraction of the way it gets before hitting
+    /// geometry, between `0.0` and `1.0`, or `None` if the path is clear.
+    fn sweep(&self, from: DVec3, to: DVec3, radius: f64) -> Option<f64>;
+
+    /// Like `sweep`, but also returns the entity that was hit. Implement this for the `SpringArmOccluded` events to
+    /// name the obstructing entity; by default it's unknown.
+    fn sweep_hit(&self, from: DVec3, to: DVec3, radius: f64) -> Option<ObstructionHit> {
+        self.sweep(from, to, radius).map(|fraction| ObstructionHit {
+            fraction,
+            entity: None,
+        })
+    }
+}
+
+/// Where a sweep hit geometry, from `ObstructionProvider::sweep_hit`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObstructionHit {
+    /// Fraction of the way the sphere got before hitting, between `0.0` and `1.0`
+    pub fraction: f64,
+    /// The entity that was hit, if known
+    pub entity: Option<Entity>,
+}
+
+impl<F> ObstructionProvider for F
+where
+    F: Fn(DVec3, DVec3, f64) -> Option<f64> + Send + Sync + 'static,
+{
+    fn sweep(&self, from: DVec3, to: DVec3, radius: f64) -> Option<f64> {
+        self(from, to, radius)
+    }
+}
+
+/// Add to a camera so that controllers which support collision stop its eye at geometry.
+#[derive(Clone, Component)]
+pub struct CameraObstruction {
+    pub provider: Arc<dyn ObstructionProvider>,
+    /// Radius of the sphere swept along the eye's path.
+    pub radius: f64,
+}
+
+impl CameraObstruction {
+    pub fn new(provider: impl ObstructionProvider, radius: f64) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            radius,
+        }
+    }
+
+    /// Returns how far the eye can actually move from `from` toward `to`.
+    pub fn resolve(&self, from: DVec3, to: DVec3) -> DVec3 {
+        match self.provider.sweep(from, to, self.radius) {
+            Some(fraction) => from.lerp(to, fraction.clamp(0.0, 1.0)),
+            None => to,
+        }
+    }
+}
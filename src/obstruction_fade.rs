@@ -0,0 +1,183 @@
+use crate::{LookTransformSystem, SpringArmOccluded};
+
+use bevy::{
+    app::prelude::*,
+    asset::{Assets, Handle},
+    ecs::prelude::*,
+    pbr::{AlphaMode, StandardMaterial},
+    time::Time,
+    utils::HashSet,
+};
+
+/// Fades the `StandardMaterial` of entities named by `SpringArmOccluded` events while they block a camera, as a
+/// reference for fading or dithering obstructions in your own materials.
+pub struct ObstructionFadePlugin;
+
+impl Plugin for ObstructionFadePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ObstructionFade>()
+            .add_event::<SpringArmOccluded>()
+            .add_system(obstruction_fade_system.after(LookTransformSystem::Offset));
+    }
+}
+
+/// Settings for the `ObstructionFadePlugin`.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct ObstructionFade {
+    /// Alpha of obstructing materials
+    pub faded_alpha: f32,
+    /// How quickly materials fade out and back in, per second
+    pub rate: f32,
+}
+
+impl Default for ObstructionFade {
+    fn default() -> Self {
+        Self {
+            faded_alpha: 0.3,
+            rate: 8.0,
+        }
+    }
+}
+
+/// Present on an obstruction while it's faded. Its material is replaced with a copy, so other entities sharing the
+/// material aren't faded, and the original is restored once it has faded back in.
+#[derive(Clone, Component, Debug)]
+pub struct FadedObstruction {
+    original: Handle<StandardMaterial>,
+    alpha: f32,
+}
+
+fn obstruction_fade_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<ObstructionFade>,
+    mut events: EventReader<SpringArmOccluded>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut faded: Query<(Entity, &mut FadedObstruction, &mut Handle<StandardMaterial>)>,
+    unfaded: Query<&Handle<StandardMaterial>, Without<FadedObstruction>>,
+) {
+    let obstructions: HashSet<Entity> = events.iter().filter_map(|e| e.entity).collect();
+
+    for &entity in obstructions.iter() {
+        let original = if let Ok(original) = unfaded.get(entity) {
+            original
+        } else {
+            continue;
+        };
+        let copy = if let Some(material) = materials.get(original) {
+            StandardMaterial {
+                alpha_mode: AlphaMode::Blend,
+                ..material.clone()
+            }
+        } else {
+            continue;
+        };
+        let alpha = copy.base_color.a();
+        commands.entity(entity).insert((
+            materials.add(copy),
+            FadedObstruction {
+                original: original.clone(),
+                alpha,
+            },
+        ));
+    }
+
+    let blend = 1.0 - (-settings.rate * time.delta_seconds()).exp();
+    for (entity, mut fade, mut handle) in faded.iter_mut() {
+        let original_alpha = materials
+            .get(&fade.original)
+            .map_or(1.0, |m| m.base_color.a());
+        let target_alpha = if obstructions.contains(&entity) {
+            settings.faded_alpha.min(original_alpha)
+        } else {
+            original_alpha
+        };
+        fade.alpha += (target_alpha - fade.alpha) * blend;
+
+        if !obstructions.contains(&entity) && (fade.alpha - original_alpha).abs() < 1e-3 {
+            // Faded back in, so put the shared material back.
+            materials.remove(&*handle);
+            *handle = fade.original.clone();
+            commands.entity(entity).remove::<FadedObstruction>();
+        } else if let Some(material) = materials.get_mut(&*handle) {
+            material.base_color.set_a(fade.alpha);
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    use bevy::asset::{AddAsset, AssetPlugin};
+    use std::{thread, time::Duration};
+
+    /// Steps with real time passing, since the fade follows `Time`.
+    fn step_slowly(app: &mut App) {
+        thread::sleep(Duration::from_millis(1));
+        app.update();
+    }
+
+    #[test]
+    fn test_obstruction_fade_restores_material() {
+        let mut app = headless_app();
+        app.add_plugin(AssetPlugin::default())
+            .add_asset::<StandardMaterial>()
+            .add_plugin(ObstructionFadePlugin)
+            .insert_resource(ObstructionFade {
+                faded_alpha: 0.3,
+                rate: 1000.0,
+            });
+        let original = app
+            .world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial::default());
+        let camera = app.world.spawn_empty().id();
+        let wall = app.world.spawn(original.clone()).id();
+
+        for _ in 0..20 {
+            send_control_event(
+                &mut app,
+                SpringArmOccluded {
+                    camera,
+                    entity: Some(wall),
+                    occlusion: 0.5,
+                },
+            );
+            step_slowly(&mut app);
+        }
+        let faded = app
+            .world
+            .get::<Handle<StandardMaterial>>(wall)
+            .unwrap()
+            .clone();
+        assert_ne!(faded, original);
+        let materials = app.world.resource::<Assets<StandardMaterial>>();
+        assert!(materials.get(&faded).unwrap().base_color.a() < 0.5);
+        // Other entities sharing the material aren't faded.
+        assert_eq!(materials.get(&original).unwrap().base_color.a(), 1.0);
+
+        // Once it stops blocking, it fades back in and gets its own material back.
+        for _ in 0..1000 {
+            if app.world.get::<FadedObstruction>(wall).is_none() {
+                break;
+            }
+            step_slowly(&mut app);
+        }
+        assert!(app.world.get::<FadedObstruction>(wall).is_none());
+        assert_eq!(
+            app.world.get::<Handle<StandardMaterial>>(wall),
+            Some(&original)
+        );
+        let materials = app.world.resource::<Assets<StandardMaterial>>();
+        assert!(materials.get(&faded).is_none());
+    }
+}
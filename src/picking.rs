@@ -0,0 +1,75 @@
+use crate::{controllers::CameraControlSystem, CameraRayHits};
+
+use bevy::{app::prelude::*, ecs::prelude::*};
+use bevy_mod_picking::{PickableMesh, PickingCamera};
+use bevy_mod_raycast::{DefaultRaycastingPlugin, RaycastMesh, RaycastSource};
+
+/// Fills the `CameraRayHits::cursor` and `cursor_normal` of every `PickingCamera` from its top pointer hit, so the
+/// cursor-dependent controller features work without any glue code. `CameraRayHits` is added to picking cameras that
+/// don't have one.
+///
+/// `CameraRayHits::center` is filled from a second ray cast along each picking camera's view direction against every
+/// `PickableMesh`. It doesn't send any picking events.
+pub struct PickingRayHitsPlugin;
+
+impl Plugin for PickingRayHitsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(DefaultRaycastingPlugin::<CenterRaycastSet>::default())
+            .add_system_to_stage(CoreStage::PreUpdate, add_camera_ray_hits_system)
+            .add_system_to_stage(CoreStage::PreUpdate, add_center_raycast_mesh_system)
+            .add_system(picking_ray_hits_system.before(CameraControlSystem::Input));
+    }
+}
+
+/// Raycasting set for the ray through the center of the viewport, kept apart from `bevy_mod_picking`'s set so it
+/// doesn't hover or select anything.
+#[derive(Clone, Debug)]
+pub struct CenterRaycastSet;
+
+fn add_camera_ray_hits_system(
+    mut commands: Commands,
+    cameras: Query<
+        (Entity, Option<&CameraRayHits>),
+        (
+            With<PickingCamera>,
+            Without<RaycastSource<CenterRaycastSet>>,
+        ),
+    >,
+) {
+    for (entity, hits) in cameras.iter() {
+        let mut camera = commands.entity(entity);
+        // Casts along the camera's -Z axis, through the center of the viewport.
+        camera.insert(RaycastSource::<CenterRaycastSet>::new_transform_empty());
+        if hits.is_none() {
+            camera.insert(CameraRayHits::default());
+        }
+    }
+}
+
+fn add_center_raycast_mesh_system(
+    mut commands: Commands,
+    meshes: Query<Entity, (With<PickableMesh>, Without<RaycastMesh<CenterRaycastSet>>)>,
+) {
+    for entity in meshes.iter() {
+        commands
+            .entity(entity)
+            .insert(RaycastMesh::<CenterRaycastSet>::default());
+    }
+}
+
+fn picking_ray_hits_system(
+    mut cameras: Query<(
+        &PickingCamera,
+        Option<&RaycastSource<CenterRaycastSet>>,
+        &mut CameraRayHits,
+    )>,
+) {
+    for (picking_camera, center, mut hits) in cameras.iter_mut() {
+        let top = picking_camera.intersect_top();
+        hits.cursor = top.map(|(_, intersection)| intersection.position().as_dvec3());
+        hits.cursor_normal = top.map(|(_, intersection)| intersection.normal().as_dvec3());
+        hits.center = center
+            .and_then(|center| center.intersect_top())
+            .map(|(_, intersection)| intersection.position().as_dvec3());
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{
+    controllers::{
+        unreal::{
+            orthographic_projection, UnrealCameraBundle, UnrealCameraController, UnrealViewport,
+        },
+        CameraControlSystem,
+    },
+    LookTransform, LookTransformSystem, SmoothingSettings,
+};
+
+use bevy::{
+    app::prelude::*,
+    core_pipeline::{
+        clear_color::ClearColorConfig,
+        core_3d::{Camera3d, Camera3dBundle},
+    },
+    ecs::prelude::*,
+    math::prelude::*,
+    render::camera::{Camera, PerspectiveProjection, Projection, Viewport},
+    transform::components::Transform,
+    window::Windows,
+};
+
+/// Keeps the panes of every `QuadViewportRig` laid out in the primary window, gives input to the pane under the cursor,
+/// and keeps the panes focused on the same point. Add it after the `UnrealCameraPlugin`.
+pub struct QuadViewportPlugin;
+
+impl Plugin for QuadViewportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(quad_viewport_layout_system)
+            .add_system(quad_viewport_activation_system.before(CameraControlSystem::Input))
+            .add_system(
+                quad_viewport_focus_system
+                    .after(CameraControlSystem::Control)
+                    .before(LookTransformSystem::Smooth),
+            );
+    }
+}
+
+/// An editor-style layout of four unreal cameras: perspective in the top left, and top, front and side orthographic
+/// views in the other quarters of the window. Spawn it with `QuadViewportRig::spawn`.
+#[derive(Clone, Component, Debug)]
+pub struct QuadViewportRig {
+    /// The point all panes look at. Moving the target of any pane moves the others along with it.
+    pub focus: DVec3,
+    /// The cameras, in the order perspective, top, front, side.
+    pub panes: [Entity; 4],
+}
+
+/// One of the cameras of a `QuadViewportRig`.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct QuadViewportPane {
+    pub rig: Entity,
+    /// Quarter of the window: 0 is top left, 1 top right, 2 bottom left and 3 bottom right.
+    pub quadrant: usize,
+}
+
+const PANE_VIEWPORTS: [UnrealViewport; 4] = [
+    UnrealViewport::Perspective,
+    UnrealViewport::Top,
+    UnrealViewport::Front,
+    UnrealViewport::Side,
+];
+
+impl QuadViewportRig {
+    /// Spawns the rig and its four cameras looking at `focus`. The perspective pane looks from `eye`, and the
+    /// orthographic panes from the same distance. Returns the rig entity.
+    pub fn spawn(
+        commands: &mut Commands,
+        controller: UnrealCameraController,
+        eye: DVec3,
+        focus: DVec3,
+    ) -> Entity {
+        let rig = commands.spawn_empty().id();
+        let radius = eye.distance(focus);
+        let panes = [0, 1, 2, 3].map(|quadrant| {
+            let viewport = PANE_VIEWPORTS[quadrant];
+            let projection = if viewport.is_orthographic() {
+                Projection::Orthographic(orthographic_projection(
+                    radius,
+                    PerspectiveProjection::default().fov,
+                ))
+            } else {
+                Projection::Perspective(Default::default())
+            };
+            commands
+                .spawn(Camera3dBundle {
+                    camera: Camera {
+                        // Render the panes in order, with only the first clearing the window.
+                        priority: quadrant as isize,
+                        ..Default::default()
+                    },
+                    camera_3d: Camera3d {
+                        clear_color: if quadrant == 0 {
+                            ClearColorConfig::Default
+                        } else {
+                            ClearColorConfig::None
+                        },
+                        ..Default::default()
+                    },
+                    projection,
+                    ..Default::default()
+                })
+                .insert(
+                    UnrealCameraBundle::new(
+                        UnrealCameraController {
+                            // Only the pane under the cursor is enabled.
+                            enabled: quadrant == 0,
+                            ..controller
+                        },
+                        eye,
+                        focus,
+                    )
+                    .with_viewport(viewport),
+                )
+                .insert(QuadViewportPane { rig, quadrant })
+                .id()
+        });
+        commands.entity(rig).insert(QuadViewportRig { focus, panes });
+        rig
+    }
+}
+
+fn quad_viewport_layout_system(
+    windows: Res<Windows>,
+    mut panes: Query<(&QuadViewportPane, &mut Camera)>,
+) {
+    let window = if let Some(window) = windows.get_primary() {
+        window
+    } else {
+        return;
+    };
+    let size = UVec2::new(window.physical_width(), window.physical_height()) / 2;
+    for (pane, mut camera) in panes.iter_mut() {
+        let position = UVec2::new(pane.quadrant as u32 % 2, pane.quadrant as u32 / 2) * size;
+        let unchanged = camera.viewport.as_ref().map_or(false, |viewport| {
+            viewport.physical_position == position && viewport.physical_size == size
+        });
+        if !unchanged {
+            camera.viewport = Some(Viewport {
+                physical_position: position,
+                physical_size: size,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+fn quad_viewport_activation_system(
+    windows: Res<Windows>,
+    mut panes: Query<(&QuadViewportPane, &mut UnrealCameraController)>,
+) {
+    let window = if let Some(window) = windows.get_primary() {
+        window
+    } else {
+        return;
+    };
+    let cursor = if let Some(cursor) = window.cursor_position() {
+        cursor
+    } else {
+        return;
+    };
+    // The cursor position starts at the bottom left, and the quadrants at the top left.
+    let column = (cursor.x >= window.width() / 2.0) as usize;
+    let row = (cursor.y < window.height() / 2.0) as usize;
+    let hovered = 2 * row + column;
+
+    for (pane, mut controller) in panes.iter_mut() {
+        let enabled = pane.quadrant == hovered;
+        // Only write on change, so the smoother isn't reset every frame.
+        if controller.enabled != enabled {
+            controller.enabled = enabled;
+        }
+    }
+}
+
+fn quad_viewport_focus_system(
+    settings: Res<SmoothingSettings>,
+    mut rigs: Query<&mut QuadViewportRig>,
+    mut panes: Query<
+        (&UnrealCameraController, &mut LookTransform, &mut Transform),
+        With<QuadViewportPane>,
+    >,
+) {
+    for mut rig in rigs.iter_mut() {
+        let moved_target = rig.panes.iter().find_map(|&pane| {
+            let (controller, transform, _) = panes.get(pane).ok()?;
+            (controller.enabled && transform.target != rig.focus).then(|| transform.target)
+        });
+        let new_focus = if let Some(target) = moved_target {
+            target
+        } else {
+            continue;
+        };
+        let offset = new_focus - rig.focus;
+        rig.focus = new_focus;
+
+        for &pane in rig.panes.iter() {
+            if let Ok((controller, mut transform, mut scene_transform)) = panes.get_mut(pane) {
+                if transform.target != new_focus {
+                    transform.eye += offset;
+                    transform.target += offset;
+                }
+                // The smoothers of disabled controllers are off, so move their cameras directly.
+                if !controller.enabled {
+                    *scene_transform = settings.conversion.transform(*transform);
+                }
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{controllers::unreal::UnrealCameraPlugin, test_utils::*, TransformConversion};
+
+    use bevy::{
+        ecs::system::CommandQueue,
+        window::{Window, WindowDescriptor, WindowId},
+    };
+
+    #[test]
+    fn test_quad_viewport_rig() {
+        let mut app = headless_app();
+        app.add_plugin(UnrealCameraPlugin::new(true))
+            .add_plugin(QuadViewportPlugin);
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+            800,
+            600,
+            1.0,
+            None,
+            None,
+        ));
+        app.insert_resource(windows);
+        let origin = DVec3::new(100.0, 0.0, 0.0);
+        app.world.resource_mut::<SmoothingSettings>().conversion =
+            TransformConversion::OriginOffset(origin);
+
+        let focus = DVec3::new(100.0, 0.0, 10.0);
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &app.world);
+        let rig = QuadViewportRig::spawn(
+            &mut commands,
+            UnrealCameraController::default(),
+            focus - 10.0 * DVec3::Z,
+            focus,
+        );
+        queue.apply(&mut app.world);
+        step(&mut app, 1);
+
+        let panes = app.world.get::<QuadViewportRig>(rig).unwrap().panes;
+        for (quadrant, &pane) in panes.iter().enumerate() {
+            let camera = app.world.get::<Camera>(pane).unwrap();
+            let viewport = camera.viewport.as_ref().unwrap();
+            let position = UVec2::new(quadrant as u32 % 2 * 400, quadrant as u32 / 2 * 300);
+            assert_eq!(viewport.physical_position, position);
+            assert_eq!(viewport.physical_size, UVec2::new(400, 300));
+            assert_eq!(camera.priority, quadrant as isize);
+            // Only the first pane clears the window.
+            let clear_color = &app.world.get::<Camera3d>(pane).unwrap().clear_color;
+            let clears = matches!(clear_color, ClearColorConfig::Default);
+            assert_eq!(clears, quadrant == 0);
+        }
+
+        // Moving the perspective pane moves the focus of the others, which are written with the conversion.
+        let mut transform = app.world.get_mut::<LookTransform>(panes[0]).unwrap();
+        transform.eye += DVec3::X;
+        transform.target += DVec3::X;
+        step(&mut app, 1);
+
+        let new_focus = app.world.get::<QuadViewportRig>(rig).unwrap().focus;
+        assert!(new_focus.abs_diff_eq(focus + DVec3::X, 1e-9));
+        for &pane in &panes[1..] {
+            let transform = look_transform(&app, pane);
+            assert!(transform.target.abs_diff_eq(new_focus, 1e-9));
+            let scene = scene_transform(&app, pane);
+            assert!(scene.translation.abs_diff_eq(transform.eye - origin, 1e-9));
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::{ecs::prelude::*, math::prelude::*};
+
+/// Where rays cast from a camera hit scene geometry this frame.
+///
+/// This crate doesn't do any raycasting itself. Fill this in from your own physics or picking system, before
+/// `CameraControlSystem::Input`, and controller features that depend on what's under the cursor or crosshair will use it.
+/// Leave a hit as `None` when the ray doesn't hit anything.
+#[derive(Clone, Component, Copy, Debug, Default)]
+pub struct CameraRayHits {
+    /// World-space hit along the view direction, through the center of the viewport.
+    pub center: Option<DVec3>,
+    /// World-space hit under the mouse cursor.
+    pub cursor: Option<DVec3>,
+    /// World-space unit normal of the surface at the `cursor` hit.
+    pub cursor_normal: Option<DVec3>,
+}
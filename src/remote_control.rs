@@ -0,0 +1,216 @@
+use crate::{controllers::CameraControlSystem, CameraCommand, CameraCommandPlugin, LookTransform};
+
+use bevy::{app::prelude::*, ecs::prelude::*, log::warn};
+use std::{
+    io::{ErrorKind, Read},
+    net::{SocketAddr, TcpListener, TcpStream},
+};
+
+/// Drives the camera from external tools, like Blender add-ons or test scripts, over a local TCP port.
+///
+/// Each line a client sends is a `CameraCommand` in RON, like `SetPos((0.0, 10.0, -5.0))` or
+/// `SetTransform((eye: (0.0, 10.0, -5.0), target: (0.0, 0.0, 0.0)))`, a bare `LookTransform` like
+/// `(eye: (0.0, 10.0, -5.0), target: (0.0, 0.0, 0.0))`, or with the `console_commands` feature a console command like
+/// `setpos 0 10 -5`. The commands are sent as events, so they drive whichever camera has an enabled controller. A
+/// client that sends more than `MAX_REMOTE_LINE_BYTES` without a newline is dropped. WebSocket clients need a bridge to
+/// plain TCP, such as `websocat`.
+///
+/// Anyone who can reach the port can move the camera, so keep it on the loopback interface outside of development.
+pub struct RemoteControlPlugin {
+    pub address: SocketAddr,
+}
+
+impl RemoteControlPlugin {
+    pub fn new(address: SocketAddr) -> Self {
+        Self { address }
+    }
+}
+
+impl Default for RemoteControlPlugin {
+    fn default() -> Self {
+        Self::new(SocketAddr::from(([127, 0, 0, 1], 7878)))
+    }
+}
+
+impl Plugin for RemoteControlPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<CameraCommandPlugin>() {
+            app.add_plugin(CameraCommandPlugin);
+        }
+
+        match bind(self.address) {
+            Ok(listener) => {
+                app.insert_resource(RemoteControlServer {
+                    listener,
+                    clients: Vec::new(),
+                })
+                .add_system(remote_control_system.before(CameraControlSystem::Control));
+            }
+            Err(error) => warn!(
+                "Failed to listen for remote control on {}: {}",
+                self.address, error
+            ),
+        }
+    }
+}
+
+fn bind(address: SocketAddr) -> std::io::Result<TcpListener> {
+    let listener = TcpListener::bind(address)?;
+    // Polled once a frame, so it must never block.
+    listener.set_nonblocking(true)?;
+    Ok(listener)
+}
+
+/// The longest line a remote client can send.
+pub const MAX_REMOTE_LINE_BYTES: usize = 64 * 1024;
+
+#[derive(Resource)]
+struct RemoteControlServer {
+    listener: TcpListener,
+    clients: Vec<RemoteClient>,
+}
+
+struct RemoteClient {
+    stream: TcpStream,
+    /// Bytes received after the last complete line
+    pending: Vec<u8>,
+}
+
+impl RemoteClient {
+    /// Reads whatever has arrived, returning the complete lines and whether the client is still connected.
+    fn read_lines(&mut self) -> (Vec<String>, bool) {
+        let mut buffer = [0; 4096];
+        let mut lines = Vec::new();
+        let connected = loop {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => break false,
+                Ok(n) => {
+                    self.pending.extend_from_slice(&buffer[..n]);
+                    lines.extend(take_lines(&mut self.pending));
+                    if self.pending.len() > MAX_REMOTE_LINE_BYTES {
+                        warn!(
+                            "Dropping remote control client that sent a line over {} bytes",
+                            MAX_REMOTE_LINE_BYTES
+                        );
+                        break false;
+                    }
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break true,
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(_) => break false,
+            }
+        };
+        (lines, connected)
+    }
+}
+
+/// Removes the complete lines from the front of `pending`.
+fn take_lines(pending: &mut Vec<u8>) -> Vec<String> {
+    let end = match pending.iter().rposition(|&b| b == b'\n') {
+        Some(end) => end,
+        None => return Vec::new(),
+    };
+    let lines = pending.drain(..=end).collect::<Vec<_>>();
+    String::from_utf8_lossy(&lines)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Parses one line from a remote client.
+fn parse_remote_command(line: &str) -> Result<CameraCommand, String> {
+    let ron_error = match ron::from_str(line) {
+        Ok(command) => return Ok(command),
+        Err(error) => error.to_string(),
+    };
+    if let Ok(transform) = ron::from_str::<LookTransform>(line) {
+        return Ok(CameraCommand::SetTransform(transform));
+    }
+    #[cfg(feature = "console_commands")]
+    if let Ok(command) = line.parse() {
+        return Ok(command);
+    }
+    Err(ron_error)
+}
+
+fn remote_control_system(
+    mut server: ResMut<RemoteControlServer>,
+    mut commands: EventWriter<CameraCommand>,
+) {
+    let server = &mut *server;
+    loop {
+        match server.listener.accept() {
+            Ok((stream, _)) => {
+                if stream.set_nonblocking(true).is_ok() {
+                    server.clients.push(RemoteClient {
+                        stream,
+                        pending: Vec::new(),
+                    });
+                }
+            }
+            Err(error) if error.kind() == ErrorKind::WouldBlock => break,
+            Err(error) => {
+                warn!("Failed to accept remote control client: {}", error);
+                break;
+            }
+        }
+    }
+
+    server.clients.retain_mut(|client| {
+        let (lines, connected) = client.read_lines();
+        for line in lines {
+            match parse_remote_command(&line) {
+                Ok(command) => commands.send(command),
+                Err(error) => warn!("Bad remote camera command `{}`: {}", line, error),
+            }
+        }
+        connected
+    });
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy::math::DVec3;
+
+    #[test]
+    fn test_parse_remote_lines() {
+        let mut pending =
+            b"SetPos((1.0, 2.0, 3.0))\n\nSetTransform((eye: (0.0, 0.0, 5.0), ".to_vec();
+        let lines = take_lines(&mut pending);
+        assert_eq!(lines, vec!["SetPos((1.0, 2.0, 3.0))".to_string()]);
+        assert_eq!(
+            parse_remote_command(&lines[0]),
+            Ok(CameraCommand::SetPos(DVec3::new(1.0, 2.0, 3.0)))
+        );
+
+        pending.extend_from_slice(b"target: (0.0, 0.0, 0.0)))\n");
+        let lines = take_lines(&mut pending);
+        assert!(pending.is_empty());
+        assert_eq!(
+            parse_remote_command(&lines[0]),
+            Ok(CameraCommand::SetTransform(LookTransform::new(
+                DVec3::new(0.0, 0.0, 5.0),
+                DVec3::ZERO
+            )))
+        );
+        assert_eq!(
+            parse_remote_command("(eye: (1.0, 0.0, 0.0), target: (0.0, 0.0, 0.0))"),
+            Ok(CameraCommand::SetTransform(LookTransform::new(
+                DVec3::X,
+                DVec3::ZERO
+            )))
+        );
+        assert!(parse_remote_command("Teleport").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{LookTransform, LookTransformSystem};
+
+use bevy::{
+    app::prelude::*, ecs::prelude::*, math::prelude::*, time::Time,
+    transform::components::GlobalTransform,
+};
+use std::collections::VecDeque;
+
+/// Records the position of every entity with a `TransformHistory`, and flies each `ReplayCamera` along the recorded
+/// path of its subject.
+pub struct ReplayCameraPlugin;
+
+impl Plugin for ReplayCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(transform_history_system).add_system(
+            replay_camera_system
+                .after(transform_history_system)
+                .before(LookTransformSystem::Smooth),
+        );
+    }
+}
+
+/// A ring buffer of the recent positions of this entity, from its `GlobalTransform`.
+#[derive(Clone, Component, Debug)]
+pub struct TransformHistory {
+    /// The most samples kept. The oldest are dropped first.
+    pub capacity: usize,
+    samples: VecDeque<(f64, DVec3)>,
+}
+
+impl Default for TransformHistory {
+    fn default() -> Self {
+        Self::new(600)
+    }
+}
+
+impl TransformHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Records `position` at `time` in seconds, which must not be earlier than the last sample.
+    pub fn push(&mut self, time: f64, position: DVec3) {
+        debug_assert!(self.samples.back().map_or(true, |s| s.0 <= time));
+        while self.samples.len() >= self.capacity.max(1) {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((time, position));
+    }
+
+    /// Moves every recorded position by `offset`, e.g. when the `FloatingOriginPlugin` shifts the scene.
+    pub fn translate(&mut self, offset: DVec3) {
+        for sample in self.samples.iter_mut() {
+            sample.1 += offset;
+        }
+    }
+
+    pub fn latest(&self) -> Option<DVec3> {
+        self.samples.back().map(|s| s.1)
+    }
+
+    /// Linearly interpolates the position at `time`, clamping to the recorded range.
+    pub fn sample(&self, time: f64) -> Option<DVec3> {
+        let first = self.samples.front()?;
+        let i = self.samples.partition_point(|s| s.0 <= time);
+        if i == 0 {
+            return Some(first.1);
+        }
+        if i == self.samples.len() {
+            return Some(self.samples[i - 1].1);
+        }
+        let (a, b) = (self.samples[i - 1], self.samples[i]);
+        Some(a.1.lerp(b.1, (time - a.0) / (b.0 - a.0)))
+    }
+
+    /// The average position over `window` seconds centered on `time`, which smooths out jitter and sharp turns.
+    pub fn sample_smoothed(&self, time: f64, window: f64) -> Option<DVec3> {
+        const STEPS: usize = 8;
+        if window <= 0.0 {
+            return self.sample(time);
+        }
+        let mut sum = DVec3::ZERO;
+        for step in 0..STEPS {
+            let t = time - window / 2.0 + window * step as f64 / (STEPS - 1) as f64;
+            sum += self.sample(t)?;
+        }
+        Some(sum / STEPS as f64)
+    }
+}
+
+/// Flies along the recorded path of `subject` a fixed `delay` behind it, while looking at where the subject is now,
+/// like the chase cameras of racing game replays. The subject needs a `TransformHistory`.
+///
+/// The camera drives its own `LookTransform`, so disable any controller on the same entity.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct ReplayCamera {
+    pub enabled: bool,
+    pub subject: Entity,
+    /// How far behind the subject the camera is, in seconds
+    pub delay: f64,
+    /// Seconds of path averaged around each point the camera passes through
+    pub smoothing: f64,
+    /// Offset of the eye from the path, in world space
+    pub offset: DVec3,
+}
+
+impl ReplayCamera {
+    pub fn new(subject: Entity) -> Self {
+        Self {
+            enabled: true,
+            subject,
+            delay: 0.5,
+            smoothing: 0.5,
+            offset: DVec3::Y,
+        }
+    }
+}
+
+fn transform_history_system(
+    time: Res<Time>,
+    mut histories: Query<(&mut TransformHistory, &GlobalTransform)>,
+) {
+    let now = time.elapsed_seconds_f64();
+    for (mut history, global) in histories.iter_mut() {
+        history.push(now, global.translation());
+    }
+}
+
+fn replay_camera_system(
+    time: Res<Time>,
+    mut cameras: Query<(&ReplayCamera, &mut LookTransform)>,
+    histories: Query<&TransformHistory>,
+) {
+    let now = time.elapsed_seconds_f64();
+    for (camera, mut transform) in cameras.iter_mut() {
+        if !camera.enabled {
+            continue;
+        }
+        let history = if let Ok(history) = histories.get(camera.subject) {
+            history
+        } else {
+            continue;
+        };
+        let (eye, target) = match (
+            history.sample_smoothed(now - camera.delay, camera.smoothing),
+            history.latest(),
+        ) {
+            (Some(eye), Some(target)) => (eye + camera.offset, target),
+            _ => continue,
+        };
+        if eye != target && (eye != transform.eye || target != transform.target) {
+            transform.eye = eye;
+            transform.target = target;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_history() {
+        let mut history = TransformHistory::new(3);
+        for i in 0..4 {
+            history.push(i as f64, DVec3::X * i as f64);
+        }
+        // The first sample was dropped.
+        assert_eq!(history.sample(0.0), Some(DVec3::X));
+        assert_eq!(history.sample(2.5), Some(2.5 * DVec3::X));
+        assert_eq!(history.latest(), Some(3.0 * DVec3::X));
+        assert!(history
+            .sample_smoothed(2.0, 1.0)
+            .unwrap()
+            .abs_diff_eq(2.0 * DVec3::X, 1e-9));
+    }
+}
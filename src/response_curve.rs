@@ -0,0 +1,80 @@
+use bevy::{
+    math::DVec2,
+    reflect::{FromReflect, Reflect},
+};
+use serde::{Deserialize, Serialize};
+
+/// Cursor delta magnitude, in pixels per frame, that a `ResponseCurve` leaves unchanged when applied to mouse input.
+pub const MOUSE_CURVE_REFERENCE: f64 = 10.0;
+
+/// Reshapes analog input by its magnitude, so small inputs can be made finer without making large ones sluggish.
+#[derive(Clone, Copy, Debug, Default, Deserialize, FromReflect, PartialEq, Reflect, Serialize)]
+pub enum ResponseCurve {
+    #[default]
+    Linear,
+    Squared,
+    /// Raise the normalized magnitude to this power.
+    Exponent(f64),
+}
+
+impl ResponseCurve {
+    pub fn exponent(self) -> f64 {
+        match self {
+            ResponseCurve::Linear => 1.0,
+            ResponseCurve::Squared => 2.0,
+            ResponseCurve::Exponent(e) => e,
+        }
+    }
+
+    /// Applies the curve to the magnitude of `input` relative to `reference`, keeping its direction. An input of length
+    /// `reference` is unchanged; use `1.0` for gamepad sticks and `MOUSE_CURVE_REFERENCE` for cursor deltas.
+    pub fn apply(self, input: DVec2, reference: f64) -> DVec2 {
+        let exponent = self.exponent();
+        let length = input.length();
+        if exponent == 1.0 || length == 0.0 || reference <= 0.0 {
+            return input;
+        }
+
+        input * (reference * (length / reference).powf(exponent) / length)
+    }
+}
+
+/// Ignores analog input up to `deadzone` in magnitude, and rescales the rest to start from zero, so there's no jump at
+/// the edge of the deadzone. Works for triggers (`0.0` to `1.0`) and stick axes (`-1.0` to `1.0`).
+pub fn apply_deadzone(value: f64, deadzone: f64) -> f64 {
+    if value.abs() <= deadzone || deadzone >= 1.0 {
+        return 0.0;
+    }
+    value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_squared_curve() {
+        let curve = ResponseCurve::Squared;
+
+        assert_relative_eq!(curve.apply(DVec2::new(0.5, 0.0), 1.0).x, 0.25);
+        assert_relative_eq!(curve.apply(DVec2::new(-1.0, 0.0), 1.0).x, -1.0);
+        assert_relative_eq!(curve.apply(DVec2::new(0.0, 20.0), 10.0).y, 40.0);
+        assert_eq!(curve.apply(DVec2::ZERO, 1.0), DVec2::ZERO);
+    }
+
+    #[test]
+    fn test_deadzone() {
+        assert_eq!(apply_deadzone(0.1, 0.2), 0.0);
+        assert_relative_eq!(apply_deadzone(0.6, 0.2), 0.5);
+        assert_relative_eq!(apply_deadzone(-1.0, 0.2), -1.0);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::LookTransform;
+
+use bevy::{ecs::prelude::*, math::prelude::*, render::camera::Projection};
+
+/// Keeps a rectangle of the ground covering at least `min_coverage` of the view, whatever moves the camera, for
+/// top-down and RTS-style cameras. Zooming out stops once the rectangle would cover less, and panning stops at its
+/// edges. Since the limit is a fraction of the view rather than world units, it's recomputed from the radius and the
+/// `Projection` every frame, so it follows zoom and window resizes. Like `LookTransformLocks`, this is enforced after
+/// the controllers and before smoothing.
+///
+/// The ground is the XZ plane. The view is approximated by the square around it at the target, so the limits don't
+/// change with the yaw or pitch of the camera. Orthographic cameras are limited through `OrthographicProjection::scale`
+/// instead of the radius.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct ScreenSpaceLimits {
+    /// Corner of the rectangle with the lowest X and Z coordinates
+    pub min: DVec2,
+    /// Corner of the rectangle with the highest X and Z coordinates
+    pub max: DVec2,
+    /// Fraction of the view, along each axis, the rectangle has to cover, in `[0, 1]`
+    pub min_coverage: f64,
+}
+
+impl ScreenSpaceLimits {
+    pub fn new(min: DVec2, max: DVec2, min_coverage: f64) -> Self {
+        Self {
+            min,
+            max,
+            min_coverage,
+        }
+    }
+
+    /// Returns `transform` limited for a view whose square reaches `half_view` from the target, in world units, and
+    /// the factor that `half_view` had to be scaled down by.
+    pub fn apply(&self, mut transform: LookTransform, half_view: f64) -> (LookTransform, f64) {
+        let coverage = self.min_coverage.clamp(0.0, 1.0);
+        let half_rect = (self.max - self.min).abs() / 2.0;
+
+        // The rectangle can't cover more than all of it.
+        let mut scale = 1.0;
+        let max_half_view = half_rect.min_element() / coverage;
+        if half_view > max_half_view && half_view > 0.0 {
+            scale = max_half_view / half_view;
+        }
+        let half_view = scale * half_view;
+
+        // Slack the target has past the edges, negative when it has to stay inside.
+        let slack = half_view * (1.0 - 2.0 * coverage);
+        let min = self.min.min(self.max);
+        let target = DVec2::new(transform.target.x, transform.target.z);
+        let clamped = target.max(min - slack).min(min + 2.0 * half_rect + slack);
+        let shift = DVec3::new(clamped.x - target.x, 0.0, clamped.y - target.y);
+        transform.target += shift;
+        transform.eye += shift;
+
+        (transform, scale)
+    }
+}
+
+/// How far the view reaches from the target at `radius`, the half size of the square around it.
+fn half_view(projection: &Projection, radius: f64) -> f64 {
+    match projection {
+        Projection::Perspective(perspective) => {
+            let tan_y = (perspective.fov as f64 / 2.0).tan();
+            radius * tan_y * (perspective.aspect_ratio as f64).max(1.0)
+        }
+        Projection::Orthographic(orthographic) => {
+            let half_width = (orthographic.right - orthographic.left) as f64 / 2.0;
+            let half_height = (orthographic.top - orthographic.bottom) as f64 / 2.0;
+            orthographic.scale as f64 * half_width.max(half_height)
+        }
+    }
+}
+
+pub(crate) fn screen_space_limits_system(
+    mut cameras: Query<(&ScreenSpaceLimits, &mut LookTransform, Option<&mut Projection>)>,
+) {
+    for (limits, mut transform, projection) in cameras.iter_mut() {
+        let projection = if let Some(projection) = projection {
+            projection
+        } else {
+            continue;
+        };
+        let half_view = half_view(&projection, transform.radius());
+        let (mut limited, scale) = limits.apply(*transform, half_view);
+        if scale < 1.0 {
+            match projection.into_inner() {
+                Projection::Perspective(_) => {
+                    limited.eye = limited.target + scale * (limited.eye - limited.target);
+                }
+                Projection::Orthographic(orthographic) => {
+                    orthographic.scale *= scale as f32;
+                }
+            }
+        }
+        // Only write when something moved, so settled cameras stay unchanged.
+        if limited != *transform {
+            *transform = limited;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_screen_space_limits() {
+        let limits = ScreenSpaceLimits::new(DVec2::splat(-10.0), DVec2::splat(10.0), 0.5);
+        let transform = LookTransform::new(DVec3::new(0.0, 5.0, 0.1), DVec3::ZERO);
+
+        // Within the limits.
+        let (limited, scale) = limits.apply(transform, 4.0);
+        assert_eq!(limited, transform);
+        assert_eq!(scale, 1.0);
+
+        // Zoomed out too far to cover half the view.
+        let (_, scale) = limits.apply(transform, 40.0);
+        assert_relative_eq!(scale, 0.5);
+
+        // Half the view may hang over the edge.
+        let offset = DVec3::new(30.0, 0.0, 0.0);
+        let moved = LookTransform::new(transform.eye + offset, transform.target + offset);
+        let (limited, _) = limits.apply(moved, 4.0);
+        assert_relative_eq!(limited.target.x, 10.0);
+        assert_relative_eq!(limited.eye.x, 10.0);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{LookTransform, LookTransformSystem};
+
+use bevy::{
+    app::prelude::*,
+    ecs::prelude::*,
+    math::prelude::*,
+    time::Time,
+    transform::components::GlobalTransform,
+};
+use serde::{Deserialize, Serialize};
+
+pub struct CameraSequencePlugin;
+
+impl Plugin for CameraSequencePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SequenceEvent>()
+            .add_system(sequence_event_system)
+            .add_system(
+                sequence_system
+                    .after(sequence_event_system)
+                    .before(LookTransformSystem::Smooth),
+            );
+    }
+}
+
+/// How progress through a shot maps to progress along its motion.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps `t` in `[0, 1]` to eased progress in `[0, 1]`.
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ShotKind {
+    /// Hold on a fixed `LookTransform`.
+    Hold(LookTransform),
+    /// Look at an entity from a fixed offset, following it as it moves.
+    Track { entity: Entity, offset: DVec3 },
+    /// Move through the given transforms, spending equal time on each segment.
+    Path(Vec<LookTransform>),
+    /// Leave the `LookTransform` untouched.
+    Wait,
+}
+
+#[derive(Clone, Debug)]
+pub struct Shot {
+    pub kind: ShotKind,
+    /// Length of the shot in seconds.
+    pub duration: f64,
+    pub easing: Easing,
+}
+
+impl Shot {
+    pub fn new(kind: ShotKind, duration: f64) -> Self {
+        Self {
+            kind,
+            duration,
+            easing: Easing::default(),
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// An ordered list of shots that drives the `LookTransform` of the same entity while playing.
+///
+/// Controllers should be disabled on the entity while a sequence plays, otherwise both will write the `LookTransform`.
+#[derive(Clone, Component, Debug, Default)]
+pub struct CameraSequence {
+    pub shots: Vec<Shot>,
+    pub playing: bool,
+    current_shot: usize,
+    elapsed: f64,
+}
+
+impl CameraSequence {
+    pub fn new(shots: Vec<Shot>) -> Self {
+        Self {
+            shots,
+            ..Default::default()
+        }
+    }
+
+    pub fn current_shot(&self) -> Option<&Shot> {
+        self.shots.get(self.current_shot)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current_shot >= self.shots.len()
+    }
+
+    /// Restart from the first shot.
+    pub fn rewind(&mut self) {
+        self.current_shot = 0;
+        self.elapsed = 0.0;
+    }
+
+    pub fn skip(&mut self) {
+        self.current_shot += 1;
+        self.elapsed = 0.0;
+    }
+}
+
+pub enum SequenceEvent {
+    Play(Entity),
+    Pause(Entity),
+    Skip(Entity),
+}
+
+fn sequence_event_system(
+    mut events: EventReader<SequenceEvent>,
+    mut sequences: Query<&mut CameraSequence>,
+) {
+    for event in events.iter() {
+        match *event {
+            SequenceEvent::Play(entity) => {
+                if let Ok(mut sequence) = sequences.get_mut(entity) {
+                    if sequence.is_finished() {
+                        sequence.rewind();
+                    }
+                    sequence.playing = true;
+                }
+            }
+            SequenceEvent::Pause(entity) => {
+                if let Ok(mut sequence) = sequences.get_mut(entity) {
+                    sequence.playing = false;
+                }
+            }
+            SequenceEvent::Skip(entity) => {
+                if let Ok(mut sequence) = sequences.get_mut(entity) {
+                    sequence.skip();
+                }
+            }
+        }
+    }
+}
+
+fn sequence_system(
+    time: Res<Time>,
+    mut sequences: Query<(&mut CameraSequence, &mut LookTransform)>,
+    tracked: Query<&GlobalTransform>,
+) {
+    for (mut sequence, mut transform) in sequences.iter_mut() {
+        if !sequence.playing {
+            continue;
+        }
+        let shot = if let Some(shot) = sequence.current_shot() {
+            shot
+        } else {
+            sequence.playing = false;
+            continue;
+        };
+
+        // The frame that runs past the end of the shot lands exactly on its end before moving on.
+        let finished = sequence.elapsed + time.delta_seconds_f64() >= shot.duration;
+        let t = if finished {
+            1.0
+        } else {
+            shot.easing.apply(sequence.elapsed / shot.duration)
+        };
+        match &shot.kind {
+            ShotKind::Hold(hold) => *transform = *hold,
+            ShotKind::Track { entity, offset } => {
+                if let Ok(global) = tracked.get(*entity) {
+                    let target = global.translation();
+                    *transform = LookTransform::new(target + *offset, target).with_up(transform.up);
+                }
+            }
+            ShotKind::Path(points) => {
+                if let Some(point) = sample_path(points, t) {
+                    *transform = point;
+                }
+            }
+            ShotKind::Wait => (),
+        }
+
+        if finished {
+            sequence.skip();
+        } else {
+            sequence.elapsed += time.delta_seconds_f64();
+        }
+    }
+}
+
+/// Samples a piecewise-linear path through `points` at `t` in `[0, 1]`.
+pub fn sample_path(points: &[LookTransform], t: f64) -> Option<LookTransform> {
+    match points.len() {
+        0 => None,
+        1 => Some(points[0]),
+        n => {
+            let segments = (n - 1) as f64;
+            let s = t.clamp(0.0, 1.0) * segments;
+            let i = (s.floor() as usize).min(n - 2);
+            let local_t = s - i as f64;
+            let (a, b) = (points[i], points[i + 1]);
+            Some(LookTransform::new(
+                a.eye.lerp(b.eye, local_t),
+                a.target.lerp(b.target, local_t),
+            )
+            .with_up(a.up))
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    use approx::assert_relative_eq;
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn test_easing_endpoints() {
+        for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_relative_eq!(easing.apply(0.0), 0.0);
+            assert_relative_eq!(easing.apply(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_sample_path() {
+        let points = [
+            LookTransform::new(DVec3::ZERO, DVec3::Z),
+            LookTransform::new(DVec3::X, DVec3::Z),
+            LookTransform::new(DVec3::new(2.0, 0.0, 0.0), DVec3::Z),
+        ];
+
+        assert_relative_eq!(sample_path(&points, 0.0).unwrap().eye.x, 0.0);
+        assert_relative_eq!(sample_path(&points, 0.25).unwrap().eye.x, 0.5);
+        assert_relative_eq!(sample_path(&points, 0.75).unwrap().eye.x, 1.5);
+        assert_relative_eq!(sample_path(&points, 1.0).unwrap().eye.x, 2.0);
+        assert!(sample_path(&[], 0.5).is_none());
+    }
+
+    #[test]
+    fn test_shot_ends_on_final_pose() {
+        let mut app = headless_app();
+        app.add_plugin(CameraSequencePlugin);
+        let end = LookTransform::new(DVec3::X, DVec3::Z);
+        let path = vec![LookTransform::new(DVec3::ZERO, DVec3::Z), end];
+        let camera = app
+            .world
+            .spawn((
+                LookTransform::new(DVec3::ZERO, DVec3::Z),
+                CameraSequence::new(vec![
+                    Shot::new(ShotKind::Path(path), 0.001),
+                    Shot::new(ShotKind::Wait, 1000.0),
+                ]),
+            ))
+            .id();
+
+        send_control_event(&mut app, SequenceEvent::Play(camera));
+        for _ in 0..3 {
+            step(&mut app, 1);
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        // Every frame overshoots the path shot, and the waiting shot leaves its final pose in place.
+        assert_eq!(look_transform(&app, camera), end);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    add_transform_offset_system, controllers::CameraInputState, LookTransformSystem,
+    TransformOffset,
+};
+
+use bevy::{
+    app::prelude::*, ecs::prelude::*, input::prelude::*, math::prelude::*,
+    render::camera::Projection, time::Time,
+};
+
+/// Moves cameras with a `ShoulderOffset` over the shoulder, with shoulder swapping and aiming.
+pub struct ShoulderOffsetPlugin;
+
+impl Plugin for ShoulderOffsetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraInputState>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                add_transform_offset_system::<ShoulderOffset>,
+            )
+            .add_system(shoulder_offset_input_system.before(LookTransformSystem::Smooth))
+            .add_system(
+                shoulder_offset_system
+                    .after(LookTransformSystem::Smooth)
+                    .before(LookTransformSystem::Offset),
+            );
+    }
+}
+
+/// An over-the-shoulder offset for third-person cameras, such as an orbit camera following a character. The camera
+/// is moved to the side and up in its own frame, so the character sits off-center, and aiming tightens the offset and
+/// the field of view. Changes of shoulder and aim blend smoothly.
+///
+/// The offset is applied as a [`TransformOffset`](crate::TransformOffset). The field of view is only written on a
+/// perspective `Projection`, so don't combine this with an `FovZoom` on the same camera.
+///
+/// If `swap_key` or `aim_button` is set, that input drives `left` or `aiming`. Otherwise set them directly.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct ShoulderOffset {
+    /// Offset to the right (or left) and up, in units
+    pub offset: DVec2,
+    /// Offset to the right (or left) and up while aiming, in units
+    pub aim_offset: DVec2,
+    /// Vertical field of view in radians when not aiming
+    pub default_fov: f32,
+    /// Vertical field of view in radians while aiming
+    pub aim_fov: f32,
+    /// How quickly the camera blends to a new shoulder or aim state, per second
+    pub rate: f64,
+    /// Over the left shoulder instead of the right
+    pub left: bool,
+    pub aiming: bool,
+    /// Pressing this key swaps shoulders
+    pub swap_key: Option<KeyCode>,
+    /// Holding this button aims. Off by default, since the right button already pans the orbit camera and looks
+    /// around with the unreal camera.
+    pub aim_button: Option<MouseButton>,
+    side: Option<f64>,
+    aim: f64,
+}
+
+impl Default for ShoulderOffset {
+    fn default() -> Self {
+        Self {
+            offset: DVec2::new(0.6, 0.3),
+            aim_offset: DVec2::new(0.4, 0.15),
+            default_fov: std::f32::consts::PI / 4.0,
+            aim_fov: std::f32::consts::PI / 6.0,
+            rate: 8.0,
+            left: false,
+            aiming: false,
+            swap_key: Some(KeyCode::Q),
+            aim_button: None,
+            side: None,
+            aim: 0.0,
+        }
+    }
+}
+
+impl ShoulderOffset {
+    /// Blends toward the current shoulder and aim state for a frame of `dt` seconds, and returns the camera-local
+    /// offset and the field of view.
+    pub fn update(&mut self, dt: f64) -> (DVec3, f32) {
+        let target_side = if self.left { -1.0 } else { 1.0 };
+        let target_aim = if self.aiming { 1.0 } else { 0.0 };
+        let blend = 1.0 - (-self.rate * dt).exp();
+        let side = self.side.map_or(target_side, |side| side + (target_side - side) * blend);
+        self.side = Some(side);
+        self.aim += (target_aim - self.aim) * blend;
+
+        let offset = self.offset.lerp(self.aim_offset, self.aim);
+        let fov = self.default_fov + (self.aim_fov - self.default_fov) * self.aim as f32;
+        (DVec3::new(side * offset.x, offset.y, 0.0), fov)
+    }
+}
+
+fn shoulder_offset_input_system(
+    keyboard: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    mut shoulders: Query<&mut ShoulderOffset>,
+    input_state: Res<CameraInputState>,
+) {
+    if input_state.is_blocked() {
+        return;
+    }
+
+    for mut shoulder in shoulders.iter_mut() {
+        if shoulder.swap_key.map_or(false, |k| keyboard.just_pressed(k)) {
+            shoulder.left = !shoulder.left;
+        }
+        if let Some(button) = shoulder.aim_button {
+            let aiming = mouse_buttons.pressed(button);
+            if shoulder.aiming != aiming {
+                shoulder.aiming = aiming;
+            }
+        }
+    }
+}
+
+fn shoulder_offset_system(
+    time: Res<Time>,
+    mut cameras: Query<(&mut ShoulderOffset, &mut TransformOffset, Option<&mut Projection>)>,
+) {
+    for (mut shoulder, mut offset, projection) in cameras.iter_mut() {
+        let (translation, fov) = shoulder.update(time.delta_seconds_f64());
+        offset.translate(translation);
+
+        if let Some(mut projection) = projection {
+            if let Projection::Perspective(perspective) = projection.as_ref() {
+                if perspective.fov == fov {
+                    continue;
+                }
+            }
+            if let Projection::Perspective(perspective) = projection.as_mut() {
+                perspective.fov = fov;
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shoulder_swap_blends() {
+        let mut shoulder = ShoulderOffset::default();
+        let (start, fov) = shoulder.update(1.0 / 60.0);
+        assert_eq!(start.x, shoulder.offset.x);
+        assert_eq!(fov, shoulder.default_fov);
+
+        shoulder.left = true;
+        shoulder.aiming = true;
+        let (mid, _) = shoulder.update(1.0 / 60.0);
+        assert!(mid.x < start.x && mid.x > -start.x);
+
+        for _ in 0..120 {
+            shoulder.update(1.0 / 60.0);
+        }
+        let (end, fov) = shoulder.update(1.0 / 60.0);
+        assert!((end.x + shoulder.aim_offset.x).abs() < 1e-3);
+        assert!((fov - shoulder.aim_fov).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{
+    controllers::{
+        fixed::FixedCameraPlugin, fps::FpsCameraPlugin, map::MapCameraPlugin,
+        orbit::OrbitCameraPlugin, photo_mode::PhotoModePlugin, spectator::SpectatorCameraPlugin,
+        unreal::UnrealCameraPlugin,
+    },
+    LookTransformPlugin,
+};
+
+use bevy::app::{PluginGroup, PluginGroupBuilder};
+
+/// Adds the `LookTransformPlugin` and the plugin of every controller, so apps can spawn any kind of camera, or switch a
+/// camera's controller, at runtime. Each controller only moves the cameras it's on, and only while it's enabled.
+///
+/// Configure or leave out a plugin as with any `PluginGroup`, and don't add any of these plugins again separately:
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use smooth_bevy_cameras::{
+///     controllers::{fps::FpsCameraPlugin, photo_mode::PhotoModePlugin},
+///     SmoothCamerasPlugins,
+/// };
+///
+/// App::new().add_plugins(DefaultPlugins).add_plugins(
+///     SmoothCamerasPlugins
+///         .set(FpsCameraPlugin::default().with_toggle_key(KeyCode::Escape))
+///         .disable::<PhotoModePlugin>(),
+/// );
+/// ```
+pub struct SmoothCamerasPlugins;
+
+impl PluginGroup for SmoothCamerasPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(LookTransformPlugin)
+            .add(FpsCameraPlugin::default())
+            .add(OrbitCameraPlugin::default())
+            .add(UnrealCameraPlugin::default())
+            .add(SpectatorCameraPlugin::default())
+            .add(PhotoModePlugin::default())
+            .add(FixedCameraPlugin)
+            .add(MapCameraPlugin::default())
+    }
+}
@@ -0,0 +1,268 @@
+use crate::controllers::{
+    orbit::{self, OrbitCameraController},
+    unreal::{self, UnrealCameraController},
+    CameraControlSystem, CameraInputState,
+};
+
+use bevy::{app::prelude::*, ecs::prelude::*, log::warn, math::prelude::*};
+use hidapi::{HidApi, HidDevice};
+
+/// USB vendor IDs used by 3Dconnexion devices, including the older ones sold under Logitech's ID.
+const SPACE_MOUSE_VENDOR_IDS: [u16; 2] = [0x046d, 0x256f];
+
+/// HID usage of the multi-axis controller collection on the Generic Desktop page (0x01). Logitech's mice and keyboards
+/// share the vendor ID, so devices are matched by this too.
+const MULTI_AXIS_CONTROLLER_USAGE: (u16, u16) = (0x01, 0x08);
+
+/// Largest axis value reported by 3Dconnexion devices.
+const SPACE_MOUSE_AXIS_RANGE: f64 = 350.0;
+
+/// Reads a 3Dconnexion SpaceMouse (or other 6-DOF HID device with the same reports) and drives the enabled orbit or
+/// unreal camera with it. The first device found at startup is used.
+pub struct SpaceMousePlugin;
+
+impl Plugin for SpaceMousePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpaceMouseSettings>()
+            .init_resource::<CameraInputState>()
+            .add_event::<SpaceMouseMotion>()
+            .add_event::<orbit::ControlEvent>()
+            .add_event::<unreal::ControlEvent>()
+            .add_system_to_stage(CoreStage::PreUpdate, read_space_mouse_system)
+            .add_system(orbit_input_map.label(CameraControlSystem::Input))
+            .add_system(unreal_input_map.label(CameraControlSystem::Input));
+
+        match open_space_mouse() {
+            Ok(Some(device)) => {
+                app.insert_non_send_resource(SpaceMouseDevice {
+                    device,
+                    motion: SpaceMouseMotion::default(),
+                });
+            }
+            Ok(None) => warn!("No SpaceMouse found"),
+            Err(error) => warn!("Failed to open SpaceMouse: {}", error),
+        }
+    }
+}
+
+/// Sensitivity of the `SpaceMousePlugin` input maps.
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct SpaceMouseSettings {
+    /// Units per frame at full deflection, for panning and moving
+    pub translate_sensitivity: f64,
+    /// Radians per frame at full deflection
+    pub rotate_sensitivity: f64,
+    /// Zoom factor per frame at full deflection, for controllers that zoom by scaling the distance to the target
+    pub zoom_sensitivity: f64,
+    /// Deflections smaller than this fraction of the range are ignored
+    pub dead_zone: f64,
+}
+
+impl Default for SpaceMouseSettings {
+    fn default() -> Self {
+        Self {
+            translate_sensitivity: 0.2,
+            rotate_sensitivity: 0.03,
+            zoom_sensitivity: 0.05,
+            dead_zone: 0.05,
+        }
+    }
+}
+
+/// The deflection of a SpaceMouse cap, sent every frame while it's deflected. Each axis is between `-1.0` and `1.0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SpaceMouseMotion {
+    /// Push and pull: +X is right, +Y is up, and +Z is forward, away from the user.
+    pub translation: DVec3,
+    /// Tilt and twist: +X tilts forward (look down), +Y twists clockwise (look right), and +Z tilts right (roll).
+    pub rotation: DVec3,
+}
+
+impl SpaceMouseMotion {
+    /// Updates the motion from one HID input report. Returns `false` if the report isn't a motion report.
+    ///
+    /// Report 1 carries translation (and on newer devices rotation too), and report 2 carries rotation. Axes are little
+    /// endian `i16`s in device coordinates: +X right, +Y toward the user and +Z down.
+    pub fn read_report(&mut self, report: &[u8]) -> bool {
+        let axes = |bytes: &[u8]| {
+            let axis = |i: usize| {
+                i16::from_le_bytes([bytes[2 * i], bytes[2 * i + 1]]) as f64 / SPACE_MOUSE_AXIS_RANGE
+            };
+            DVec3::new(axis(0), axis(1), axis(2)).clamp(DVec3::splat(-1.0), DVec3::splat(1.0))
+        };
+        let to_translation = |v: DVec3| DVec3::new(v.x, -v.z, -v.y);
+        let to_rotation = |v: DVec3| DVec3::new(v.x, v.z, v.y);
+        match report {
+            [1, rest @ ..] if rest.len() >= 12 => {
+                self.translation = to_translation(axes(&rest[..6]));
+                self.rotation = to_rotation(axes(&rest[6..12]));
+            }
+            [1, rest @ ..] if rest.len() >= 6 => self.translation = to_translation(axes(rest)),
+            [2, rest @ ..] if rest.len() >= 6 => self.rotation = to_rotation(axes(rest)),
+            _ => return false,
+        }
+        true
+    }
+
+    fn with_dead_zone(mut self, dead_zone: f64) -> Self {
+        let filter =
+            |v: DVec3| DVec3::select(v.abs().cmplt(DVec3::splat(dead_zone)), DVec3::ZERO, v);
+        self.translation = filter(self.translation);
+        self.rotation = filter(self.rotation);
+        self
+    }
+}
+
+struct SpaceMouseDevice {
+    device: HidDevice,
+    motion: SpaceMouseMotion,
+}
+
+fn open_space_mouse() -> Result<Option<HidDevice>, hidapi::HidError> {
+    let api = HidApi::new()?;
+    let info = if let Some(info) = api
+        .device_list()
+        .find(|info| is_space_mouse(info.vendor_id(), info.usage_page(), info.usage()))
+    {
+        info
+    } else {
+        return Ok(None);
+    };
+    let device = info.open_device(&api)?;
+    device.set_blocking_mode(false)?;
+    Ok(Some(device))
+}
+
+fn is_space_mouse(vendor_id: u16, usage_page: u16, usage: u16) -> bool {
+    SPACE_MOUSE_VENDOR_IDS.contains(&vendor_id)
+        && (usage_page, usage) == MULTI_AXIS_CONTROLLER_USAGE
+}
+
+fn read_space_mouse_system(
+    device: Option<NonSendMut<SpaceMouseDevice>>,
+    settings: Res<SpaceMouseSettings>,
+    mut events: EventWriter<SpaceMouseMotion>,
+) {
+    let mut device = if let Some(device) = device {
+        device
+    } else {
+        return;
+    };
+    let SpaceMouseDevice { device, motion } = &mut *device;
+
+    // The device only reports changes, so the last motion holds until the next report.
+    let mut report = [0; 64];
+    while let Ok(len) = device.read(&mut report) {
+        if len == 0 {
+            break;
+        }
+        motion.read_report(&report[..len]);
+    }
+
+    let motion = motion.with_dead_zone(settings.dead_zone);
+    if motion != SpaceMouseMotion::default() {
+        events.send(motion);
+    }
+}
+
+fn orbit_input_map(
+    mut events: EventWriter<orbit::ControlEvent>,
+    mut motion_events: EventReader<SpaceMouseMotion>,
+    settings: Res<SpaceMouseSettings>,
+    controllers: Query<&OrbitCameraController>,
+    input_state: Res<CameraInputState>,
+) {
+    // Can only control one camera at a time.
+    if input_state.is_blocked() || !controllers.iter().any(|c| c.enabled) {
+        return;
+    }
+
+    for motion in motion_events.iter() {
+        let rotate = DVec2::new(motion.rotation.y, motion.rotation.x);
+        if rotate != DVec2::ZERO {
+            events.send(orbit::ControlEvent::Orbit(settings.rotate_sensitivity * rotate));
+        }
+        if motion.translation.x != 0.0 || motion.translation.y != 0.0 {
+            // Move the target the way the view should move, which is opposite to dragging it with the mouse.
+            events.send(orbit::ControlEvent::TranslateTarget(
+                settings.translate_sensitivity
+                    * DVec2::new(-motion.translation.x, motion.translation.y),
+            ));
+        }
+        if motion.translation.z != 0.0 {
+            events.send(orbit::ControlEvent::Zoom(
+                1.0 - settings.zoom_sensitivity * motion.translation.z,
+            ));
+        }
+    }
+}
+
+fn unreal_input_map(
+    mut events: EventWriter<unreal::ControlEvent>,
+    mut motion_events: EventReader<SpaceMouseMotion>,
+    settings: Res<SpaceMouseSettings>,
+    controllers: Query<&UnrealCameraController>,
+    input_state: Res<CameraInputState>,
+) {
+    // Can only control one camera at a time.
+    if input_state.is_blocked() || !controllers.iter().any(|c| c.enabled) {
+        return;
+    }
+
+    for motion in motion_events.iter() {
+        let rotate = DVec2::new(motion.rotation.y, motion.rotation.x);
+        if rotate != DVec2::ZERO {
+            events.send(unreal::ControlEvent::Rotate(settings.rotate_sensitivity * rotate));
+        }
+        if motion.translation.x != 0.0 || motion.translation.y != 0.0 {
+            events.send(unreal::ControlEvent::TranslateEye(
+                settings.translate_sensitivity
+                    * DVec2::new(motion.translation.x, motion.translation.y),
+            ));
+        }
+        if motion.translation.z != 0.0 {
+            events.send(unreal::ControlEvent::Locomotion(DVec2::new(
+                0.0,
+                settings.translate_sensitivity * motion.translation.z,
+            )));
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_report() {
+        let mut motion = SpaceMouseMotion::default();
+
+        // Push right and down.
+        assert!(motion.read_report(&[1, 94, 1, 0, 0, 94, 1]));
+        assert_eq!(motion.translation, DVec3::new(1.0, -1.0, 0.0));
+
+        // Twist clockwise.
+        assert!(motion.read_report(&[2, 0, 0, 0, 0, 94, 1]));
+        assert_eq!(motion.rotation, DVec3::new(0.0, 1.0, 0.0));
+
+        // Buttons.
+        assert!(!motion.read_report(&[3, 1, 0]));
+    }
+
+    #[test]
+    fn test_is_space_mouse() {
+        assert!(is_space_mouse(0x256f, 0x01, 0x08));
+        assert!(is_space_mouse(0x046d, 0x01, 0x08));
+
+        // A Logitech mouse and keyboard.
+        assert!(!is_space_mouse(0x046d, 0x01, 0x02));
+        assert!(!is_space_mouse(0x046d, 0x01, 0x06));
+    }
+}
@@ -0,0 +1,209 @@
+use crate::controllers::{
+    fps::{CameraControlEvent, ControlEvent, FpsCameraController},
+    CameraControlSystem, CameraInputState,
+};
+
+use bevy::{
+    app::prelude::*,
+    ecs::prelude::*,
+    input::{mouse::MouseMotion, prelude::*},
+    math::prelude::*,
+    render::camera::{Camera, Viewport},
+    window::Windows,
+};
+
+/// Lays out every `SplitScreenPlayer` camera in the primary window and drives its FPS controller with its own input
+/// device. Add it after the `FpsCameraPlugin`, and override that plugin's input system, which would otherwise move the
+/// first camera with the keyboard and mouse as well.
+pub struct SplitScreenPlugin;
+
+impl Plugin for SplitScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraInputState>()
+            .add_system(split_screen_layout_system)
+            .add_system(split_screen_input_map.label(CameraControlSystem::Input));
+    }
+}
+
+/// The input device that drives a split screen player.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlayerInput {
+    KeyboardMouse,
+    Gamepad(Gamepad),
+}
+
+/// One player's camera in split screen. Players are laid out by `index`, from the top left: two players side by side,
+/// and three or four in quadrants.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct SplitScreenPlayer {
+    pub index: usize,
+    pub input: PlayerInput,
+    /// Radians per frame for each rotation axis at full deflection of the right gamepad stick
+    pub gamepad_rotate_sensitivity: DVec2,
+}
+
+impl SplitScreenPlayer {
+    pub fn new(index: usize, input: PlayerInput) -> Self {
+        Self {
+            index,
+            input,
+            gamepad_rotate_sensitivity: DVec2::splat(0.05),
+        }
+    }
+}
+
+/// The viewport of player `index` out of `count`, as a position and size in a window of `window_size`.
+pub fn split_screen_viewport(index: usize, count: usize, window_size: UVec2) -> (UVec2, UVec2) {
+    let (columns, rows) = match count {
+        0 | 1 => (1, 1),
+        2 => (2, 1),
+        _ => (2, 2),
+    };
+    let size = window_size / UVec2::new(columns, rows);
+    let cell = (index as u32).min(columns * rows - 1);
+    (UVec2::new(cell % columns, cell / columns) * size, size)
+}
+
+fn split_screen_layout_system(
+    windows: Res<Windows>,
+    mut players: Query<(&SplitScreenPlayer, &mut Camera)>,
+) {
+    let window = if let Some(window) = windows.get_primary() {
+        window
+    } else {
+        return;
+    };
+    let window_size = UVec2::new(window.physical_width(), window.physical_height());
+    let count = players.iter().count();
+    for (player, mut camera) in players.iter_mut() {
+        let (position, size) = split_screen_viewport(player.index, count, window_size);
+        let unchanged = camera.viewport.as_ref().map_or(false, |viewport| {
+            viewport.physical_position == position && viewport.physical_size == size
+        });
+        if !unchanged {
+            camera.viewport = Some(Viewport {
+                physical_position: position,
+                physical_size: size,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn split_screen_input_map(
+    mut events: EventWriter<CameraControlEvent>,
+    keyboard: Res<Input<KeyCode>>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    input_state: Res<CameraInputState>,
+    players: Query<(Entity, &SplitScreenPlayer, &FpsCameraController)>,
+) {
+    let mut cursor_delta = DVec2::ZERO;
+    for event in mouse_motion_events.iter() {
+        cursor_delta += DVec2::new(event.delta.x as f64, event.delta.y as f64);
+    }
+    if input_state.is_blocked() {
+        return;
+    }
+
+    for (camera, player, controller) in players.iter() {
+        if !controller.enabled {
+            continue;
+        }
+        let mut send = |event| events.send(CameraControlEvent { camera, event });
+        let sensitivity = controller.translate_sensitivity;
+        match player.input {
+            PlayerInput::KeyboardMouse => {
+                if cursor_delta != DVec2::ZERO {
+                    send(ControlEvent::Rotate(
+                        controller.mouse_rotate_sensitivity * cursor_delta,
+                    ));
+                }
+                for (key, dir) in [
+                    (KeyCode::W, DVec3::Z),
+                    (KeyCode::A, DVec3::X),
+                    (KeyCode::S, -DVec3::Z),
+                    (KeyCode::D, -DVec3::X),
+                    (KeyCode::LShift, -DVec3::Y),
+                    (KeyCode::Space, DVec3::Y),
+                ] {
+                    if keyboard.pressed(key) {
+                        send(ControlEvent::TranslateEye(sensitivity * dir));
+                    }
+                }
+                if keyboard.just_pressed(controller.crouch_key) {
+                    send(ControlEvent::Crouch(true));
+                }
+                if keyboard.just_released(controller.crouch_key) {
+                    send(ControlEvent::Crouch(false));
+                }
+            }
+            PlayerInput::Gamepad(gamepad) => {
+                let axis = |axis_type| {
+                    gamepad_axes
+                        .get(GamepadAxis::new(gamepad, axis_type))
+                        .unwrap_or(0.0) as f64
+                };
+                // Stick up is positive, but should pitch up like moving the mouse up does.
+                let look = DVec2::new(
+                    axis(GamepadAxisType::RightStickX),
+                    -axis(GamepadAxisType::RightStickY),
+                );
+                if look != DVec2::ZERO {
+                    send(ControlEvent::Rotate(player.gamepad_rotate_sensitivity * look));
+                }
+                // +X is left and +Z is forward, like the keyboard.
+                let mut translate = DVec3::new(
+                    -axis(GamepadAxisType::LeftStickX),
+                    0.0,
+                    axis(GamepadAxisType::LeftStickY),
+                );
+                let button = |button_type| GamepadButton::new(gamepad, button_type);
+                if gamepad_buttons.pressed(button(GamepadButtonType::South)) {
+                    translate.y += 1.0;
+                }
+                if gamepad_buttons.pressed(button(GamepadButtonType::East)) {
+                    translate.y -= 1.0;
+                }
+                if translate != DVec3::ZERO {
+                    send(ControlEvent::TranslateEye(sensitivity * translate));
+                }
+                let crouch = button(GamepadButtonType::LeftThumb);
+                if gamepad_buttons.just_pressed(crouch) {
+                    send(ControlEvent::Crouch(true));
+                }
+                if gamepad_buttons.just_released(crouch) {
+                    send(ControlEvent::Crouch(false));
+                }
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_screen_viewport() {
+        let window = UVec2::new(1920, 1080);
+        assert_eq!(split_screen_viewport(0, 1, window), (UVec2::ZERO, window));
+        assert_eq!(
+            split_screen_viewport(1, 2, window),
+            (UVec2::new(960, 0), UVec2::new(960, 1080))
+        );
+        assert_eq!(
+            split_screen_viewport(2, 3, window),
+            (UVec2::new(0, 540), UVec2::new(960, 540))
+        );
+    }
+}
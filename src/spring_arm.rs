@@ -0,0 +1,223 @@
+use crate::{
+    add_transform_offset_system, CameraObstruction, LookTransform, LookTransformSystem, Smoother,
+    TransformOffset,
+};
+
+use bevy::{app::prelude::*, ecs::prelude::*, math::prelude::*, time::Time};
+
+/// Pulls cameras with a `SpringArm` in front of the geometry between them and their target.
+pub struct SpringArmPlugin;
+
+impl Plugin for SpringArmPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            add_transform_offset_system::<SpringArm>,
+        )
+        .add_event::<SpringArmOccluded>()
+        .add_system(
+            spring_arm_system
+                .after(LookTransformSystem::Smooth)
+                .before(LookTransformSystem::Offset),
+        );
+    }
+}
+
+/// Keeps the target of a third-person camera in view by shortening the arm from the target to the eye wherever the
+/// camera's `CameraObstruction` blocks it. The arm retracts quickly so the camera doesn't clip into walls, and extends
+/// slowly once they're out of the way. To keep it from oscillating near thin obstacles, it only extends after
+/// `hold_seconds` without retracting, and when it can extend by more than `hysteresis`.
+///
+/// The arm is shortened through a [`TransformOffset`](crate::TransformOffset), while the `LookTransform` keeps the full
+/// distance.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct SpringArm {
+    /// How quickly the arm shortens to a closer obstruction, per second. `f64::INFINITY` snaps to it.
+    pub retract_rate: f64,
+    /// How quickly the arm lengthens once an obstruction clears, per second
+    pub extend_rate: f64,
+    /// The arm only lengthens when the obstruction is at least this many units further away, or has cleared
+    pub hysteresis: f64,
+    /// Seconds to wait after the arm last shortened before lengthening it
+    pub hold_seconds: f64,
+    length: Option<f64>,
+    held: f64,
+}
+
+impl Default for SpringArm {
+    fn default() -> Self {
+        Self {
+            retract_rate: 30.0,
+            extend_rate: 3.0,
+            hysteresis: 0.2,
+            hold_seconds: 0.3,
+            length: None,
+            held: 0.0,
+        }
+    }
+}
+
+impl SpringArm {
+    /// The current length of the arm, if it has been updated.
+    pub fn length(&self) -> Option<f64> {
+        self.length
+    }
+
+    /// Moves the arm for a frame of `dt` seconds, for an unobstructed length of `full_length` that is clear of
+    /// obstructions up to `free_length`, and returns the new length.
+    pub fn update(&mut self, full_length: f64, free_length: f64, dt: f64) -> f64 {
+        let free_length = free_length.min(full_length);
+        let length = self.length.map_or(free_length, |l| l.min(full_length));
+        let approach = |rate: f64| length + (free_length - length) * (1.0 - (-rate * dt).exp());
+
+        let new_length = if free_length < length {
+            self.held = 0.0;
+            // Never end up behind the obstruction, whatever the rate.
+            approach(self.retract_rate).min(length)
+        } else {
+            self.held += dt;
+            let clear = free_length >= full_length || free_length - length > self.hysteresis;
+            if self.held >= self.hold_seconds && clear {
+                approach(self.extend_rate)
+            } else {
+                length
+            }
+        };
+        self.length = Some(new_length);
+        new_length
+    }
+}
+
+/// Sent every frame something blocks the arm of a `SpringArm`, so games can fade or dither the obstructing geometry.
+/// With the `obstruction_fade` feature, the `ObstructionFadePlugin` fades its `StandardMaterial`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpringArmOccluded {
+    pub camera: Entity,
+    /// The obstructing entity, if the camera's `ObstructionProvider` implements `sweep_hit`
+    pub entity: Option<Entity>,
+    /// Fraction of the arm that is blocked, from the eye's end. `1.0` is blocked right at the target.
+    pub occlusion: f64,
+}
+
+fn spring_arm_system(
+    time: Res<Time>,
+    mut events: EventWriter<SpringArmOccluded>,
+    mut cameras: Query<(
+        Entity,
+        &mut SpringArm,
+        &CameraObstruction,
+        &LookTransform,
+        Option<&Smoother>,
+        &mut TransformOffset,
+    )>,
+) {
+    let dt = time.delta_seconds_f64();
+    for (camera, mut arm, obstruction, look_transform, smoother, mut offset) in cameras.iter_mut() {
+        let smoothed = smoother
+            .and_then(|s| s.smoothed_transform())
+            .unwrap_or(*look_transform);
+        let full_length = smoothed.radius();
+        let hit = obstruction
+            .provider
+            .sweep_hit(smoothed.target, smoothed.eye, obstruction.radius);
+        let fraction = hit.map_or(1.0, |hit| hit.fraction.clamp(0.0, 1.0));
+        if let Some(hit) = hit {
+            events.send(SpringArmOccluded {
+                camera,
+                entity: hit.entity,
+                occlusion: 1.0 - fraction,
+            });
+        }
+        let length = arm.update(full_length, fraction * full_length, dt);
+
+        // The camera looks down its -Z axis, toward the target.
+        offset.translate(DVec3::new(0.0, 0.0, length - full_length));
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::*, ObstructionHit, ObstructionProvider};
+
+    use approx::assert_relative_eq;
+
+    /// Hits `entity` a quarter of the way along every sweep.
+    struct Wall {
+        entity: Entity,
+    }
+
+    impl ObstructionProvider for Wall {
+        fn sweep(&self, _from: DVec3, _to: DVec3, _radius: f64) -> Option<f64> {
+            Some(0.25)
+        }
+
+        fn sweep_hit(&self, from: DVec3, to: DVec3, radius: f64) -> Option<ObstructionHit> {
+            self.sweep(from, to, radius).map(|fraction| ObstructionHit {
+                fraction,
+                entity: Some(self.entity),
+            })
+        }
+    }
+
+    #[test]
+    fn test_spring_arm_hysteresis() {
+        let mut arm = SpringArm {
+            retract_rate: f64::INFINITY,
+            ..Default::default()
+        };
+        let dt = 0.1;
+        assert_relative_eq!(arm.update(10.0, 10.0, dt), 10.0);
+
+        // Snaps in front of a wall.
+        assert_relative_eq!(arm.update(10.0, 4.0, dt), 4.0);
+
+        // Holds while the wall flickers slightly further away, and for a while after it clears.
+        assert_relative_eq!(arm.update(10.0, 4.1, dt), 4.0);
+        assert_relative_eq!(arm.update(10.0, 10.0, dt), 4.0);
+        for _ in 0..3 {
+            arm.update(10.0, 10.0, dt);
+        }
+        let extending = arm.update(10.0, 10.0, dt);
+        assert!(extending > 4.0 && extending < 10.0);
+
+        for _ in 0..100 {
+            arm.update(10.0, 10.0, dt);
+        }
+        assert_relative_eq!(arm.length().unwrap(), 10.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_spring_arm_occluded_event() {
+        let mut app = headless_app();
+        app.add_plugin(SpringArmPlugin);
+        let wall = app.world.spawn_empty().id();
+        let camera = app
+            .world
+            .spawn((
+                LookTransform::new(10.0 * DVec3::Z, DVec3::ZERO),
+                CameraObstruction::new(Wall { entity: wall }, 0.5),
+                SpringArm::default(),
+            ))
+            .id();
+        step(&mut app, 2);
+
+        let events = app.world.resource::<Events<SpringArmOccluded>>();
+        let occluded = events.get_reader().iter(events).last().copied();
+        assert_eq!(
+            occluded,
+            Some(SpringArmOccluded {
+                camera,
+                entity: Some(wall),
+                occlusion: 0.75,
+            })
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{LookTransform, LookTransformSystem};
+
+use bevy::{
+    app::prelude::*,
+    core_pipeline::core_3d::Camera3dBundle,
+    ecs::prelude::*,
+    hierarchy::BuildChildren,
+    math::prelude::*,
+    transform::{
+        components::{GlobalTransform, Transform},
+        TransformBundle,
+    },
+};
+
+/// Moves the head and eyes of every `StereoRig`. Add it along with the plugin of the controller driving the anchor.
+pub struct StereoRigPlugin;
+
+impl Plugin for StereoRigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(stereo_rig_system.after(LookTransformSystem::Smooth));
+    }
+}
+
+/// A stereo rig for VR, anchored to the entity a controller drives.
+///
+/// The smoothed `LookTransform` of the anchor moves it, as with any camera. The tracked head is a child of the anchor,
+/// posed by `head_pose` without any smoothing, and the two eye cameras are children of the head, `ipd` apart. Smoothing
+/// locomotion but not head tracking avoids the motion sickness of a view that lags behind the head. Spawn it with
+/// `StereoRig::spawn`.
+///
+/// The eye cameras render to the window by default. Point their `Camera::target` at the headset's eye textures, and
+/// set their projections, from your XR integration.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct StereoRig {
+    /// Distance between the eyes in world units, typically 0.064 for 64 mm in meters
+    pub ipd: f64,
+    /// Pose of the tracked head relative to the anchor, written by your XR integration each frame
+    pub head_pose: Transform,
+    /// Smooth the anchor's rotation along with its translation. Turn this off to make turning with the controller
+    /// immediate rather than eased, which is less likely to cause motion sickness.
+    pub smooth_rotation: bool,
+    head: Entity,
+    eyes: [Entity; 2],
+}
+
+/// Marks one of the eye cameras of a `StereoRig`.
+#[derive(Clone, Component, Copy, Debug, Eq, PartialEq)]
+pub enum StereoEye {
+    Left,
+    Right,
+}
+
+impl StereoEye {
+    /// Offset from the head along its +X axis, for eyes `ipd` apart.
+    pub fn offset(&self, ipd: f64) -> DVec3 {
+        match self {
+            StereoEye::Left => DVec3::new(-ipd / 2.0, 0.0, 0.0),
+            StereoEye::Right => DVec3::new(ipd / 2.0, 0.0, 0.0),
+        }
+    }
+}
+
+impl StereoRig {
+    /// Adds the rig to `anchor`, an entity spawned with a controller's bundle but no camera, and spawns the head and
+    /// the eye cameras under it. Returns a copy of the rig; change the one on the anchor.
+    pub fn spawn(commands: &mut Commands, anchor: Entity, ipd: f64) -> Self {
+        let eyes = [StereoEye::Left, StereoEye::Right].map(|eye| {
+            commands
+                .spawn(Camera3dBundle {
+                    transform: Transform::from_translation(eye.offset(ipd)),
+                    ..Default::default()
+                })
+                .insert(eye)
+                .id()
+        });
+        let head = commands
+            .spawn(TransformBundle::default())
+            .push_children(&eyes)
+            .id();
+        let rig = Self {
+            ipd,
+            head_pose: Transform::IDENTITY,
+            smooth_rotation: true,
+            head,
+            eyes,
+        };
+        commands
+            .entity(anchor)
+            .insert((rig, GlobalTransform::default()))
+            .add_child(head);
+        rig
+    }
+
+    pub fn head(&self) -> Entity {
+        self.head
+    }
+
+    /// The eye cameras, left then right.
+    pub fn eyes(&self) -> [Entity; 2] {
+        self.eyes
+    }
+}
+
+fn stereo_rig_system(
+    mut rigs: Query<(&StereoRig, &LookTransform, &mut Transform)>,
+    mut parts: Query<(&mut Transform, Option<&StereoEye>), Without<StereoRig>>,
+) {
+    for (rig, look_transform, mut transform) in rigs.iter_mut() {
+        if !rig.smooth_rotation {
+            let rotation = Transform::from(*look_transform).rotation;
+            // Avoid marking a settled anchor's `Transform` as changed every frame.
+            if transform.rotation != rotation {
+                transform.rotation = rotation;
+            }
+        }
+
+        if let Ok((mut head, _)) = parts.get_mut(rig.head) {
+            if *head != rig.head_pose {
+                *head = rig.head_pose;
+            }
+        }
+        for &eye in rig.eyes.iter() {
+            if let Ok((mut transform, Some(side))) = parts.get_mut(eye) {
+                let offset = side.offset(rig.ipd);
+                if transform.translation != offset {
+                    transform.translation = offset;
+                }
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::*, LookTransformBundle, Smoother};
+
+    #[test]
+    fn test_stereo_rig() {
+        let mut app = headless_app();
+        app.add_plugin(StereoRigPlugin);
+        let eyes = [StereoEye::Left, StereoEye::Right]
+            .map(|eye| app.world.spawn((Transform::IDENTITY, eye)).id());
+        let head = app.world.spawn(Transform::IDENTITY).id();
+        let head_pose =
+            Transform::from_xyz(0.0, 1.7, 0.0).with_rotation(DQuat::from_rotation_y(0.3));
+        let anchor = app
+            .world
+            .spawn((
+                LookTransformBundle {
+                    transform: LookTransform::new(DVec3::ZERO, -DVec3::Z),
+                    smoother: Smoother::new(0.9),
+                },
+                Transform::IDENTITY,
+                StereoRig {
+                    ipd: 0.064,
+                    head_pose,
+                    smooth_rotation: false,
+                    head,
+                    eyes,
+                },
+            ))
+            .id();
+        step(&mut app, 1);
+
+        // Turn the anchor, which the smoother would ease.
+        let turned = LookTransform::new(DVec3::ZERO, DVec3::X);
+        *app.world.get_mut::<LookTransform>(anchor).unwrap() = turned;
+        step(&mut app, 1);
+
+        let rotation = scene_transform(&app, anchor).rotation;
+        assert!(rotation.abs_diff_eq(Transform::from(turned).rotation, 1e-9));
+        assert_eq!(scene_transform(&app, head), head_pose);
+        assert_eq!(
+            scene_transform(&app, eyes[0]).translation,
+            DVec3::new(-0.032, 0.0, 0.0)
+        );
+        assert_eq!(
+            scene_transform(&app, eyes[1]).translation,
+            DVec3::new(0.032, 0.0, 0.0)
+        );
+    }
+}
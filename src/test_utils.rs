@@ -0,0 +1,103 @@
+//! Helpers for driving cameras from tests without any input devices or window.
+//!
+//! ```no_run
+//! use bevy::math::{DVec2, DVec3};
+//! use smooth_bevy_cameras::{
+//!     controllers::orbit::{ControlEvent, OrbitCameraBundle, OrbitCameraController, OrbitCameraPlugin},
+//!     test_utils,
+//! };
+//!
+//! let mut app = test_utils::headless_app();
+//! app.add_plugin(OrbitCameraPlugin::new(true));
+//! let camera = app
+//!     .world
+//!     .spawn(OrbitCameraBundle::new(OrbitCameraController::default(), DVec3::Z, DVec3::ZERO))
+//!     .id();
+//!
+//! test_utils::send_control_event(&mut app, ControlEvent::Orbit(DVec2::new(0.1, 0.0)));
+//! test_utils::step(&mut app, 10);
+//! let transform = test_utils::scene_transform(&app, camera);
+//! ```
+
+use crate::{LookTransform, LookTransformPlugin};
+
+use bevy::{
+    app::prelude::*,
+    ecs::{event::Events, prelude::*},
+    transform::components::Transform,
+    MinimalPlugins,
+};
+
+/// An `App` with `MinimalPlugins` and the `LookTransformPlugin`. Add controller plugins with `override_input_system` set so
+/// that only synthetic events are processed.
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugin(LookTransformPlugin);
+
+    app
+}
+
+/// Queue a synthetic event, such as a controller's `ControlEvent`, for the next update.
+pub fn send_control_event<E: Send + Sync + 'static>(app: &mut App, event: E) {
+    app.world.resource_mut::<Events<E>>().send(event);
+}
+
+/// Run `frames` updates of the app.
+pub fn step(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+/// The (smoothed) scene graph `Transform` of `entity`.
+pub fn scene_transform(app: &App, entity: Entity) -> Transform {
+    *app.world
+        .get::<Transform>(entity)
+        .expect("Entity has no Transform")
+}
+
+/// The unsmoothed `LookTransform` of `entity`.
+pub fn look_transform(app: &App, entity: Entity) -> LookTransform {
+    *app.world
+        .get::<LookTransform>(entity)
+        .expect("Entity has no LookTransform")
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controllers::fps::{
+        ControlEvent, FpsCameraBundle, FpsCameraController, FpsCameraPlugin,
+    };
+
+    use bevy::math::DVec3;
+
+    #[test]
+    fn test_synthetic_translation_moves_camera() {
+        let mut app = headless_app();
+        app.add_plugin(FpsCameraPlugin::new(true));
+        let camera = app
+            .world
+            .spawn(FpsCameraBundle::new(
+                FpsCameraController::default(),
+                DVec3::ZERO,
+                DVec3::Z,
+            ))
+            .id();
+
+        // Movement is scaled by the frame time, which is zero on the first frame.
+        step(&mut app, 1);
+        send_control_event(&mut app, ControlEvent::TranslateEye(DVec3::Z));
+        step(&mut app, 5);
+
+        assert!(look_transform(&app, camera).eye.z > 0.0);
+        assert!(scene_transform(&app, camera).translation.z > 0.0);
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::{ecs::prelude::*, math::prelude::*};
+
+/// Layout and sensitivity of the on-screen touch controls, which let the FPS controller be used on phones and tablets.
+/// Enable them with `FpsCameraPlugin::with_touch_joystick`.
+///
+/// A touch that starts in the stick zone on the left of the window is a virtual joystick centered where it started, and
+/// moves the camera. Dragging anywhere else looks around.
+#[derive(Clone, Debug, Resource)]
+pub struct TouchJoystick {
+    /// Fraction of the window width, from the left edge, where touches start the stick
+    pub stick_zone: f32,
+    /// Logical pixels from the start of the stick touch to full deflection
+    pub stick_radius: f32,
+    /// Deflections smaller than this fraction of `stick_radius` are ignored
+    pub dead_zone: f64,
+    /// Units per frame at full deflection
+    pub translate_sensitivity: f64,
+    /// Radians per logical pixel dragged
+    pub rotate_sensitivity: DVec2,
+}
+
+impl Default for TouchJoystick {
+    fn default() -> Self {
+        Self {
+            stick_zone: 0.5,
+            stick_radius: 60.0,
+            dead_zone: 0.1,
+            translate_sensitivity: 0.2,
+            rotate_sensitivity: DVec2::splat(0.005),
+        }
+    }
+}
+
+impl TouchJoystick {
+    /// Returns `true` if a touch starting at `position` in a window `window_width` wide controls the stick.
+    pub fn is_in_stick_zone(&self, position: Vec2, window_width: f32) -> bool {
+        position.x < self.stick_zone * window_width
+    }
+
+    /// The deflection of a stick touch that started at `start` and is now at `position`, with length at most `1.0`.
+    /// Positive is right and up.
+    pub fn stick(&self, start: Vec2, position: Vec2) -> DVec2 {
+        let offset = (position - start).as_dvec2() / self.stick_radius as f64;
+        // Window coordinates grow downward.
+        let deflection = DVec2::new(offset.x, -offset.y).clamp_length_max(1.0);
+        if deflection.length() < self.dead_zone {
+            DVec2::ZERO
+        } else {
+            deflection
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stick() {
+        let joystick = TouchJoystick::default();
+        let start = Vec2::new(100.0, 500.0);
+
+        assert_eq!(joystick.stick(start, start + Vec2::new(3.0, 0.0)), DVec2::ZERO);
+        assert_eq!(joystick.stick(start, start + Vec2::new(0.0, -30.0)), DVec2::new(0.0, 0.5));
+        assert_eq!(joystick.stick(start, start + Vec2::new(600.0, 0.0)), DVec2::new(1.0, 0.0));
+    }
+}
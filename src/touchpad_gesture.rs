@@ -0,0 +1,12 @@
+/// A touchpad gesture, consumed by the orbit and unreal input maps: pinch to zoom, and rotate two fingers to orbit or
+/// turn.
+///
+/// The version of Bevy this crate targets doesn't forward winit's gesture events (macOS only), so send these yourself,
+/// for example from a winit event hook. The built-in controller plugins register the event.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TouchpadGesture {
+    /// Pinch, as the change in magnification. Positive when the fingers spread apart.
+    Magnify(f64),
+    /// Two-finger rotation, in radians. Positive is counterclockwise.
+    Rotate(f64),
+}
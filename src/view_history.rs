@@ -0,0 +1,186 @@
+use crate::{controllers::CameraInputState, LookTransform, LookTransformSystem};
+
+use bevy::{app::prelude::*, ecs::prelude::*, input::prelude::*, time::Time};
+
+/// Records the viewpoints of cameras with a `ViewHistory`, and steps back and forward through them on
+/// `ViewHistoryEvent`s or with Alt+Left and Alt+Right.
+pub struct ViewHistoryPlugin;
+
+impl Plugin for ViewHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraInputState>()
+            .add_event::<ViewHistoryEvent>()
+            .add_system(view_history_input_map)
+            .add_system(
+                view_history_system
+                    .after(view_history_input_map)
+                    .before(LookTransformSystem::Smooth),
+            );
+    }
+}
+
+pub enum ViewHistoryEvent {
+    Back(Entity),
+    Forward(Entity),
+}
+
+/// A back and forward history of this camera's viewpoints. A viewpoint is recorded once the `LookTransform` stops
+/// changing for `settle_seconds` with no mouse button held, so a whole drag or zoom gesture records one viewpoint.
+/// Restoring a viewpoint moves the `LookTransform`, so the transition is smoothed like any other motion.
+#[derive(Clone, Component, Debug)]
+pub struct ViewHistory {
+    /// The most viewpoints kept. The oldest are dropped first.
+    pub depth: usize,
+    /// Seconds the view must stay still before it's recorded
+    pub settle_seconds: f64,
+    views: Vec<LookTransform>,
+    current: usize,
+    last: Option<LookTransform>,
+    still_seconds: f64,
+}
+
+impl Default for ViewHistory {
+    fn default() -> Self {
+        Self::new(50)
+    }
+}
+
+impl ViewHistory {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            settle_seconds: 0.3,
+            views: Vec::new(),
+            current: 0,
+            last: None,
+            still_seconds: 0.0,
+        }
+    }
+
+    /// Records `view` as the current viewpoint, discarding any viewpoints ahead of the previous one.
+    pub fn record(&mut self, view: LookTransform) {
+        if self.views.get(self.current) == Some(&view) {
+            return;
+        }
+        self.views.truncate(self.current + 1);
+        self.views.push(view);
+        if self.views.len() > self.depth.max(1) {
+            self.views.remove(0);
+        }
+        self.current = self.views.len() - 1;
+    }
+
+    /// Steps back to the previous viewpoint and returns it.
+    pub fn back(&mut self) -> Option<LookTransform> {
+        self.current = self.current.checked_sub(1)?;
+        Some(self.views[self.current])
+    }
+
+    /// Steps forward to the next viewpoint and returns it.
+    pub fn forward(&mut self) -> Option<LookTransform> {
+        let view = *self.views.get(self.current + 1)?;
+        self.current += 1;
+        Some(view)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.current > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.current + 1 < self.views.len()
+    }
+}
+
+fn view_history_input_map(
+    mut events: EventWriter<ViewHistoryEvent>,
+    keyboard: Res<Input<KeyCode>>,
+    input_state: Res<CameraInputState>,
+    cameras: Query<Entity, With<ViewHistory>>,
+) {
+    if input_state.is_blocked() || !keyboard.any_pressed([KeyCode::LAlt, KeyCode::RAlt]) {
+        return;
+    }
+
+    for camera in cameras.iter() {
+        if keyboard.just_pressed(KeyCode::Left) {
+            events.send(ViewHistoryEvent::Back(camera));
+        }
+        if keyboard.just_pressed(KeyCode::Right) {
+            events.send(ViewHistoryEvent::Forward(camera));
+        }
+    }
+}
+
+fn view_history_system(
+    time: Res<Time>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    mut events: EventReader<ViewHistoryEvent>,
+    mut cameras: Query<(&mut ViewHistory, &mut LookTransform)>,
+) {
+    for event in events.iter() {
+        let (camera, back) = match *event {
+            ViewHistoryEvent::Back(camera) => (camera, true),
+            ViewHistoryEvent::Forward(camera) => (camera, false),
+        };
+        if let Ok((mut history, mut transform)) = cameras.get_mut(camera) {
+            let view = if back {
+                history.back()
+            } else {
+                history.forward()
+            };
+            if let Some(view) = view {
+                *transform = view;
+                // Don't record the restored view as a new one.
+                history.last = Some(view);
+                history.still_seconds = history.settle_seconds;
+            }
+        }
+    }
+
+    let dragging = mouse_buttons.get_pressed().next().is_some();
+    for (mut history, transform) in cameras.iter_mut() {
+        if history.last != Some(*transform) || dragging {
+            history.last = Some(*transform);
+            history.still_seconds = 0.0;
+            continue;
+        }
+        if history.still_seconds < history.settle_seconds {
+            history.still_seconds += time.delta_seconds_f64();
+            if history.still_seconds >= history.settle_seconds {
+                history.record(*transform);
+            }
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::math::DVec3;
+
+    #[test]
+    fn test_view_history() {
+        let view = |x| LookTransform::new(DVec3::new(x, 0.0, 1.0), DVec3::ZERO);
+        let mut history = ViewHistory::new(3);
+        for x in 0..4 {
+            history.record(view(x as f64));
+        }
+        // The first view was dropped.
+        assert_eq!(history.back(), Some(view(2.0)));
+        assert_eq!(history.back(), Some(view(1.0)));
+        assert_eq!(history.back(), None);
+
+        history.record(view(5.0));
+        assert!(!history.can_go_forward());
+        assert_eq!(history.back(), Some(view(1.0)));
+        assert_eq!(history.forward(), Some(view(5.0)));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{LookTransform, LookTransformSystem, SmoothedLookTransform, Smoother};
+
+use bevy::{app::prelude::*, ecs::prelude::*, math::prelude::*, transform::components::Transform};
+
+/// Turns every `ViewLocked` entity along with its camera each frame.
+pub struct ViewLockedPlugin;
+
+impl Plugin for ViewLockedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(view_locked_system.after(LookTransformSystem::Smooth));
+    }
+}
+
+/// Keeps this entity at a fixed pose relative to the view of `camera`, rotating around its target as the camera
+/// orbits. On a light, or the root of a rig of lights, the model stays lit from the same direction on screen while
+/// orbiting, like the turntables of CAD and product viewers. The entity should not have a parent.
+///
+/// Follows the smoothed pose of the camera, from its `SmoothedLookTransform` or else its `Smoother`, so the lighting
+/// stays in step with what's on screen.
+#[derive(Clone, Component, Copy, Debug)]
+pub struct ViewLocked {
+    pub camera: Entity,
+    /// Pose relative to the camera's rotation, with its translation relative to the target
+    pub offset: Transform,
+}
+
+impl ViewLocked {
+    pub fn new(camera: Entity, offset: Transform) -> Self {
+        Self { camera, offset }
+    }
+
+    /// Locks `transform` where it is now relative to the view of `look`, so it doesn't jump when locked.
+    pub fn keeping(camera: Entity, look: &LookTransform, transform: &Transform) -> Self {
+        let inverse = look.rotation().inverse();
+        Self::new(
+            camera,
+            Transform {
+                translation: inverse * (transform.translation - look.target),
+                rotation: inverse * transform.rotation,
+                scale: transform.scale,
+            },
+        )
+    }
+
+    /// Where the entity goes for the view of `look`.
+    pub fn locked_transform(&self, look: &LookTransform) -> Transform {
+        let rotation = look.rotation();
+        Transform {
+            translation: look.target + rotation * self.offset.translation,
+            rotation: rotation * self.offset.rotation,
+            scale: self.offset.scale,
+        }
+    }
+}
+
+fn view_locked_system(
+    cameras: Query<(
+        &LookTransform,
+        Option<&SmoothedLookTransform>,
+        Option<&Smoother>,
+    )>,
+    mut locked: Query<(&ViewLocked, &mut Transform)>,
+) {
+    for (lock, mut transform) in locked.iter_mut() {
+        let (look, smoothed, smoother) = if let Ok(camera) = cameras.get(lock.camera) {
+            camera
+        } else {
+            continue;
+        };
+        let look = smoothed
+            .map(|s| s.0)
+            .or_else(|| smoother.and_then(|s| s.smoothed_transform()))
+            .unwrap_or(*look);
+        let new_transform = lock.locked_transform(&look);
+        if *transform != new_transform {
+            *transform = new_transform;
+        }
+    }
+}
+
+// ████████╗███████╗███████╗████████╗
+// ╚══██╔══╝██╔════╝██╔════╝╚══██╔══╝
+//    ██║   █████╗  ███████╗   ██║
+//    ██║   ██╔══╝  ╚════██║   ██║
+//    ██║   ███████╗███████║   ██║
+//    ╚═╝   ╚══════╝╚══════╝   ╚═╝
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::*, LookTransformBundle};
+
+    #[test]
+    fn test_view_locked_follows_orbit() {
+        let look = LookTransform::new(DVec3::new(0.0, 0.0, 5.0), DVec3::ZERO);
+        let light = Transform::from_translation(DVec3::new(3.0, 4.0, 0.0))
+            .looking_at(DVec3::ZERO, DVec3::Y);
+        let lock = ViewLocked::keeping(Entity::from_raw(0), &look, &light);
+        assert!(lock
+            .locked_transform(&look)
+            .translation
+            .abs_diff_eq(light.translation, 1e-9));
+
+        // A quarter turn around the target carries the light along.
+        let orbited = LookTransform::new(DVec3::new(5.0, 0.0, 0.0), DVec3::ZERO);
+        let moved = lock.locked_transform(&orbited);
+        assert!(moved
+            .translation
+            .abs_diff_eq(DVec3::new(0.0, 4.0, -3.0), 1e-9));
+        let quarter_turn = DQuat::from_rotation_y(std::f64::consts::FRAC_PI_2);
+        let forward = moved.rotation * -DVec3::Z;
+        assert!(forward.abs_diff_eq(quarter_turn * (light.rotation * -DVec3::Z), 1e-9));
+    }
+
+    #[test]
+    fn test_view_locked_follows_smoother() {
+        let mut app = headless_app();
+        app.add_plugin(ViewLockedPlugin);
+        let camera = app
+            .world
+            .spawn(LookTransformBundle {
+                transform: LookTransform::new(DVec3::new(0.0, 0.0, 5.0), DVec3::ZERO),
+                smoother: Smoother::new(0.9),
+            })
+            .id();
+        let offset = Transform::from_translation(DVec3::X);
+        let light = app
+            .world
+            .spawn((ViewLocked::new(camera, offset), Transform::default()))
+            .id();
+        step(&mut app, 1);
+
+        app.world.get_mut::<LookTransform>(camera).unwrap().eye = DVec3::new(5.0, 0.0, 0.0);
+        step(&mut app, 1);
+
+        let smoothed = app
+            .world
+            .get::<Smoother>(camera)
+            .and_then(Smoother::smoothed_transform)
+            .unwrap();
+        let lock = app.world.get::<ViewLocked>(light).unwrap();
+        let expected = lock.locked_transform(&smoothed);
+        let unsmoothed = lock.locked_transform(&look_transform(&app, camera));
+        assert_eq!(scene_transform(&app, light), expected);
+        assert_ne!(expected, unsmoothed);
+    }
+}